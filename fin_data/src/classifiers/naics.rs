@@ -6,6 +6,9 @@ The data for this file was generated automatically.
 
 use std::collections::HashMap;
 
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
 use fin_model::classification::Code;
 use fin_model::prelude::*;
 
@@ -13,6 +16,7 @@ use fin_model::prelude::*;
 // Public Types
 // ------------------------------------------------------------------------------------------------
 
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Scheme {
     codes: HashMap<u32, Code<u32>>,
 }
@@ -8,6 +8,8 @@ values that may be returned from methods on _request traits_.
 
 extern crate chrono;
 extern crate fin_model;
+#[cfg(feature = "serde")]
+extern crate serde;
 
 // ------------------------------------------------------------------------------------------------
 // Trait Implementation Modules
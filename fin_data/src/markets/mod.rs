@@ -7,6 +7,9 @@ The data for this file was generated from
 
 use std::collections::HashMap;
 
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
 use fin_model::market::{Market, MarketStatus};
 use fin_model::prelude::*;
 
@@ -14,6 +17,7 @@ use fin_model::prelude::*;
 // Public Types
 // ------------------------------------------------------------------------------------------------
 
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ISORegistry {
     registry: HashMap<String, Market>,
 }
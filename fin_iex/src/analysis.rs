@@ -1,25 +1,21 @@
 use std::collections::HashMap;
-use std::str::FromStr;
 
 use serde;
 use serde::{Deserialize, Serialize};
 
 use fin_model::analysis::*;
 use fin_model::prelude::*;
-use fin_model::reporting::FinancialPeriod;
-use fin_model::symbol::is_valid;
 
 use crate::internal::convert::*;
-use crate::internal::metric::{record_api_use, ApiName};
-use crate::internal::request;
+use crate::internal::metric::{record_api_usage, record_api_use, ApiName};
 use crate::IEXProvider;
 
 // ------------------------------------------------------------------------------------------------
 // API Types (internal)
 // ------------------------------------------------------------------------------------------------
 
-#[serde(rename_all = "camelCase")]
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
 struct IEXPriceTarget {
     symbol: String,
     updated_date: String,
@@ -29,8 +25,8 @@ struct IEXPriceTarget {
     number_of_analysts: f64,
 }
 
-#[serde(rename_all = "camelCase")]
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
 struct IEXRecommendationTrends {
     consensus_end_date: f64,
     consensus_start_date: f64,
@@ -44,15 +40,15 @@ struct IEXRecommendationTrends {
     rating_scale_mark: f64,
 }
 
-#[serde(rename_all = "camelCase")]
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
 struct IEXEstimates {
     symbol: String,
     estimates: Vec<IEXEstimateData>,
 }
 
-#[serde(rename_all = "camelCase")]
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
 struct IEXEstimateData {
     #[serde(rename = "consensusEPS")]
     consensus_eps: f64,
@@ -62,6 +58,32 @@ struct IEXEstimateData {
     report_date: String,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct IEXEarnings {
+    symbol: String,
+    earnings: Vec<IEXEarningsData>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct IEXEarningsData {
+    #[serde(rename = "actualEPS")]
+    actual_eps: f64,
+    #[serde(rename = "consensusEPS")]
+    consensus_eps: Option<f64>,
+    announce_time: Option<String>,
+    number_of_estimates: Option<f64>,
+    #[serde(rename = "EPSSurpriseDollar")]
+    eps_surprise_dollar: Option<f64>,
+    #[serde(rename = "EPSReportDate")]
+    eps_report_date: String,
+    fiscal_period: String,
+    fiscal_end_date: Option<String>,
+    year_ago: Option<f64>,
+    year_ago_change_percent: Option<f64>,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Trait Implementations
 // ------------------------------------------------------------------------------------------------
@@ -73,11 +95,11 @@ impl Peers for IEXProvider {
 
         let api_url = self.make_api_url(format!("/stock/{}/peers", for_symbol), None);
 
-        let response: RequestResult<Symbols> = request::make_json_call(api_url);
+        let response: RequestResult<Vec<String>> = self.get_json(api_url);
         match response {
             Ok(values) => {
                 record_api_use(ApiName::Peers);
-                Ok(values)
+                Ok(values.iter().filter_map(|v| to_symbol(v)).collect())
             }
             Err(err) => {
                 println!("IEXProvider::<Peers>::peers returned error: {:?}", err);
@@ -97,7 +119,7 @@ impl AnalystRecommendations for IEXProvider {
 
         let api_url = self.make_api_url(format!("/stock/{}/price-target", for_symbol), None);
 
-        let response: RequestResult<IEXPriceTarget> = request::make_json_call(api_url);
+        let response: RequestResult<IEXPriceTarget> = self.get_json(api_url);
         let dc = self.get_default_currency();
         match response {
             Ok(target) => {
@@ -132,8 +154,7 @@ impl AnalystRecommendations for IEXProvider {
         let api_url =
             self.make_api_url(format!("/stock/{}/recommendation-trends", for_symbol), None);
 
-        let response: RequestResult<Vec<IEXRecommendationTrends>> =
-            request::make_json_call(api_url);
+        let response: RequestResult<Vec<IEXRecommendationTrends>> = self.get_json(api_url);
         match response {
             Ok(consensus) => {
                 record_api_use(ApiName::ConsensusRatings);
@@ -161,10 +182,10 @@ impl AnalystRecommendations for IEXProvider {
         );
         assert_is_valid!(for_symbol);
 
-        let api_url =
-            self.make_api_url(format!("/stock/{}/recommendation-trends", for_symbol), None);
+        // consensus estimates are returned by `estimates`, not `recommendation-trends`.
+        let api_url = self.make_api_url(format!("/stock/{}/estimates", for_symbol), None);
 
-        let response: RequestResult<IEXEstimates> = request::make_json_call(api_url);
+        let response: RequestResult<IEXEstimates> = self.get_json(api_url);
         let dc = self.get_default_currency();
         match response {
             Ok(estimates) => {
@@ -190,10 +211,52 @@ impl AnalystRecommendations for IEXProvider {
     }
 }
 
+impl FetchEarnings for IEXProvider {
+    fn earnings(&self, for_symbol: Symbol, last: usize) -> RequestResult<Vec<EarningsReport>> {
+        debug!(
+            "IEXProvider::<FetchEarnings>::earnings for_symbol: {}, last: {}",
+            for_symbol, last
+        );
+        assert_is_valid!(for_symbol);
+
+        let api_url = self.make_api_url(format!("/stock/{}/earnings/{}", for_symbol, last), None);
+
+        let response: RequestResult<IEXEarnings> = self.get_json(api_url);
+        let dc = self.get_default_currency();
+        match response {
+            Ok(earnings) => {
+                record_api_usage(ApiName::Earnings, earnings.earnings.len());
+                earnings
+                    .earnings
+                    .iter()
+                    .map(|v| to_earnings(dc, v))
+                    .collect()
+            }
+            Err(err) => {
+                warn!(
+                    "IEXProvider::<FetchEarnings>::earnings returning error: {:?}",
+                    err
+                );
+                Err(err)
+            }
+        }
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 // Private Implementations
 // ------------------------------------------------------------------------------------------------
 
+fn to_symbol(v: &str) -> Option<Symbol> {
+    match v.parse::<Symbol>() {
+        Ok(symbol) => Some(symbol),
+        Err(err) => {
+            warn!("ignoring invalid peer symbol: {}, error: {}", v, err);
+            None
+        }
+    }
+}
+
 fn to_rating(v: &IEXRecommendationTrends) -> RequestResult<Bounded<Ratings>> {
     let mut ratings: HashMap<RatingType, Counter> = HashMap::new();
     ratings.insert(RatingType::Buy, v.rating_buy as Counter);
@@ -216,8 +279,162 @@ fn to_estimate(dc: &String, v: &IEXEstimateData) -> RequestResult<EPSConsensus>
     Ok(EPSConsensus {
         consensus: price_from_float(dc, v.consensus_eps)?,
         number_of_estimates: v.number_of_estimates as Counter,
-        fiscal_period: FinancialPeriod::from_str(v.fiscal_period.as_str()).unwrap(),
+        fiscal_period: period_from_string(&v.fiscal_period)?,
         fiscal_end_date: date_from_string(&v.fiscal_end_date)?,
         next_report_date: date_from_string(&v.report_date)?,
     })
 }
+
+fn to_earnings(dc: &String, v: &IEXEarningsData) -> RequestResult<EarningsReport> {
+    let actual_eps = price_from_float(dc, v.actual_eps)?;
+    let consensus_eps = match v.consensus_eps {
+        Some(consensus_eps) => Some(price_from_float(dc, consensus_eps)?),
+        None => None,
+    };
+    // the surprise reported by IEX is preferred, the percentage is always that
+    // of the surprise stored so that the two agree.
+    let (surprise, surprise_percent) = match &consensus_eps {
+        Some(consensus_eps) => {
+            let actual = match v.eps_surprise_dollar {
+                Some(dollars) => *consensus_eps + price_from_float(dc, dollars)?,
+                None => actual_eps,
+            };
+            let (surprise, percent) = EarningsReport::surprise_of(&actual, consensus_eps);
+            (Some(surprise), percent)
+        }
+        None => (None, None),
+    };
+    Ok(EarningsReport {
+        actual_eps,
+        consensus_eps,
+        number_of_estimates: v.number_of_estimates.map(|n| n as Counter),
+        surprise,
+        surprise_percent,
+        fiscal_period: period_from_string(&v.fiscal_period)?,
+        fiscal_end_date: match &v.fiscal_end_date {
+            Some(date) if !date.is_empty() => Some(date_from_string(date)?),
+            _ => None,
+        },
+        report_date: date_from_string(&v.eps_report_date)?,
+        timing: match v.announce_time.as_deref() {
+            Some("BTO") => Some(ReportTiming::BeforeMarketOpen),
+            Some("DMT") => Some(ReportTiming::DuringMarketHours),
+            Some("AMC") => Some(ReportTiming::AfterMarketClose),
+            _ => None,
+        },
+    })
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::{to_earnings, IEXEarningsData};
+    use crate::fixture::replay_provider;
+    use fin_model::analysis::{
+        AnalystRecommendations, FetchEarnings, Peers, RatingType, ReportTiming,
+    };
+    use fin_model::prelude::*;
+    use fin_model::reporting::FinancialPeriod;
+    use steel_cent::currency::USD;
+
+    fn aapl() -> Symbol {
+        "AAPL".parse().unwrap()
+    }
+
+    #[test]
+    fn test_peers() {
+        let peers = replay_provider().peers(aapl()).unwrap();
+        assert_eq!(peers.len(), 3);
+        assert!(peers.contains(&"MSFT".parse::<Symbol>().unwrap()));
+    }
+
+    #[test]
+    fn test_target_price() {
+        let target = replay_provider().target_price(aapl()).unwrap();
+        assert_eq!(
+            target.date,
+            "2019-06-28T00:00:00".parse::<DateTime>().unwrap()
+        );
+        assert_eq!(target.data.average, Money::of_major_minor(USD, 212, 55));
+        assert_eq!(target.data.high, Money::of_major_minor(USD, 250, 25));
+        assert_eq!(target.data.low, Money::of_major_minor(USD, 160, 35));
+        assert_eq!(target.data.number_of_analysts, 37);
+    }
+
+    #[test]
+    fn test_consensus_rating() {
+        let ratings = replay_provider().consensus_rating(aapl()).unwrap();
+        assert_eq!(ratings.len(), 1);
+        let ratings = &ratings[0].data;
+        assert_eq!(ratings.ratings.get(&RatingType::Buy), Some(&20));
+        assert_eq!(ratings.ratings.get(&RatingType::Outperform), Some(&4));
+        assert_eq!(ratings.scale_mark, Some(1.85));
+    }
+
+    #[test]
+    fn test_consensus_eps() {
+        let estimates = replay_provider().consensus_eps(aapl()).unwrap();
+        assert_eq!(estimates.len(), 1);
+        assert_eq!(estimates[0].consensus, Money::of_major_minor(USD, 2, 15));
+        assert_eq!(estimates[0].number_of_estimates, 34);
+        assert_eq!(
+            estimates[0].fiscal_period,
+            FinancialPeriod::Quarter {
+                quarter: 3,
+                year: 2019
+            }
+        );
+        assert_eq!(
+            estimates[0].next_report_date,
+            "2019-07-30".parse::<Date>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_earnings() {
+        let earnings = replay_provider().earnings(aapl(), 2).unwrap();
+        assert_eq!(earnings.len(), 2);
+        let latest = &earnings[0];
+        assert_eq!(latest.actual_eps, Money::of_major_minor(USD, 2, 46));
+        assert_eq!(
+            latest.consensus_eps,
+            Some(Money::of_major_minor(USD, 2, 36))
+        );
+        assert_eq!(latest.surprise, Some(Money::of_major_minor(USD, 0, 10)));
+        assert!((latest.surprise_percent.unwrap() - 4.2373).abs() < 0.0001);
+        assert_eq!(latest.timing, Some(ReportTiming::AfterMarketClose));
+        assert_eq!(
+            latest.fiscal_period,
+            FinancialPeriod::Quarter {
+                quarter: 1,
+                year: 2019
+            }
+        );
+        assert_eq!(latest.report_date, "2019-04-30".parse::<Date>().unwrap());
+        assert_eq!(earnings[1].consensus_eps, None);
+        assert_eq!(earnings[1].surprise, None);
+    }
+
+    #[test]
+    fn test_earnings_reported_surprise() {
+        let data = IEXEarningsData {
+            actual_eps: 2.46,
+            consensus_eps: Some(2.36),
+            announce_time: None,
+            number_of_estimates: None,
+            eps_surprise_dollar: Some(0.12),
+            eps_report_date: "2019-04-30".to_string(),
+            fiscal_period: "Q1 2019".to_string(),
+            fiscal_end_date: None,
+            year_ago: None,
+            year_ago_change_percent: None,
+        };
+        let report = to_earnings(&"USD".to_string(), &data).unwrap();
+        assert_eq!(report.actual_eps, Money::of_major_minor(USD, 2, 46));
+        assert_eq!(report.surprise, Some(Money::of_major_minor(USD, 0, 12)));
+        assert!((report.surprise_percent.unwrap() - 5.0847).abs() < 0.0001);
+    }
+}
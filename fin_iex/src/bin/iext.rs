@@ -22,13 +22,16 @@ use fin_data::classifiers::uk_sic;
 use fin_data::classifiers::us_sic;
 use fin_data::markets::ISORegistry;
 
-use fin_iex::IEXProvider;
+use fin_model::provider::{Operation, Provider};
+
+use fin_iex::BudgetedProvider;
 
 enum Command {
-    Price(String),
-    Quote(String, bool),
-    Historical(String, String),
+    Price(Symbol),
+    Quote(Symbol, bool),
+    Historical(Symbol, String),
     Lookup(String, String),
+    Budget,
     None,
 }
 
@@ -39,7 +42,7 @@ fn main() {
     let cmd = handle_args();
 
     match cmd {
-        Command::Price(_) | Command::Quote(_, _) | Command::Historical(_, _) => {
+        Command::Price(_) | Command::Quote(_, _) | Command::Historical(_, _) | Command::Budget => {
             provider_commands(cmd)
         }
         Command::Lookup(_, _) => registry_commands(cmd),
@@ -50,16 +53,18 @@ fn main() {
 // ------------------------------------------------------------------------------------------------
 
 fn provider_commands(cmd: Command) {
-    let provider = match IEXProvider::new() {
+    let provider = match BudgetedProvider::new() {
         Ok(provider) => provider,
-        Err(RequestError::ConfigurationError(err)) => {
-            println!("Error configuring provider: {}", err);
-            return ();
-        }
-        Err(err) => {
-            println!("Unknown error from provider: {:?}", err);
-            return ();
-        }
+        Err(err) => match err.kind() {
+            RequestErrorKind::ConfigurationError(reason) => {
+                println!("Error configuring provider: {}", reason);
+                return;
+            }
+            _ => {
+                println!("Unknown error from provider: {}", err);
+                return;
+            }
+        },
     };
 
     let locale = SystemLocale::default().unwrap();
@@ -167,6 +172,10 @@ fn provider_commands(cmd: Command) {
                 }
             }
         }
+        Command::Budget => {
+            budget_details(&provider);
+            return;
+        }
         _ => (),
     }
 
@@ -176,9 +185,51 @@ fn provider_commands(cmd: Command) {
         provider.url()
     );
 
+    // without a usage file the counts only include this process.
+    if provider.budget().usage_file.is_some() {
+        let usage = provider.usage();
+        if let Some(remaining) = usage.remaining_today {
+            println!("Credits remaining today: {}", remaining);
+        }
+        if let Some(remaining) = usage.remaining_this_month {
+            println!("Credits remaining this month: {}", remaining);
+        }
+    }
+
     provider.finish();
 }
 
+fn budget_details(provider: &BudgetedProvider) {
+    let budget = provider.budget();
+    let limit = |value: Option<u64>| match value {
+        None => "unlimited".to_string(),
+        Some(v) => v.to_string(),
+    };
+    println!("  Daily credits: {}", limit(budget.daily_credits));
+    println!("Monthly credits: {}", limit(budget.monthly_credits));
+    println!(
+        "   Requests/sec: {}",
+        limit(budget.requests_per_second.map(|v| v as u64))
+    );
+    let usage = provider.usage();
+    println!("Remaining today: {}", limit(usage.remaining_today));
+    println!("Remaining month: {}", limit(usage.remaining_this_month));
+    match &budget.usage_file {
+        Some(file) => println!("     Usage file: {}", file.display()),
+        None => println!("     Usage file: none, usage only counts this process"),
+    }
+    let mut operations: Vec<Operation> = provider.capabilities().operations.into_iter().collect();
+    operations.sort();
+    let mut table = Table::new();
+    table.add_row(row!["Operation", "Credits"]);
+    for operation in operations {
+        if let Some(cost) = BudgetedProvider::cost_of(operation) {
+            table.add_row(row![format!("{:?}", operation), cost]);
+        }
+    }
+    table.printstd();
+}
+
 fn registry_commands(cmd: Command) {
     match cmd {
         Command::Lookup(scheme, code) => match scheme.as_str() {
@@ -225,7 +276,7 @@ fn registry_commands(cmd: Command) {
 
 extern crate clap;
 
-use clap::{App, Arg, SubCommand};
+use clap::{App, Arg, ArgMatches, SubCommand};
 
 fn handle_args() -> Command {
     let matches = App::new("iext")
@@ -276,6 +327,7 @@ fn handle_args() -> Command {
                         .index(1),
                 ),
         )
+        .subcommand(SubCommand::with_name("budget").about("Show the credit budget and API costs"))
         .subcommand(
             SubCommand::with_name("lookup")
                 .about("Lookup a code from within a classification scheme")
@@ -296,15 +348,21 @@ fn handle_args() -> Command {
         .get_matches();
 
     match matches.subcommand() {
-        ("price", Some(matches)) => Command::Price(matches.value_of("symbol").unwrap().to_string()),
-        ("quote", Some(matches)) => Command::Quote(
-            matches.value_of("symbol").unwrap().to_string(),
-            matches.is_present("delayed"),
-        ),
-        ("history", Some(matches)) => Command::Historical(
-            matches.value_of("symbol").unwrap().to_string(),
-            matches.value_of("interval").unwrap().to_string(),
-        ),
+        ("price", Some(matches)) => match symbol_arg(matches) {
+            Some(symbol) => Command::Price(symbol),
+            None => Command::None,
+        },
+        ("quote", Some(matches)) => match symbol_arg(matches) {
+            Some(symbol) => Command::Quote(symbol, matches.is_present("delayed")),
+            None => Command::None,
+        },
+        ("history", Some(matches)) => match symbol_arg(matches) {
+            Some(symbol) => {
+                Command::Historical(symbol, matches.value_of("interval").unwrap().to_string())
+            }
+            None => Command::None,
+        },
+        ("budget", Some(_)) => Command::Budget,
         ("lookup", Some(matches)) => Command::Lookup(
             matches.value_of("scheme").unwrap().to_string(),
             matches.value_of("code").unwrap().to_string(),
@@ -315,6 +373,17 @@ fn handle_args() -> Command {
         }
     }
 }
+
+fn symbol_arg(matches: &ArgMatches) -> Option<Symbol> {
+    let symbol = matches.value_of("symbol").unwrap();
+    match symbol.parse::<Symbol>() {
+        Ok(symbol) => Some(symbol),
+        Err(err) => {
+            println!("Invalid symbol {}: {}", symbol, err);
+            None
+        }
+    }
+}
 // ------------------------------------------------------------------------------------------------
 
 fn registry_details<C: std::fmt::Display, T>(registry: &dyn Registry<C, T>) {
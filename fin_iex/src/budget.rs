@@ -0,0 +1,981 @@
+/*!
+Credit budget and rate-limit enforcement for the IEX provider.
+
+IEX Cloud charges each call in _credits_, or messages, the cost depending
+on the endpoint and, for series and news, the number of items returned. The
+`BudgetedProvider` wraps an `IEXProvider` and, before issuing each call,
+checks the cost against a `CreditBudget` of daily and monthly credits, and
+takes a token from a requests-per-second token bucket.
+
+* A call that would exceed either credit budget is not issued, and returns
+  an error of kind `RequestErrorKind::QuotaExceeded` with a `retry_after`
+  value for the time at which the budget resets; daily budgets reset at
+  midnight UTC, monthly budgets on the first day of the month.
+* A call made when the token bucket is empty waits until a token is
+  available, so that calls never exceed the configured rate.
+* Calls that fail are not charged against the budget. Where the cost depends
+  on the number of items returned the call is checked against an estimate of
+  the items returned, the trading days in the requested interval (for series)
+  or the requested number of items (for news), and the budget is then charged
+  for the items actually returned.
+
+By default the credits used are held in memory, and do not include credits
+used by other processes using the same token. If a `usage_file` is set the
+credits used are read from, and written to, that file for each call so that
+the budget is enforced across processes, and runs of a tool; processes that
+share a file are not synchronized, so concurrent calls may both be issued.
+
+## Example
+
+```rust
+use fin_iex::{BudgetedProvider, CreditBudget, IEXConfig};
+
+let config = IEXConfig::new("Tpk_0123456789")
+    .with_host("sandbox")
+    .with_budget(
+        CreditBudget::default()
+            .with_monthly_credits(500_000)
+            .with_requests_per_second(10),
+    );
+let provider = BudgetedProvider::with_config(config).unwrap();
+assert_eq!(provider.usage().remaining_this_month, Some(500_000));
+```
+
+The budget may also be set in a configuration file,
+
+```toml
+token = "pk_0123456789"
+
+[budget]
+daily_credits = 20000
+monthly_credits = 500000
+requests_per_second = 10
+usage_file = "/home/me/.iex/usage.json"
+```
+
+or with the environment variables `IEX_DAILY_CREDITS`, `IEX_MONTHLY_CREDITS`,
+`IEX_REQUESTS_PER_SECOND`, and `IEX_USAGE_FILE`.
+*/
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use fin_model::analysis::*;
+use fin_model::company::*;
+use fin_model::corporate::*;
+use fin_model::news::*;
+use fin_model::options::*;
+use fin_model::prelude::*;
+use fin_model::provider::{Capabilities, Operation};
+use fin_model::quote::*;
+use fin_model::reporting::FinancialPeriod;
+
+use crate::config::IEXConfig;
+use crate::internal::env;
+use crate::internal::metric::{cost_of, ApiName};
+use crate::internal::request::PROVIDER_NAME;
+use crate::IEXProvider;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The limits enforced by a `BudgetedProvider`, any limit not set is not enforced.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct CreditBudget {
+    /// the number of credits that may be used each day (UTC)
+    #[serde(default)]
+    pub daily_credits: Option<u64>,
+    /// the number of credits that may be used each calendar month
+    #[serde(default)]
+    pub monthly_credits: Option<u64>,
+    /// the maximum number of calls issued each second
+    #[serde(default)]
+    pub requests_per_second: Option<u32>,
+    /// if set, the credits used are stored in, and shared through, this file
+    #[serde(default)]
+    pub usage_file: Option<PathBuf>,
+}
+
+/// The credits used, and remaining, in the current day and month.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BudgetUsage {
+    pub used_today: u64,
+    pub used_this_month: u64,
+    /// the credits remaining today, if a daily budget is set
+    pub remaining_today: Option<u64>,
+    /// the credits remaining this month, if a monthly budget is set
+    pub remaining_this_month: Option<u64>,
+}
+
+/// A wrapper around `IEXProvider` that enforces a `CreditBudget`.
+pub struct BudgetedProvider {
+    provider: IEXProvider,
+    budget: CreditBudget,
+    state: Mutex<BudgetState>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct BudgetState {
+    day: NaiveDate,
+    used_today: u64,
+    month: (i32, u32),
+    used_this_month: u64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// The content of a `CreditBudget::usage_file`.
+#[derive(Serialize, Deserialize, Debug)]
+struct StoredUsage {
+    day: String,
+    used_today: u64,
+    used_this_month: u64,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+const TRADING_DAYS_PER_YEAR: u64 = 253;
+const TRADING_MINUTES_PER_DAY: u64 = 390;
+
+const ENV_DAILY_CREDITS: &str = "IEX_DAILY_CREDITS";
+const ENV_MONTHLY_CREDITS: &str = "IEX_MONTHLY_CREDITS";
+const ENV_REQUESTS_PER_SECOND: &str = "IEX_REQUESTS_PER_SECOND";
+const ENV_USAGE_FILE: &str = "IEX_USAGE_FILE";
+
+impl CreditBudget {
+    /// Construct a budget from the environment variables `IEX_DAILY_CREDITS`,
+    /// `IEX_MONTHLY_CREDITS`, `IEX_REQUESTS_PER_SECOND`, and `IEX_USAGE_FILE`;
+    /// all are optional.
+    pub fn from_environment() -> RequestResult<Self> {
+        Ok(CreditBudget {
+            daily_credits: parse_environment(ENV_DAILY_CREDITS)?,
+            monthly_credits: parse_environment(ENV_MONTHLY_CREDITS)?,
+            requests_per_second: parse_environment(ENV_REQUESTS_PER_SECOND)?,
+            usage_file: env::get_from_environment(ENV_USAGE_FILE, None).map(PathBuf::from),
+        })
+    }
+
+    /// Set the number of credits that may be used each day.
+    pub fn with_daily_credits(self, daily_credits: u64) -> Self {
+        CreditBudget {
+            daily_credits: Some(daily_credits),
+            ..self
+        }
+    }
+
+    /// Set the number of credits that may be used each month.
+    pub fn with_monthly_credits(self, monthly_credits: u64) -> Self {
+        CreditBudget {
+            monthly_credits: Some(monthly_credits),
+            ..self
+        }
+    }
+
+    /// Set the maximum number of calls issued each second.
+    pub fn with_requests_per_second(self, requests_per_second: u32) -> Self {
+        CreditBudget {
+            requests_per_second: Some(requests_per_second),
+            ..self
+        }
+    }
+
+    /// Store the credits used in the file at `path`, so that they are shared
+    /// across processes.
+    pub fn with_usage_file<P: AsRef<Path>>(self, path: P) -> Self {
+        CreditBudget {
+            usage_file: Some(path.as_ref().to_path_buf()),
+            ..self
+        }
+    }
+
+    /// Returns `true` if no limits are set.
+    pub fn is_unlimited(&self) -> bool {
+        self.daily_credits.is_none()
+            && self.monthly_credits.is_none()
+            && self.requests_per_second.is_none()
+    }
+
+    /// Validate the budget, a rate of zero requests per second is invalid.
+    pub fn validate(&self) -> RequestResult<()> {
+        if self.requests_per_second == Some(0) {
+            return Err(RequestErrorKind::ConfigurationError(
+                "invalid value 0 for IEX requests_per_second".to_string(),
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+impl BudgetedProvider {
+    /// Wrap `provider`, enforcing `budget`.
+    pub fn with_budget(provider: IEXProvider, budget: CreditBudget) -> RequestResult<Self> {
+        budget.validate()?;
+        info!("BudgetedProvider::with_budget budget: {:?}", budget);
+        let mut state = BudgetState::new(&budget, Utc::now().naive_utc(), Instant::now());
+        if let Some(file) = &budget.usage_file {
+            state.load(file)?;
+        }
+        Ok(BudgetedProvider {
+            provider,
+            budget,
+            state: Mutex::new(state),
+        })
+    }
+
+    /// Construct a provider, and its budget, from an explicit configuration.
+    pub fn with_config(config: IEXConfig) -> RequestResult<Self> {
+        let budget = config.budget.clone();
+        BudgetedProvider::with_budget(IEXProvider::with_config(config)?, budget)
+    }
+
+    /// The budget enforced by this provider.
+    pub fn budget(&self) -> &CreditBudget {
+        &self.budget
+    }
+
+    /// The credits used, and remaining, in the current day and month; these
+    /// include credits used by other processes only if a `usage_file` is set.
+    pub fn usage(&self) -> BudgetUsage {
+        let mut state = self.state.lock().unwrap();
+        if let Some(file) = &self.budget.usage_file {
+            if let Err(err) = state.load(file) {
+                warn!("BudgetedProvider::usage could not read usage: {}", err);
+            }
+        }
+        state.roll_over(Utc::now().naive_utc());
+        state.usage(&self.budget)
+    }
+
+    /// The wrapped provider.
+    pub fn inner(&self) -> &IEXProvider {
+        &self.provider
+    }
+
+    /// As `IEXProvider::unadjusted_last`, charged against the budget.
+    pub fn unadjusted_last(
+        &self,
+        for_symbol: Symbol,
+        interval: SeriesInterval,
+    ) -> RequestResult<PriceRangeSeries> {
+        self.call(
+            ApiName::Historical,
+            trading_days(interval),
+            |p| p.unadjusted_last(for_symbol, interval),
+            |series| series.series.len() as u64,
+        )
+    }
+
+    /// The cost, in credits, of a single call to, or item returned by,
+    /// `operation`, summed over all the calls it makes; `None` if the
+    /// operation is not implemented. The cost of `OptionExpirations` is
+    /// that of a single month, each month listed costs an `OptionChain` call.
+    pub fn cost_of(operation: Operation) -> Option<u64> {
+        let apis = apis_for(operation);
+        if apis.is_empty() {
+            None
+        } else {
+            Some(apis.iter().map(cost_of).sum())
+        }
+    }
+
+    /// Charge `items` of `api` against the budget, wait for a token, and then
+    /// call `f`; the charge is adjusted by `actual_items` once the call returns.
+    fn call<T, F, A>(&self, api: ApiName, items: u64, f: F, actual_items: A) -> RequestResult<T>
+    where
+        F: FnOnce(&IEXProvider) -> RequestResult<T>,
+        A: FnOnce(&T) -> u64,
+    {
+        let cost = cost_of(&api) * items;
+        let wait = {
+            let mut state = self.state.lock().unwrap();
+            if let Some(file) = &self.budget.usage_file {
+                state.load(file)?;
+            }
+            state.roll_over(Utc::now().naive_utc());
+            state.charge(&self.budget, cost)?;
+            if let Some(file) = &self.budget.usage_file {
+                state.store(file)?;
+            }
+            match self.budget.requests_per_second {
+                Some(rate) => state.take_token(rate, Instant::now()),
+                None => Duration::from_secs(0),
+            }
+        };
+        if wait > Duration::from_secs(0) {
+            debug!("BudgetedProvider::call waiting {:?} for {:?}", wait, api);
+            thread::sleep(wait);
+        }
+        let result = f(&self.provider);
+        let actual_cost = match &result {
+            Ok(value) => cost_of(&api) * actual_items(value),
+            Err(_) => 0,
+        };
+        let mut state = self.state.lock().unwrap();
+        if let Some(file) = &self.budget.usage_file {
+            let stored = state.load(file).and_then(|_| {
+                state.adjust(cost, actual_cost);
+                state.store(file)
+            });
+            if let Err(err) = stored {
+                warn!("BudgetedProvider::call could not update usage: {}", err);
+            }
+        } else {
+            state.adjust(cost, actual_cost);
+        }
+        result
+    }
+}
+
+impl BudgetState {
+    fn new(budget: &CreditBudget, now: NaiveDateTime, instant: Instant) -> Self {
+        BudgetState {
+            day: now.date(),
+            used_today: 0,
+            month: (now.year(), now.month()),
+            used_this_month: 0,
+            tokens: budget.requests_per_second.unwrap_or(0) as f64,
+            last_refill: instant,
+        }
+    }
+
+    /// Reset the daily and monthly counters if `now` is in a new day or month.
+    fn roll_over(&mut self, now: NaiveDateTime) {
+        if now.date() != self.day {
+            self.day = now.date();
+            self.used_today = 0;
+        }
+        if (now.year(), now.month()) != self.month {
+            self.month = (now.year(), now.month());
+            self.used_this_month = 0;
+        }
+    }
+
+    /// Add `cost` to the counters, unless it would exceed either budget.
+    fn charge(&mut self, budget: &CreditBudget, cost: u64) -> RequestResult<()> {
+        if let Some(daily) = budget.daily_credits {
+            if self.used_today + cost > daily {
+                warn!(
+                    "BudgetedProvider daily budget exceeded, used: {}, cost: {}, budget: {}",
+                    self.used_today, cost, daily
+                );
+                let tomorrow = self.day + chrono::Duration::days(1);
+                return Err(quota_exceeded(tomorrow));
+            }
+        }
+        if let Some(monthly) = budget.monthly_credits {
+            if self.used_this_month + cost > monthly {
+                warn!(
+                    "BudgetedProvider monthly budget exceeded, used: {}, cost: {}, budget: {}",
+                    self.used_this_month, cost, monthly
+                );
+                let next_month = match self.month {
+                    (year, 12) => NaiveDate::from_ymd_opt(year + 1, 1, 1),
+                    (year, month) => NaiveDate::from_ymd_opt(year, month + 1, 1),
+                };
+                return Err(quota_exceeded(next_month.unwrap()));
+            }
+        }
+        self.used_today += cost;
+        self.used_this_month += cost;
+        Ok(())
+    }
+
+    /// Replace the counters with those stored in `file`, if it exists.
+    fn load(&mut self, file: &Path) -> RequestResult<()> {
+        let content = match fs::read_to_string(file) {
+            Ok(content) => content,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(usage_error(file, err)),
+        };
+        let stored: StoredUsage =
+            serde_json::from_str(&content).map_err(|err| usage_error(file, err))?;
+        let day: NaiveDate = stored.day.parse().map_err(|err| usage_error(file, err))?;
+        self.day = day;
+        self.used_today = stored.used_today;
+        self.month = (day.year(), day.month());
+        self.used_this_month = stored.used_this_month;
+        Ok(())
+    }
+
+    /// Write the counters to `file`.
+    fn store(&self, file: &Path) -> RequestResult<()> {
+        let stored = StoredUsage {
+            day: self.day.to_string(),
+            used_today: self.used_today,
+            used_this_month: self.used_this_month,
+        };
+        let content = serde_json::to_string(&stored).map_err(|err| usage_error(file, err))?;
+        fs::write(file, content).map_err(|err| usage_error(file, err))
+    }
+
+    /// Replace a `charged` cost with the `actual` cost.
+    fn adjust(&mut self, charged: u64, actual: u64) {
+        self.used_today = (self.used_today + actual).saturating_sub(charged);
+        self.used_this_month = (self.used_this_month + actual).saturating_sub(charged);
+    }
+
+    /// Take a token from the bucket, returning the time to wait before the
+    /// token is available; the bucket holds at most one second of tokens.
+    fn take_token(&mut self, rate: u32, now: Instant) -> Duration {
+        let rate = rate as f64;
+        let elapsed = now.duration_since(self.last_refill);
+        let elapsed = elapsed.as_secs() as f64 + elapsed.subsec_nanos() as f64 / 1e9;
+        self.tokens = (self.tokens + elapsed * rate).min(rate) - 1.0;
+        self.last_refill = now;
+        if self.tokens >= 0.0 {
+            Duration::from_secs(0)
+        } else {
+            let wait = -self.tokens / rate;
+            Duration::new(wait.trunc() as u64, (wait.fract() * 1e9) as u32)
+        }
+    }
+
+    fn usage(&self, budget: &CreditBudget) -> BudgetUsage {
+        BudgetUsage {
+            used_today: self.used_today,
+            used_this_month: self.used_this_month,
+            remaining_today: budget
+                .daily_credits
+                .map(|daily| daily.saturating_sub(self.used_today)),
+            remaining_this_month: budget
+                .monthly_credits
+                .map(|monthly| monthly.saturating_sub(self.used_this_month)),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Trait Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Provider for BudgetedProvider {
+    fn new() -> RequestResult<Self> {
+        BudgetedProvider::with_config(IEXConfig::from_environment()?)
+    }
+
+    fn attribution(&self) -> String {
+        self.provider.attribution()
+    }
+
+    fn url(&self) -> String {
+        self.provider.url()
+    }
+
+    fn get_default_currency(&self) -> &String {
+        self.provider.get_default_currency()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.provider.capabilities()
+    }
+
+    fn finish(&self) {
+        info!("BudgetedProvider::finish usage: {:?}", self.usage());
+        self.provider.finish()
+    }
+}
+
+impl Peers for BudgetedProvider {
+    fn peers(&self, for_symbol: Symbol) -> RequestResult<Symbols> {
+        self.call(ApiName::Peers, 1, |p| p.peers(for_symbol), |_| 1)
+    }
+}
+
+impl AnalystRecommendations for BudgetedProvider {
+    fn target_price(&self, for_symbol: Symbol) -> RequestResult<Snapshot<PriceTarget>> {
+        self.call(
+            ApiName::TargetPrice,
+            1,
+            |p| p.target_price(for_symbol),
+            |_| 1,
+        )
+    }
+
+    fn consensus_rating(&self, for_symbol: Symbol) -> RequestResult<Vec<Bounded<Ratings>>> {
+        self.call(
+            ApiName::ConsensusRatings,
+            1,
+            |p| p.consensus_rating(for_symbol),
+            |_| 1,
+        )
+    }
+
+    fn consensus_eps(&self, for_symbol: Symbol) -> RequestResult<Vec<EPSConsensus>> {
+        self.call(
+            ApiName::ConsensusEPS,
+            1,
+            |p| p.consensus_eps(for_symbol),
+            |_| 1,
+        )
+    }
+}
+
+impl FetchEarnings for BudgetedProvider {
+    fn earnings(&self, for_symbol: Symbol, last: usize) -> RequestResult<Vec<EarningsReport>> {
+        self.call(
+            ApiName::Earnings,
+            last as u64,
+            |p| p.earnings(for_symbol, last),
+            |earnings| earnings.len() as u64,
+        )
+    }
+}
+
+impl FetchCompanyFinancials for BudgetedProvider {
+    fn reported_income(
+        &self,
+        for_symbol: Symbol,
+        last: u8,
+        period: ReportingPeriod,
+    ) -> RequestResult<Vec<Snapshot<IncomeStatement>>> {
+        self.call(
+            ApiName::Income,
+            u64::from(last),
+            |p| p.reported_income(for_symbol, last, period),
+            |statements| statements.len() as u64,
+        )
+    }
+
+    fn reported_balance_sheet(
+        &self,
+        for_symbol: Symbol,
+        last: u8,
+        period: ReportingPeriod,
+    ) -> RequestResult<Vec<Snapshot<BalanceSheet>>> {
+        self.call(
+            ApiName::BalanceSheet,
+            u64::from(last),
+            |p| p.reported_balance_sheet(for_symbol, last, period),
+            |statements| statements.len() as u64,
+        )
+    }
+
+    fn reported_cash_flow(
+        &self,
+        for_symbol: Symbol,
+        last: u8,
+        period: ReportingPeriod,
+    ) -> RequestResult<Vec<Snapshot<CashFlowStatement>>> {
+        self.call(
+            ApiName::CashFlow,
+            u64::from(last),
+            |p| p.reported_cash_flow(for_symbol, last, period),
+            |statements| statements.len() as u64,
+        )
+    }
+}
+
+impl FetchCompanyStatistics for BudgetedProvider {
+    fn core_statistics(&self, for_symbol: Symbol) -> RequestResult<Statistics> {
+        self.call(ApiName::Stats, 1, |p| p.core_statistics(for_symbol), |_| 1)
+    }
+}
+
+impl FetchCorporateActions for BudgetedProvider {
+    fn dividends(
+        &self,
+        for_symbol: Symbol,
+        interval: SeriesInterval,
+    ) -> RequestResult<Vec<Dividend>> {
+        self.call(
+            ApiName::Dividends,
+            quarters(interval),
+            |p| p.dividends(for_symbol, interval),
+            |dividends| dividends.len() as u64,
+        )
+    }
+
+    fn splits(&self, for_symbol: Symbol, interval: SeriesInterval) -> RequestResult<Vec<Split>> {
+        self.call(
+            ApiName::Splits,
+            1,
+            |p| p.splits(for_symbol, interval),
+            |splits| splits.len() as u64,
+        )
+    }
+
+    fn corporate_actions(
+        &self,
+        for_symbol: Symbol,
+        interval: SeriesInterval,
+    ) -> RequestResult<Vec<CorporateAction>> {
+        let dividends = self.dividends(for_symbol.clone(), interval)?;
+        let splits = self.splits(for_symbol, interval)?;
+        Ok(crate::corporate::combine(dividends, splits))
+    }
+}
+
+impl FetchNews for BudgetedProvider {
+    fn latest_news(&self, for_symbol: Symbol, max_items: usize) -> RequestResult<NewsFeed> {
+        self.call(
+            ApiName::NewsFeed,
+            max_items as u64,
+            |p| p.latest_news(for_symbol, max_items),
+            |feed| feed.len() as u64,
+        )
+    }
+
+    fn news_from(
+        &self,
+        for_symbol: Symbol,
+        start_date: Date,
+        max_items: usize,
+    ) -> RequestResult<NewsFeed> {
+        self.provider.news_from(for_symbol, start_date, max_items)
+    }
+}
+
+impl FetchOptionChain for BudgetedProvider {
+    fn expirations(&self, for_symbol: Symbol) -> RequestResult<Vec<Date>> {
+        let months = self.call(
+            ApiName::OptionExpirations,
+            1,
+            |p| p.expiration_months(&for_symbol),
+            |_| 1,
+        )?;
+        let mut expirations: Vec<Date> = Vec::new();
+        for month in months {
+            expirations.extend(self.call(
+                ApiName::Options,
+                1,
+                |p| p.month_expirations(&for_symbol, &month),
+                |_| 1,
+            )?);
+        }
+        expirations.sort();
+        expirations.dedup();
+        Ok(expirations)
+    }
+
+    fn option_chain(
+        &self,
+        for_symbol: Symbol,
+        expiration: Date,
+    ) -> RequestResult<Snapshot<OptionChain>> {
+        self.call(
+            ApiName::Options,
+            1,
+            |p| p.option_chain(for_symbol, expiration),
+            |_| 1,
+        )
+    }
+}
+
+impl FetchPriceQuote for BudgetedProvider {
+    fn latest_price_only(&self, for_symbol: Symbol) -> RequestResult<Money> {
+        self.call(
+            ApiName::Price,
+            1,
+            |p| p.latest_price_only(for_symbol),
+            |_| 1,
+        )
+    }
+
+    fn real_time(&self, for_symbol: Symbol) -> RequestResult<Quote> {
+        self.call(ApiName::Quote, 1, |p| p.real_time(for_symbol), |_| 1)
+    }
+
+    fn delayed(&self, for_symbol: Symbol) -> RequestResult<DelayedQuote> {
+        self.call(ApiName::DelayedQuote, 1, |p| p.delayed(for_symbol), |_| 1)
+    }
+}
+
+impl FetchPriceRangeSeries for BudgetedProvider {
+    fn intra_day(
+        &self,
+        for_symbol: Symbol,
+        interval_minutes: u8,
+    ) -> RequestResult<Option<PriceRangeSeries>> {
+        self.call(
+            ApiName::Intraday,
+            intra_day_points(interval_minutes),
+            |p| p.intra_day(for_symbol, interval_minutes),
+            |series| match series {
+                Some(series) => series.series.len() as u64,
+                None => 0,
+            },
+        )
+    }
+
+    fn last(
+        &self,
+        for_symbol: Symbol,
+        interval: SeriesInterval,
+    ) -> RequestResult<PriceRangeSeries> {
+        self.call(
+            ApiName::Historical,
+            trading_days(interval),
+            |p| p.last(for_symbol, interval),
+            |series| series.series.len() as u64,
+        )
+    }
+
+    fn from(
+        &self,
+        for_symbol: Symbol,
+        start_date: DateTime,
+        interval: SeriesInterval,
+    ) -> RequestResult<PriceRangeSeries> {
+        self.provider.from(for_symbol, start_date, interval)
+    }
+
+    fn for_period(
+        &self,
+        for_symbol: Symbol,
+        period: FinancialPeriod,
+    ) -> RequestResult<PriceRangeSeries> {
+        self.provider.for_period(for_symbol, period)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn apis_for(operation: Operation) -> Vec<ApiName> {
+    match operation {
+        Operation::Peers => vec![ApiName::Peers],
+        Operation::TargetPrice => vec![ApiName::TargetPrice],
+        Operation::ConsensusRating => vec![ApiName::ConsensusRatings],
+        Operation::ConsensusEPS => vec![ApiName::ConsensusEPS],
+        Operation::Earnings => vec![ApiName::Earnings],
+        Operation::ReportedIncome => vec![ApiName::Income],
+        Operation::ReportedBalanceSheet => vec![ApiName::BalanceSheet],
+        Operation::ReportedCashFlow => vec![ApiName::CashFlow],
+        Operation::CoreStatistics => vec![ApiName::Stats],
+        Operation::LatestNews => vec![ApiName::NewsFeed],
+        Operation::OptionExpirations => vec![ApiName::OptionExpirations, ApiName::Options],
+        Operation::OptionChain => vec![ApiName::Options],
+        Operation::LatestPriceOnly => vec![ApiName::Price],
+        Operation::RealTime => vec![ApiName::Quote],
+        Operation::Delayed => vec![ApiName::DelayedQuote],
+        Operation::IntraDay => vec![ApiName::Intraday],
+        Operation::Last => vec![ApiName::Historical],
+        Operation::Dividends => vec![ApiName::Dividends],
+        Operation::Splits => vec![ApiName::Splits],
+        Operation::CorporateActions => vec![ApiName::Dividends, ApiName::Splits],
+        Operation::About
+        | Operation::Filings
+        | Operation::NewsFrom
+        | Operation::LatestCategoryNews
+        | Operation::CategoryNewsFrom
+        | Operation::RealTimeBatch
+        | Operation::DelayedBatch
+        | Operation::From
+        | Operation::ForPeriod => Vec::new(),
+    }
+}
+
+/// The number of trading days, at most, in `interval`.
+fn trading_days(interval: SeriesInterval) -> u64 {
+    match interval {
+        SeriesInterval::Day => 1,
+        SeriesInterval::FiveDays => 5,
+        SeriesInterval::OneMonth => 23,
+        SeriesInterval::ThreeMonths => 66,
+        SeriesInterval::SixMonths => 131,
+        SeriesInterval::YearToDate => {
+            u64::from(Utc::now().naive_utc().ordinal()) * TRADING_DAYS_PER_YEAR / 365 + 1
+        }
+        SeriesInterval::OneYear => TRADING_DAYS_PER_YEAR,
+        SeriesInterval::TwoYears => TRADING_DAYS_PER_YEAR * 2,
+        SeriesInterval::FiveYears => TRADING_DAYS_PER_YEAR * 5,
+    }
+}
+
+/// The number of quarters, rounded up, in `interval`; an estimate of the
+/// number of dividends paid.
+fn quarters(interval: SeriesInterval) -> u64 {
+    match interval {
+        SeriesInterval::Day
+        | SeriesInterval::FiveDays
+        | SeriesInterval::OneMonth
+        | SeriesInterval::ThreeMonths => 1,
+        SeriesInterval::SixMonths => 2,
+        SeriesInterval::YearToDate | SeriesInterval::OneYear => 4,
+        SeriesInterval::TwoYears => 8,
+        SeriesInterval::FiveYears => 20,
+    }
+}
+
+/// The number of data points in a trading session at `interval_minutes`.
+fn intra_day_points(interval_minutes: u8) -> u64 {
+    let interval_minutes = u64::from(interval_minutes.max(1));
+    TRADING_MINUTES_PER_DAY.div_ceil(interval_minutes)
+}
+
+fn parse_environment<T: std::str::FromStr>(env_key: &'static str) -> RequestResult<Option<T>> {
+    match env::get_from_environment(env_key, None) {
+        Some(value) => match value.trim().parse::<T>() {
+            Ok(value) => Ok(Some(value)),
+            Err(_) => Err(RequestErrorKind::ConfigurationError(format!(
+                "invalid value {} for {}",
+                value, env_key
+            ))
+            .into()),
+        },
+        None => Ok(None),
+    }
+}
+
+fn usage_error<E>(file: &Path, err: E) -> RequestError
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    RequestError::new(RequestErrorKind::ConfigurationError(format!(
+        "could not use IEX usage file {:?}",
+        file
+    )))
+    .with_source(err)
+}
+
+/// A `QuotaExceeded` error, to be retried when the budget resets on `resets`.
+fn quota_exceeded(resets: NaiveDate) -> RequestError {
+    let resets = resets.and_hms_opt(0, 0, 0).unwrap();
+    let error = RequestError::new(RequestErrorKind::QuotaExceeded).with_provider(PROVIDER_NAME);
+    match (resets - Utc::now().naive_utc()).to_std() {
+        Ok(retry_after) => error.with_retry_after(retry_after),
+        Err(_) => error,
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::{intra_day_points, quarters, trading_days};
+    use super::{BudgetState, BudgetedProvider, CreditBudget};
+    use chrono::NaiveDate;
+    use fin_model::provider::Operation;
+    use fin_model::quote::SeriesInterval;
+    use fin_model::request::RequestErrorKind;
+    use std::env::temp_dir;
+    use std::fs;
+    use std::time::{Duration, Instant};
+
+    fn at(y: i32, m: u32, d: u32, h: u32) -> chrono::NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_credit_budget() {
+        let budget = CreditBudget::default()
+            .with_daily_credits(1_000)
+            .with_monthly_credits(1_500);
+        let mut state = BudgetState::new(&budget, at(2019, 6, 29, 9), Instant::now());
+
+        assert!(state.charge(&budget, 500).is_ok());
+        assert!(state.charge(&budget, 500).is_ok());
+        let error = state.charge(&budget, 1).err().unwrap();
+        assert_eq!(error.kind(), &RequestErrorKind::QuotaExceeded);
+        assert_eq!(error.provider(), Some("IEX Cloud"));
+
+        // a new day resets the daily, but not the monthly, budget.
+        state.roll_over(at(2019, 6, 30, 9));
+        assert_eq!(state.usage(&budget).remaining_today, Some(1_000));
+        assert_eq!(state.usage(&budget).remaining_this_month, Some(500));
+        assert!(state.charge(&budget, 501).is_err());
+
+        // failed calls are refunded, and item counts adjusted.
+        assert!(state.charge(&budget, 500).is_ok());
+        state.adjust(500, 0);
+        assert_eq!(state.usage(&budget).used_this_month, 1_000);
+
+        state.roll_over(at(2019, 7, 1, 0));
+        assert_eq!(state.usage(&budget).used_this_month, 0);
+        assert!(state.charge(&budget, 1_000).is_ok());
+    }
+
+    #[test]
+    fn test_usage_file() {
+        let file = temp_dir().join("fin_iex_test_usage.json");
+        let _ = fs::remove_file(&file);
+        let budget = CreditBudget::default()
+            .with_daily_credits(1_000)
+            .with_usage_file(&file);
+
+        let mut state = BudgetState::new(&budget, at(2019, 6, 29, 9), Instant::now());
+        state.load(&file).unwrap();
+        assert!(state.charge(&budget, 600).is_ok());
+        state.store(&file).unwrap();
+
+        // a second process sees the credits used by the first.
+        let mut other = BudgetState::new(&budget, at(2019, 6, 29, 10), Instant::now());
+        other.load(&file).unwrap();
+        assert_eq!(other.usage(&budget).remaining_today, Some(400));
+        assert!(other.charge(&budget, 600).is_err());
+
+        other.roll_over(at(2019, 6, 30, 9));
+        assert_eq!(other.usage(&budget).remaining_today, Some(1_000));
+        assert_eq!(other.usage(&budget).used_this_month, 600);
+
+        fs::write(&file, "not usage").unwrap();
+        assert!(other.load(&file).is_err());
+        fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn test_token_bucket() {
+        let budget = CreditBudget::default().with_requests_per_second(2);
+        let start = Instant::now();
+        let mut state = BudgetState::new(&budget, at(2019, 6, 29, 9), start);
+
+        assert_eq!(state.take_token(2, start), Duration::from_secs(0));
+        assert_eq!(state.take_token(2, start), Duration::from_secs(0));
+        assert_eq!(state.take_token(2, start), Duration::from_millis(500));
+        assert_eq!(
+            state.take_token(2, start + Duration::from_millis(500)),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            state.take_token(2, start + Duration::from_secs(5)),
+            Duration::from_secs(0)
+        );
+
+        assert!(CreditBudget::default()
+            .with_requests_per_second(0)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_estimated_items() {
+        assert_eq!(trading_days(SeriesInterval::OneYear), 253);
+        assert!(trading_days(SeriesInterval::YearToDate) <= 254);
+        assert_eq!(quarters(SeriesInterval::SixMonths), 2);
+        assert_eq!(intra_day_points(1), 390);
+        assert_eq!(intra_day_points(0), 390);
+        assert_eq!(intra_day_points(60), 7);
+    }
+
+    #[test]
+    fn test_cost_of() {
+        assert_eq!(BudgetedProvider::cost_of(Operation::Peers), Some(500));
+        assert_eq!(BudgetedProvider::cost_of(Operation::LatestNews), Some(10));
+        assert_eq!(
+            BudgetedProvider::cost_of(Operation::CorporateActions),
+            Some(20)
+        );
+        assert_eq!(BudgetedProvider::cost_of(Operation::About), None);
+    }
+}
@@ -0,0 +1,735 @@
+/*!
+IEX API wrapper
+*/
+
+use std::collections::HashMap;
+
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+
+use fin_model::company::*;
+use fin_model::prelude::*;
+use fin_model::reporting::{FinancialPeriod, FiscalPeriod};
+
+use crate::internal::convert::*;
+use crate::internal::metric::{record_api_usage, ApiName};
+use crate::IEXProvider;
+
+// ------------------------------------------------------------------------------------------------
+// API Types (internal)
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct IEXStatementInfo {
+    report_date: String,
+    fiscal_date: Option<String>,
+    fiscal_quarter: Option<u8>,
+    fiscal_year: Option<u16>,
+    currency: Option<String>,
+    filing_type: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct IEXIncomeStatements {
+    symbol: String,
+    income: Vec<IEXIncomeStatement>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct IEXIncomeStatement {
+    #[serde(flatten)]
+    info: IEXStatementInfo,
+    total_revenue: Option<f64>,
+    cost_of_revenue: Option<f64>,
+    gross_profit: Option<f64>,
+    research_and_development: Option<f64>,
+    selling_general_and_admin: Option<f64>,
+    operating_expense: Option<f64>,
+    operating_income: Option<f64>,
+    other_income_expense_net: Option<f64>,
+    ebit: Option<f64>,
+    interest_income: Option<f64>,
+    pretax_income: Option<f64>,
+    income_tax: Option<f64>,
+    minority_interest: Option<f64>,
+    net_income: Option<f64>,
+    net_income_basic: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct IEXBalanceSheets {
+    symbol: String,
+    balancesheet: Vec<IEXBalanceSheet>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct IEXBalanceSheet {
+    #[serde(flatten)]
+    info: IEXStatementInfo,
+    current_cash: Option<f64>,
+    short_term_investments: Option<f64>,
+    receivables: Option<f64>,
+    inventory: Option<f64>,
+    other_current_assets: Option<f64>,
+    current_assets: Option<f64>,
+    long_term_investments: Option<f64>,
+    property_plant_equipment: Option<f64>,
+    goodwill: Option<f64>,
+    intangible_assets: Option<f64>,
+    other_assets: Option<f64>,
+    total_assets: Option<f64>,
+    accounts_payable: Option<f64>,
+    current_long_term_debt: Option<f64>,
+    other_current_liabilities: Option<f64>,
+    total_current_liabilities: Option<f64>,
+    long_term_debt: Option<f64>,
+    other_liabilities: Option<f64>,
+    minority_interest: Option<f64>,
+    total_liabilities: Option<f64>,
+    common_stock: Option<f64>,
+    retained_earnings: Option<f64>,
+    treasury_stock: Option<f64>,
+    capital_surplus: Option<f64>,
+    shareholder_equity: Option<f64>,
+    net_tangible_assets: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct IEXCashFlowStatements {
+    symbol: String,
+    cashflow: Vec<IEXCashFlowStatement>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct IEXCashFlowStatement {
+    #[serde(flatten)]
+    info: IEXStatementInfo,
+    net_income: Option<f64>,
+    depreciation: Option<f64>,
+    changes_in_receivables: Option<f64>,
+    changes_in_inventories: Option<f64>,
+    cash_change: Option<f64>,
+    cash_flow: Option<f64>,
+    capital_expenditures: Option<f64>,
+    investments: Option<f64>,
+    investing_activity_other: Option<f64>,
+    total_investing_cash_flows: Option<f64>,
+    dividends_paid: Option<f64>,
+    net_borrowings: Option<f64>,
+    other_financing_cash_flows: Option<f64>,
+    cash_flow_financing: Option<f64>,
+    exchange_rate_effect: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct IEXStats {
+    marketcap: Option<f64>,
+    week52high: Option<f64>,
+    week52low: Option<f64>,
+    week52change: Option<f64>,
+    shares_outstanding: Option<f64>,
+    float: Option<f64>,
+    avg10_volume: Option<f64>,
+    avg30_volume: Option<f64>,
+    day200_moving_avg: Option<f64>,
+    day50_moving_avg: Option<f64>,
+    #[serde(rename = "ttmEPS")]
+    ttm_eps: Option<f64>,
+    ttm_dividend_rate: Option<f64>,
+    dividend_yield: Option<f64>,
+    next_dividend_date: Option<String>,
+    ex_dividend_date: Option<String>,
+    next_earnings_date: Option<String>,
+    pe_ratio: Option<f64>,
+    beta: Option<f64>,
+    max_change_percent: Option<f64>,
+    year5_change_percent: Option<f64>,
+    year2_change_percent: Option<f64>,
+    year1_change_percent: Option<f64>,
+    ytd_change_percent: Option<f64>,
+    month6_change_percent: Option<f64>,
+    month3_change_percent: Option<f64>,
+    month1_change_percent: Option<f64>,
+    day30_change_percent: Option<f64>,
+    day5_change_percent: Option<f64>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Trait Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl FetchCompanyFinancials for IEXProvider {
+    fn reported_income(
+        &self,
+        for_symbol: Symbol,
+        last: u8,
+        period: ReportingPeriod,
+    ) -> RequestResult<Vec<Snapshot<IncomeStatement>>> {
+        debug!(
+            "IEXProvider::<FetchCompanyFinancials>::reported_income for_symbol: {}, last: {}, period: {:?}",
+            for_symbol, last, period
+        );
+        assert_is_valid!(for_symbol);
+
+        let api_url = self.make_api_url(
+            format!("/stock/{}/income", for_symbol),
+            Some(statement_params(last, period)),
+        );
+
+        let response: RequestResult<IEXIncomeStatements> = self.get_json(api_url);
+        let dc = self.get_default_currency();
+        match response {
+            Ok(statements) => {
+                record_api_usage(ApiName::Income, statements.income.len());
+                statements
+                    .income
+                    .iter()
+                    .map(|v| to_income(dc, v, period))
+                    .collect()
+            }
+            Err(err) => {
+                warn!(
+                    "IEXProvider::<FetchCompanyFinancials>::reported_income returning error: {:?}",
+                    err
+                );
+                Err(err)
+            }
+        }
+    }
+
+    fn reported_balance_sheet(
+        &self,
+        for_symbol: Symbol,
+        last: u8,
+        period: ReportingPeriod,
+    ) -> RequestResult<Vec<Snapshot<BalanceSheet>>> {
+        debug!(
+            "IEXProvider::<FetchCompanyFinancials>::reported_balance_sheet for_symbol: {}, last: {}, period: {:?}",
+            for_symbol, last, period
+        );
+        assert_is_valid!(for_symbol);
+
+        let api_url = self.make_api_url(
+            format!("/stock/{}/balance-sheet", for_symbol),
+            Some(statement_params(last, period)),
+        );
+
+        let response: RequestResult<IEXBalanceSheets> = self.get_json(api_url);
+        let dc = self.get_default_currency();
+        match response {
+            Ok(statements) => {
+                record_api_usage(ApiName::BalanceSheet, statements.balancesheet.len());
+                statements
+                    .balancesheet
+                    .iter()
+                    .map(|v| to_balance_sheet(dc, v, period))
+                    .collect()
+            }
+            Err(err) => {
+                warn!(
+                    "IEXProvider::<FetchCompanyFinancials>::reported_balance_sheet returning error: {:?}",
+                    err
+                );
+                Err(err)
+            }
+        }
+    }
+
+    fn reported_cash_flow(
+        &self,
+        for_symbol: Symbol,
+        last: u8,
+        period: ReportingPeriod,
+    ) -> RequestResult<Vec<Snapshot<CashFlowStatement>>> {
+        debug!(
+            "IEXProvider::<FetchCompanyFinancials>::reported_cash_flow for_symbol: {}, last: {}, period: {:?}",
+            for_symbol, last, period
+        );
+        assert_is_valid!(for_symbol);
+
+        let api_url = self.make_api_url(
+            format!("/stock/{}/cash-flow", for_symbol),
+            Some(statement_params(last, period)),
+        );
+
+        let response: RequestResult<IEXCashFlowStatements> = self.get_json(api_url);
+        let dc = self.get_default_currency();
+        match response {
+            Ok(statements) => {
+                record_api_usage(ApiName::CashFlow, statements.cashflow.len());
+                statements
+                    .cashflow
+                    .iter()
+                    .map(|v| to_cash_flow(dc, v, period))
+                    .collect()
+            }
+            Err(err) => {
+                warn!(
+                    "IEXProvider::<FetchCompanyFinancials>::reported_cash_flow returning error: {:?}",
+                    err
+                );
+                Err(err)
+            }
+        }
+    }
+}
+
+impl FetchCompanyStatistics for IEXProvider {
+    fn core_statistics(&self, for_symbol: Symbol) -> RequestResult<Statistics> {
+        debug!(
+            "IEXProvider::<FetchCompanyStatistics>::core_statistics for_symbol: {}",
+            for_symbol
+        );
+        assert_is_valid!(for_symbol);
+
+        let api_url = self.make_api_url(format!("/stock/{}/stats", for_symbol), None);
+
+        let response: RequestResult<IEXStats> = self.get_json(api_url);
+        match response {
+            Ok(stats) => {
+                record_api_usage(ApiName::Stats, 1);
+                to_statistics(self.get_default_currency(), &stats)
+            }
+            Err(err) => {
+                warn!(
+                    "IEXProvider::<FetchCompanyStatistics>::core_statistics returning error: {:?}",
+                    err
+                );
+                Err(err)
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Implementations
+// ------------------------------------------------------------------------------------------------
+
+fn statement_params(last: u8, period: ReportingPeriod) -> HashMap<String, String> {
+    let mut params = HashMap::new();
+    params.insert("last".to_string(), last.to_string());
+    params.insert(
+        "period".to_string(),
+        match period {
+            ReportingPeriod::Quarter => "quarter",
+            ReportingPeriod::Annual => "annual",
+        }
+        .to_string(),
+    );
+    params
+}
+
+/// IEX returns null for line items that are not reported.
+fn value(v: Option<f64>) -> Option<i64> {
+    v.map(|v| v as i64)
+}
+
+/// The remainder of `total` once all `parts` are subtracted; `None` unless
+/// all values are reported.
+fn remainder(total: Option<i64>, parts: &[Option<i64>]) -> Option<i64> {
+    parts.iter().fold(total, |total, part| match (total, part) {
+        (Some(total), Some(part)) => Some(total - part),
+        _ => None,
+    })
+}
+
+/// IEX reports values in units, the snapshot date is the end of the fiscal
+/// period and the report date is the date the statement was filed.
+fn to_info(
+    dc: &String,
+    v: &IEXStatementInfo,
+    period: ReportingPeriod,
+) -> RequestResult<(DateTime, StatementInfo)> {
+    let report_date = date_from_string(&v.report_date)?;
+    let fiscal_date = match &v.fiscal_date {
+        Some(date) if !date.is_empty() => date_from_string(date)?,
+        _ => report_date,
+    };
+    let currency = match &v.currency {
+        Some(currency) if !currency.is_empty() => currency.to_string(),
+        _ => dc.to_string(),
+    };
+    let date = match fiscal_date.and_hms_opt(0, 0, 0) {
+        Some(date) => date,
+        None => return Err(RequestErrorKind::BadResponseError.into()),
+    };
+    Ok((
+        date,
+        StatementInfo {
+            currency,
+            scale: ReportingScale::Units,
+            fiscal_period: fiscal_period(fiscal_date, v.fiscal_quarter, v.fiscal_year, period)?,
+            filing_date: Some(report_date),
+            restated: match &v.filing_type {
+                Some(filing_type) => filing_type.ends_with("/A"),
+                None => false,
+            },
+        },
+    ))
+}
+
+/// IEX does not report the start of the fiscal year, it is assumed to start on
+/// the first of a month such that the period ending on `fiscal_date` is the
+/// `fiscal_quarter` (or calendar quarter, if not reported) of the year. The
+/// reported `fiscal_year` is used in preference to the calculated one, as not
+/// all companies name their fiscal year for the calendar year it ends in.
+fn fiscal_period(
+    fiscal_date: Date,
+    fiscal_quarter: Option<u8>,
+    fiscal_year: Option<u16>,
+    period: ReportingPeriod,
+) -> RequestResult<FiscalPeriod> {
+    let months = match (period, fiscal_quarter) {
+        (ReportingPeriod::Annual, _) => 12,
+        (ReportingPeriod::Quarter, Some(quarter)) if (1..=4).contains(&quarter) => {
+            3 * u32::from(quarter)
+        }
+        (ReportingPeriod::Quarter, _) => 3 * ((fiscal_date.month() - 1) / 3 + 1),
+    };
+    let start_month = (fiscal_date.month() + 12 - months) % 12 + 1;
+    let start_year = if start_month <= fiscal_date.month() {
+        fiscal_date.year()
+    } else {
+        fiscal_date.year() - 1
+    };
+    let fiscal_year_start = match Date::from_ymd_opt(start_year, start_month, 1) {
+        Some(date) => date,
+        None => return Err(RequestErrorKind::BadResponseError.into()),
+    };
+    let mut fiscal_period = match period {
+        ReportingPeriod::Quarter => FiscalPeriod::for_date(fiscal_date, fiscal_year_start),
+        ReportingPeriod::Annual => FiscalPeriod::year_for_date(fiscal_date, fiscal_year_start),
+    };
+    if let Some(year) = fiscal_year {
+        let reported = match fiscal_period.period {
+            FinancialPeriod::Quarter { quarter, .. } => FinancialPeriod::Quarter { quarter, year },
+            _ => FinancialPeriod::Year { year },
+        };
+        if reported.is_valid() {
+            fiscal_period.period = reported;
+        }
+    }
+    Ok(fiscal_period)
+}
+
+fn to_income(
+    dc: &String,
+    v: &IEXIncomeStatement,
+    period: ReportingPeriod,
+) -> RequestResult<Snapshot<IncomeStatement>> {
+    let (date, info) = to_info(dc, &v.info, period)?;
+    let gross_profit = value(v.gross_profit);
+    let research_and_development = value(v.research_and_development);
+    let sales_and_administration = value(v.selling_general_and_admin);
+    let operating_income = value(v.operating_income);
+    Ok(Snapshot {
+        date,
+        data: IncomeStatement {
+            info,
+            total_revenue: value(v.total_revenue),
+            cost_of_revenue: value(v.cost_of_revenue),
+            gross_profit,
+            research_and_development,
+            sales_and_administration,
+            non_recurring: None,
+            other_operating_expense: remainder(
+                gross_profit,
+                &[
+                    operating_income,
+                    research_and_development,
+                    sales_and_administration,
+                ],
+            ),
+            operating_income,
+            additional_income_or_expense: value(v.other_income_expense_net),
+            earnings_before_interest_and_tax: value(v.ebit),
+            interest_expense: value(v.interest_income),
+            earnings_before_tax: value(v.pretax_income),
+            income_tax_expense: value(v.income_tax),
+            minority_interest: value(v.minority_interest),
+            equity_earnings: None,
+            net_income: value(v.net_income),
+            net_income_to_shareholders: value(v.net_income_basic),
+        },
+    })
+}
+
+fn to_balance_sheet(
+    dc: &String,
+    v: &IEXBalanceSheet,
+    period: ReportingPeriod,
+) -> RequestResult<Snapshot<BalanceSheet>> {
+    let (date, info) = to_info(dc, &v.info, period)?;
+    Ok(Snapshot {
+        date,
+        data: BalanceSheet {
+            info,
+            current_cash: value(v.current_cash),
+            short_term_investments: value(v.short_term_investments),
+            net_receivables: value(v.receivables),
+            inventory: value(v.inventory),
+            other_current_assets: value(v.other_current_assets),
+            total_current_assets: value(v.current_assets),
+            long_term_investments: value(v.long_term_investments),
+            property_plant_equipment: value(v.property_plant_equipment),
+            goodwill: value(v.goodwill),
+            intangible_assets: value(v.intangible_assets),
+            accumulated_amortization: None,
+            other_assets: value(v.other_assets),
+            deferred_asset_charges: None,
+            total_assets: value(v.total_assets),
+            accounts_payable: value(v.accounts_payable),
+            current_long_term_debt: value(v.current_long_term_debt),
+            other_current_liabilities: value(v.other_current_liabilities),
+            total_current_liabilities: value(v.total_current_liabilities),
+            long_term_debt: value(v.long_term_debt),
+            other_liabilities: value(v.other_liabilities),
+            deferred_liability_charges: None,
+            minority_interest: value(v.minority_interest),
+            total_liabilities: value(v.total_liabilities),
+            redeemable_preferred_stock: None,
+            preferred_stock: None,
+            common_stock: value(v.common_stock),
+            retained_earnings: value(v.retained_earnings),
+            treasury_stock: value(v.treasury_stock),
+            capital_surplus: value(v.capital_surplus),
+            other_shareholder_equity: None,
+            total_shareholder_equity: value(v.shareholder_equity),
+            net_tangible_assets: value(v.net_tangible_assets),
+        },
+    })
+}
+
+fn to_cash_flow(
+    dc: &String,
+    v: &IEXCashFlowStatement,
+    period: ReportingPeriod,
+) -> RequestResult<Snapshot<CashFlowStatement>> {
+    let (date, info) = to_info(dc, &v.info, period)?;
+    let net_income = value(v.net_income);
+    let depreciation = value(v.depreciation);
+    let changes_in_receivables = value(v.changes_in_receivables);
+    let changes_in_inventories = value(v.changes_in_inventories);
+    let operating_cash_flow = value(v.cash_flow);
+    let capital_expenditures = value(v.capital_expenditures);
+    let investments = value(v.investments);
+    let investing_cash_flow = value(v.total_investing_cash_flows);
+    let dividends_paid = value(v.dividends_paid);
+    let net_borrowings = value(v.net_borrowings);
+    let financing_cash_flow = value(v.cash_flow_financing);
+    Ok(Snapshot {
+        date,
+        data: CashFlowStatement {
+            info,
+            net_income,
+            depreciation,
+            changes_in_receivables,
+            changes_in_inventories,
+            other_operating_activities: remainder(
+                operating_cash_flow,
+                &[
+                    net_income,
+                    depreciation,
+                    changes_in_receivables,
+                    changes_in_inventories,
+                ],
+            ),
+            operating_cash_flow,
+            capital_expenditures,
+            investments,
+            other_investing_activities: value(v.investing_activity_other),
+            investing_cash_flow,
+            dividends_paid,
+            net_borrowings,
+            other_financing_activities: value(v.other_financing_cash_flows),
+            financing_cash_flow,
+            exchange_rate_effect: value(v.exchange_rate_effect),
+            net_change_in_cash: value(v.cash_change),
+        },
+    })
+}
+
+/// IEX does not report a currency for statistics, all money values are in the
+/// default currency. IEX reports the next dividend date, rather than the next
+/// ex-dividend date, which is used in its place.
+fn to_statistics(dc: &String, v: &IEXStats) -> RequestResult<Statistics> {
+    let money = |value: Option<f64>| value.map(|v| price_from_float(dc, v)).transpose();
+    let count = |value: Option<f64>| value.map(|v| v.round() as u64);
+    Ok(Statistics {
+        currency: dc.to_string(),
+        shares_outstanding: count(v.shares_outstanding),
+        market_cap: value(v.marketcap),
+        share_float: count(v.float),
+        pe_ratio: v.pe_ratio,
+        beta: v.beta,
+        t52w_high: money(v.week52high)?,
+        t52w_low: money(v.week52low)?,
+        t52w_change: v.week52change,
+        t10d_average_volume: count(v.avg10_volume),
+        t30d_average_volume: count(v.avg30_volume),
+        t50d_moving_average: money(v.day50_moving_avg)?,
+        t200d_moving_average: money(v.day200_moving_avg)?,
+        t12m_eps: money(v.ttm_eps)?,
+        t12m_dividend_rate: money(v.ttm_dividend_rate)?,
+        dividend_yield: v.dividend_yield,
+        last_ex_dividend_date: optional_date_from_string(&v.ex_dividend_date)?,
+        next_ex_dividend_date: optional_date_from_string(&v.next_dividend_date)?,
+        next_earnings_date: optional_date_from_string(&v.next_earnings_date)?,
+        t5d_change_percentage: v.day5_change_percent,
+        t30d_change_percentage: v.day30_change_percent,
+        t1m_change_percentage: v.month1_change_percent,
+        t3m_change_percentage: v.month3_change_percent,
+        t6m_change_percentage: v.month6_change_percent,
+        t1y_change_percentage: v.year1_change_percent,
+        t2y_change_percentage: v.year2_change_percent,
+        t5y_change_percentage: v.year5_change_percent,
+        ytd_change_percentage: v.ytd_change_percent,
+        max_change_percentage: v.max_change_percent,
+    })
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::fiscal_period;
+    use crate::fixture::replay_provider;
+    use fin_model::company::{
+        FetchCompanyFinancials, FetchCompanyStatistics, ReportingPeriod, ReportingScale,
+    };
+    use fin_model::prelude::*;
+    use fin_model::reporting::{FinancialPeriod, FiscalPeriod};
+    use steel_cent::currency::USD;
+
+    fn aapl() -> Symbol {
+        "AAPL".parse().unwrap()
+    }
+
+    #[test]
+    fn test_reported_income() {
+        let statements = replay_provider()
+            .reported_income(aapl(), 1, ReportingPeriod::Quarter)
+            .unwrap();
+        assert_eq!(statements.len(), 1);
+        assert_eq!(
+            statements[0].date,
+            "2019-03-30T00:00:00".parse::<DateTime>().unwrap()
+        );
+        let income = &statements[0].data;
+        assert_eq!(income.info.currency, "USD");
+        assert_eq!(income.info.scale, ReportingScale::Units);
+        assert_eq!(
+            income.info.fiscal_period,
+            FiscalPeriod {
+                period: FinancialPeriod::Quarter {
+                    quarter: 2,
+                    year: 2019
+                },
+                fiscal_year_start_date: Date::from_ymd(2018, 10, 1),
+            }
+        );
+        assert_eq!(income.info.filing_date, Some(Date::from_ymd(2019, 5, 1)));
+        assert!(!income.info.restated);
+        assert_eq!(income.total_revenue, Some(58_015_000_000));
+        assert_eq!(income.other_operating_expense, Some(0));
+        assert_eq!(income.net_income, Some(11_561_000_000));
+        assert_eq!(income.non_recurring, None);
+    }
+
+    #[test]
+    fn test_reported_cash_flow() {
+        let statements = replay_provider()
+            .reported_cash_flow(aapl(), 1, ReportingPeriod::Quarter)
+            .unwrap();
+        assert_eq!(statements.len(), 1);
+        let cash_flow = &statements[0].data;
+        assert_eq!(cash_flow.operating_cash_flow, Some(11_155_000_000));
+        assert_eq!(cash_flow.capital_expenditures, Some(-2_363_000_000));
+        assert_eq!(cash_flow.dividends_paid, Some(-3_443_000_000));
+        assert_eq!(cash_flow.other_operating_activities, Some(-12_599_000_000));
+        assert_eq!(cash_flow.exchange_rate_effect, None);
+        assert_eq!(cash_flow.free_cash_flow(), Some(8_792_000_000));
+    }
+
+    #[test]
+    fn test_core_statistics() {
+        let statistics = replay_provider().core_statistics(aapl()).unwrap();
+        assert_eq!(statistics.currency, "USD");
+        assert_eq!(statistics.market_cap, Some(1_015_083_283_120));
+        assert_eq!(statistics.shares_outstanding, Some(4_519_180_000));
+        assert_eq!(statistics.t10d_average_volume, Some(25_926_781));
+        assert_eq!(
+            statistics.t52w_high.unwrap(),
+            Money::of_major_minor(USD, 229, 93)
+        );
+        assert_eq!(
+            statistics.t12m_eps.unwrap(),
+            Money::of_major_minor(USD, 11, 89)
+        );
+        assert_eq!(
+            statistics.last_ex_dividend_date,
+            Some(Date::from_ymd(2019, 8, 9))
+        );
+        assert_eq!(statistics.next_ex_dividend_date, None);
+        assert_eq!(
+            statistics.next_earnings_date,
+            Some(Date::from_ymd(2019, 10, 30))
+        );
+        assert_eq!(statistics.beta, None);
+    }
+
+    #[test]
+    fn test_fiscal_period() {
+        let period = fiscal_period(
+            Date::from_ymd(2019, 9, 28),
+            None,
+            None,
+            ReportingPeriod::Annual,
+        )
+        .unwrap();
+        assert_eq!(period.period, FinancialPeriod::Year { year: 2019 });
+        assert_eq!(period.fiscal_year_start_date, Date::from_ymd(2018, 10, 1));
+
+        let period = fiscal_period(
+            Date::from_ymd(2019, 6, 30),
+            None,
+            None,
+            ReportingPeriod::Quarter,
+        )
+        .unwrap();
+        assert_eq!(
+            period.period,
+            FinancialPeriod::Quarter {
+                quarter: 2,
+                year: 2019
+            }
+        );
+        assert_eq!(period.fiscal_year_start_date, Date::from_ymd(2019, 1, 1));
+
+        let period = fiscal_period(
+            Date::from_ymd(2019, 2, 2),
+            Some(4),
+            Some(2018),
+            ReportingPeriod::Quarter,
+        )
+        .unwrap();
+        assert_eq!(
+            period.period,
+            FinancialPeriod::Quarter {
+                quarter: 4,
+                year: 2018
+            }
+        );
+        assert_eq!(period.fiscal_year_start_date, Date::from_ymd(2018, 3, 1));
+    }
+}
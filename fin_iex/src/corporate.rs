@@ -0,0 +1,250 @@
+/*!
+IEX API wrapper
+*/
+
+use serde::{Deserialize, Serialize};
+
+use fin_model::corporate::*;
+use fin_model::prelude::*;
+use fin_model::quote::SeriesInterval;
+
+use crate::internal::convert::*;
+use crate::internal::metric::{record_api_usage, ApiName};
+use crate::IEXProvider;
+
+// ------------------------------------------------------------------------------------------------
+// API Types (internal)
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct IEXDividend {
+    pub ex_date: String,
+    pub payment_date: Option<String>,
+    pub record_date: Option<String>,
+    pub declared_date: Option<String>,
+    pub amount: f64,
+    pub flag: Option<String>,
+    pub currency: Option<String>,
+    pub description: Option<String>,
+    pub frequency: Option<String>,
+}
+
+type IEXDividends = Vec<IEXDividend>;
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct IEXSplit {
+    pub ex_date: String,
+    pub declared_date: Option<String>,
+    pub ratio: f64,
+    pub to_factor: f64,
+    pub from_factor: f64,
+    pub description: Option<String>,
+}
+
+type IEXSplits = Vec<IEXSplit>;
+
+// ------------------------------------------------------------------------------------------------
+// Trait Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl FetchCorporateActions for IEXProvider {
+    /// Note that IEX returns at least the last month of dividends, and so
+    /// `SeriesInterval::Day` and `SeriesInterval::FiveDays` return the same
+    /// as `SeriesInterval::OneMonth`.
+    fn dividends(
+        &self,
+        for_symbol: Symbol,
+        interval: SeriesInterval,
+    ) -> RequestResult<Vec<Dividend>> {
+        debug!(
+            "IEXProvider::<FetchCorporateActions>::dividends for_symbol: {}, interval: {:?}",
+            for_symbol, interval
+        );
+        assert_is_valid!(for_symbol);
+
+        let api_url = self.make_api_url(
+            format!("stock/{}/dividends/{}", for_symbol, range_for(interval)),
+            None,
+        );
+
+        let response: RequestResult<IEXDividends> = self.get_json(api_url);
+        let dc = self.get_default_currency();
+        match response {
+            Ok(values) => {
+                record_api_usage(ApiName::Dividends, values.len());
+                values.iter().map(|v| to_dividend(dc, v)).collect()
+            }
+            Err(err) => {
+                warn!(
+                    "IEXProvider::<FetchCorporateActions>::dividends returning error: {:?}",
+                    err
+                );
+                Err(err)
+            }
+        }
+    }
+
+    /// Note that IEX returns at least the last month of splits, and so
+    /// `SeriesInterval::Day` and `SeriesInterval::FiveDays` return the same
+    /// as `SeriesInterval::OneMonth`.
+    fn splits(&self, for_symbol: Symbol, interval: SeriesInterval) -> RequestResult<Vec<Split>> {
+        debug!(
+            "IEXProvider::<FetchCorporateActions>::splits for_symbol: {}, interval: {:?}",
+            for_symbol, interval
+        );
+        assert_is_valid!(for_symbol);
+
+        let api_url = self.make_api_url(
+            format!("stock/{}/splits/{}", for_symbol, range_for(interval)),
+            None,
+        );
+
+        let response: RequestResult<IEXSplits> = self.get_json(api_url);
+        match response {
+            Ok(values) => {
+                record_api_usage(ApiName::Splits, values.len());
+                values.iter().map(to_split).collect()
+            }
+            Err(err) => {
+                warn!(
+                    "IEXProvider::<FetchCorporateActions>::splits returning error: {:?}",
+                    err
+                );
+                Err(err)
+            }
+        }
+    }
+
+    /// IEX only provides dividends and splits, this combines the two.
+    fn corporate_actions(
+        &self,
+        for_symbol: Symbol,
+        interval: SeriesInterval,
+    ) -> RequestResult<Vec<CorporateAction>> {
+        let dividends = self.dividends(for_symbol.clone(), interval)?;
+        let splits = self.splits(for_symbol, interval)?;
+        Ok(combine(dividends, splits))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Implementations
+// ------------------------------------------------------------------------------------------------
+
+/// Combine dividends and splits into a single list of actions in ex-date order.
+pub(crate) fn combine(dividends: Vec<Dividend>, splits: Vec<Split>) -> Vec<CorporateAction> {
+    let mut actions: Vec<CorporateAction> = dividends
+        .into_iter()
+        .map(CorporateAction::Dividend)
+        .chain(splits.into_iter().map(CorporateAction::Split))
+        .collect();
+    actions.sort_by_key(|action| action.ex_date());
+    actions
+}
+
+fn range_for(interval: SeriesInterval) -> &'static str {
+    match interval {
+        SeriesInterval::Day | SeriesInterval::FiveDays | SeriesInterval::OneMonth => "1m",
+        SeriesInterval::ThreeMonths => "3m",
+        SeriesInterval::SixMonths => "6m",
+        SeriesInterval::YearToDate => "ytd",
+        SeriesInterval::OneYear => "1y",
+        SeriesInterval::TwoYears => "2y",
+        SeriesInterval::FiveYears => "5y",
+    }
+}
+
+/// IEX returns an empty string, or null, for values that are not known.
+fn optional_string(value: &Option<String>) -> Option<String> {
+    match value {
+        Some(value) if !value.is_empty() => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+fn to_dividend(dc: &String, v: &IEXDividend) -> RequestResult<Dividend> {
+    let currency = match &v.currency {
+        Some(currency) if !currency.is_empty() => currency,
+        _ => dc,
+    };
+    Ok(Dividend {
+        ex_date: date_from_string(&v.ex_date)?,
+        declared_date: optional_date_from_string(&v.declared_date)?,
+        record_date: optional_date_from_string(&v.record_date)?,
+        payment_date: optional_date_from_string(&v.payment_date)?,
+        amount: price_from_float(currency, v.amount)?,
+        frequency: match v.frequency.as_ref().map(|f| f.to_lowercase()) {
+            Some(frequency) => match frequency.as_str() {
+                "annual" => Some(DividendFrequency::Annual),
+                "semi-annual" => Some(DividendFrequency::SemiAnnual),
+                "quarterly" => Some(DividendFrequency::Quarterly),
+                "monthly" => Some(DividendFrequency::Monthly),
+                "" => None,
+                _ => Some(DividendFrequency::Irregular),
+            },
+            None => None,
+        },
+        description: optional_string(&v.description),
+    })
+}
+
+fn to_split(v: &IEXSplit) -> RequestResult<Split> {
+    Ok(Split {
+        ex_date: date_from_string(&v.ex_date)?,
+        declared_date: optional_date_from_string(&v.declared_date)?,
+        from_factor: v.from_factor,
+        to_factor: v.to_factor,
+        description: optional_string(&v.description),
+    })
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture::replay_provider;
+    use fin_model::corporate::*;
+    use fin_model::prelude::*;
+    use fin_model::quote::SeriesInterval;
+    use steel_cent::currency::USD;
+
+    fn aapl() -> Symbol {
+        "AAPL".parse().unwrap()
+    }
+
+    #[test]
+    fn test_dividends() {
+        let dividends = replay_provider()
+            .dividends(aapl(), SeriesInterval::OneYear)
+            .unwrap();
+        assert_eq!(dividends.len(), 2);
+        assert_eq!(dividends[0].ex_date, Date::from_ymd(2019, 5, 10));
+        assert_eq!(dividends[0].amount, Money::of_major_minor(USD, 0, 77));
+        assert_eq!(dividends[0].frequency, Some(DividendFrequency::Quarterly));
+        assert_eq!(dividends[1].record_date, Some(Date::from_ymd(2019, 2, 11)));
+    }
+
+    #[test]
+    fn test_corporate_actions() {
+        let actions = replay_provider()
+            .corporate_actions(aapl(), SeriesInterval::FiveYears)
+            .unwrap();
+        let dates: Vec<Date> = actions.iter().map(|action| action.ex_date()).collect();
+        assert_eq!(
+            dates,
+            vec![
+                Date::from_ymd(2014, 6, 9),
+                Date::from_ymd(2019, 2, 8),
+                Date::from_ymd(2019, 5, 10),
+            ]
+        );
+        match &actions[0] {
+            CorporateAction::Split(split) => assert_eq!(split.ratio(), 7.0),
+            other => panic!("expected a split, not {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,308 @@
+/*!
+Record and replay of IEX responses, for offline and deterministic testing.
+
+When a provider is configured with `Fixtures` every call to IEX is routed
+through the fixture directory:
+
+* in `FixtureMode::Record` each call is made to IEX as normal and the raw
+  response body is saved to a file in the directory, any occurrence of the
+  API token in the body is redacted;
+* in `FixtureMode::Replay` no call is made to IEX, the response is read from
+  the file saved for the endpoint, and an error of kind
+  `RequestErrorKind::ConfigurationError` is returned if there is none.
+
+Files are keyed by the endpoint path, without the base URL, API version, or
+token; for example `stock/AAPL/chart/1m?chartByDay=true` is saved as
+`stock/AAPL/chart/1m@chartByDay=true.json`. Responses are recorded only for
+successful calls.
+
+Fixtures may be set in an `IEXConfig`, or with the environment variables
+`IEX_FIXTURE_MODE` (`record` or `replay`) and `IEX_FIXTURE_DIR`.
+
+## Example
+
+```rust,no_run
+use fin_iex::fixture::Fixtures;
+use fin_iex::{IEXConfig, IEXProvider};
+use fin_model::quote::FetchPriceQuote;
+
+let provider = IEXProvider::with_config(
+    IEXConfig::new("Tpk_0123456789")
+        .with_host("sandbox")
+        .with_fixtures(Fixtures::replay("tests/fixtures")),
+)
+.unwrap();
+let quote = provider.real_time("AAPL".parse().unwrap()).unwrap();
+```
+*/
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use fin_model::prelude::*;
+
+use crate::internal::env;
+use crate::internal::request;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// Whether responses are recorded from, or replayed instead of, IEX.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FixtureMode {
+    Record,
+    Replay,
+}
+
+/// The fixture mode and the directory fixture files are read from, or saved to.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Fixtures {
+    pub mode: FixtureMode,
+    pub directory: PathBuf,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+const ENV_FIXTURE_MODE: &str = "IEX_FIXTURE_MODE";
+const ENV_FIXTURE_DIR: &str = "IEX_FIXTURE_DIR";
+
+const REDACTED: &str = "<<private>>";
+
+impl Fixtures {
+    /// Record responses to files in `directory`.
+    pub fn record<P: AsRef<Path>>(directory: P) -> Self {
+        Fixtures {
+            mode: FixtureMode::Record,
+            directory: directory.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Replay responses from files in `directory`.
+    pub fn replay<P: AsRef<Path>>(directory: P) -> Self {
+        Fixtures {
+            mode: FixtureMode::Replay,
+            directory: directory.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Construct fixtures from the environment variables `IEX_FIXTURE_MODE`
+    /// and `IEX_FIXTURE_DIR`, returning `None` if no mode is set.
+    pub fn from_environment() -> RequestResult<Option<Self>> {
+        let mode = match env::get_from_environment(ENV_FIXTURE_MODE, None) {
+            None => return Ok(None),
+            Some(mode) => match mode.to_lowercase().as_str() {
+                "record" => FixtureMode::Record,
+                "replay" => FixtureMode::Replay,
+                _ => {
+                    return Err(RequestErrorKind::ConfigurationError(format!(
+                        "invalid value {} for {}",
+                        mode, ENV_FIXTURE_MODE
+                    ))
+                    .into())
+                }
+            },
+        };
+        match env::get_from_environment(ENV_FIXTURE_DIR, None) {
+            Some(directory) => Ok(Some(Fixtures {
+                mode,
+                directory: PathBuf::from(directory),
+            })),
+            None => Err(env::missing_environment(ENV_FIXTURE_DIR)),
+        }
+    }
+
+    /// Validate the fixtures, the directory must exist to replay responses.
+    pub fn validate(&self) -> RequestResult<()> {
+        if self.mode == FixtureMode::Replay && !self.directory.is_dir() {
+            return Err(RequestErrorKind::ConfigurationError(format!(
+                "IEX fixture directory {:?} does not exist",
+                self.directory
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
+    /// The key for an API URL; the endpoint path, and any query parameters
+    /// other than the token, without the host or API version.
+    pub fn key_for(api_url: &str) -> String {
+        let path = match api_url.find("://") {
+            Some(idx) => &api_url[idx + 3..],
+            None => api_url,
+        };
+        // skip the host and API version segments.
+        let path = path.splitn(3, '/').nth(2).unwrap_or_default();
+        endpoint_key(path)
+    }
+
+    /// The key for an API URL made by a provider with the base URL, including
+    /// the API version, `base_url`; this allows for a base URL with a path.
+    pub fn key_relative_to(base_url: &str, api_url: &str) -> String {
+        match api_url.strip_prefix(base_url) {
+            Some(endpoint) => endpoint_key(endpoint),
+            None => Fixtures::key_for(api_url),
+        }
+    }
+
+    /// The file a response for `api_url` is saved to.
+    pub fn file_for(&self, api_url: &str) -> PathBuf {
+        self.file_for_key(&Fixtures::key_for(api_url))
+    }
+
+    fn file_for_key(&self, key: &str) -> PathBuf {
+        let key = key.replace('?', "@");
+        let mut file = self.directory.clone();
+        for segment in key.split('/') {
+            file.push(segment);
+        }
+        file.set_file_name(format!(
+            "{}.json",
+            file.file_name().unwrap_or_default().to_string_lossy()
+        ));
+        file
+    }
+
+    /// Return the response body for `api_url`, either replayed from a file or
+    /// fetched with `client` and recorded; `token` is redacted from recorded
+    /// files.
+    pub(crate) fn get(
+        &self,
+        client: &reqwest::Client,
+        base_url: &str,
+        api_url: String,
+        token: &str,
+    ) -> RequestResult<String> {
+        let key = Fixtures::key_relative_to(base_url, &api_url);
+        let file = self.file_for_key(&key);
+        match self.mode {
+            FixtureMode::Replay => {
+                debug!("Fixtures::get replaying {:?}", file);
+                fs::read_to_string(&file).map_err(|err| {
+                    warn!("Fixtures::get no fixture {:?}: {}", file, err);
+                    RequestError::new(RequestErrorKind::ConfigurationError(format!(
+                        "no IEX fixture for {}",
+                        key
+                    )))
+                    .with_source(err)
+                })
+            }
+            FixtureMode::Record => {
+                let body = request::make_api_call(client, api_url)?;
+                debug!("Fixtures::get recording {:?}", file);
+                let recorded = if token.is_empty() {
+                    body.clone()
+                } else {
+                    body.replace(token, REDACTED)
+                };
+                let saved = match file.parent() {
+                    Some(parent) => fs::create_dir_all(parent),
+                    None => Ok(()),
+                }
+                .and_then(|_| fs::write(&file, recorded));
+                if let Err(err) = saved {
+                    warn!("Fixtures::get could not record {:?}: {}", file, err);
+                }
+                Ok(body)
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn endpoint_key(endpoint: &str) -> String {
+    let (path, query) = match endpoint.find('?') {
+        Some(idx) => (&endpoint[..idx], &endpoint[idx + 1..]),
+        None => (endpoint, ""),
+    };
+    let path: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let mut params: Vec<&str> = query
+        .split('&')
+        .filter(|p| !p.is_empty() && !p.starts_with("token="))
+        .collect();
+    params.sort_unstable();
+    if params.is_empty() {
+        path.join("/")
+    } else {
+        format!("{}?{}", path.join("/"), params.join("&"))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Test Support
+// ------------------------------------------------------------------------------------------------
+
+/// A provider replaying the fixtures in this crate's `tests/fixtures` directory.
+#[cfg(test)]
+pub(crate) fn replay_provider() -> crate::IEXProvider {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    crate::IEXProvider::with_config(
+        crate::IEXConfig::new("Tpk_fixtures")
+            .with_host("sandbox")
+            .with_fixtures(Fixtures::replay(directory)),
+    )
+    .unwrap()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::Fixtures;
+    use std::env::temp_dir;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_key_for() {
+        assert_eq!(
+            Fixtures::key_for("https://cloud.iexapis.com/stable/stock/AAPL/quote?token=pk_123"),
+            "stock/AAPL/quote".to_string()
+        );
+        assert_eq!(
+            Fixtures::key_for(
+                "https://sandbox.iexapis.com/beta//stock/AAPL/chart/1m?chartByDay=true&token=Tpk_1"
+            ),
+            "stock/AAPL/chart/1m?chartByDay=true".to_string()
+        );
+        assert_eq!(
+            Fixtures::replay("fixtures").file_for(
+                "https://cloud.iexapis.com/stable/stock/AAPL/chart/1m?chartByDay=true&token=pk_1"
+            ),
+            PathBuf::from("fixtures/stock/AAPL/chart/1m@chartByDay=true.json")
+        );
+        assert_eq!(
+            Fixtures::key_relative_to(
+                "http://localhost:8080/iex/stable",
+                "http://localhost:8080/iex/stable/stock/AAPL/quote?token=pk_123"
+            ),
+            "stock/AAPL/quote".to_string()
+        );
+    }
+
+    #[test]
+    fn test_replay_missing() {
+        let fixtures = Fixtures::replay(temp_dir());
+        assert!(fixtures.validate().is_ok());
+        assert!(fixtures
+            .get(
+                &reqwest::Client::new(),
+                "https://cloud.iexapis.com/stable",
+                "https://cloud.iexapis.com/stable/stock/NONE/quote?token=pk_1".to_string(),
+                "pk_1"
+            )
+            .is_err());
+        assert!(Fixtures::replay(temp_dir().join("fin_iex_missing"))
+            .validate()
+            .is_err());
+    }
+}
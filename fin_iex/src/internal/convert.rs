@@ -8,6 +8,7 @@ use steel_cent::currency::Currency;
 use regex::Regex;
 
 use fin_model::prelude::*;
+use fin_model::reporting::FinancialPeriod;
 
 // ------------------------------------------------------------------------------------------------
 // Public Functions
@@ -21,17 +22,25 @@ pub fn date_from_string(date: &String) -> RequestResult<Date> {
     match date.parse::<Date>() {
         Err(err) => {
             warn!("doesn't look like a date: {}, error: {}", date, err);
-            Err(RequestError::BadResponseError)
+            Err(RequestErrorKind::BadResponseError.into())
         }
         Ok(dt) => Ok(dt),
     }
 }
 
+/// IEX returns either null or an empty string for dates it does not have.
+pub fn optional_date_from_string(date: &Option<String>) -> RequestResult<Option<Date>> {
+    match date {
+        Some(date) if !date.is_empty() => Ok(Some(date_from_string(date)?)),
+        _ => Ok(None),
+    }
+}
+
 pub fn datetime_from_date_string(date: &String) -> RequestResult<DateTime> {
     match format!("{}T00:00:00", date).parse::<DateTime>() {
         Err(err) => {
             warn!("doesn't look like a date/time: {}, error: {}", date, err);
-            Err(RequestError::BadResponseError)
+            Err(RequestErrorKind::BadResponseError.into())
         }
         Ok(dt) => Ok(dt),
     }
@@ -44,42 +53,82 @@ pub fn datetime_from_string(date: &String, time: &String) -> RequestResult<DateT
                 "doesn't look like a date/time: {} {}, error: {}",
                 date, time, err
             );
-            Err(RequestError::BadResponseError)
+            Err(RequestErrorKind::BadResponseError.into())
         }
         Ok(dt) => Ok(dt),
     }
 }
 
+pub fn period_from_string(period: &String) -> RequestResult<FinancialPeriod> {
+    match period.parse::<FinancialPeriod>() {
+        Err(err) => {
+            warn!("doesn't look like a period: '{}', error: {}", period, err);
+            Err(RequestErrorKind::BadResponseError.into())
+        }
+        Ok(period) => Ok(period),
+    }
+}
+
 pub fn price_from_string(currency: &String, price: &String) -> RequestResult<Money> {
     lazy_static! {
-        static ref RE: Regex = Regex::new(r"^(\-?\d+)(\.(\d+))?$").unwrap();
+        static ref RE: Regex = Regex::new(r"^\-?\d+(\.\d+)?$").unwrap();
     }
 
-    match RE.captures(price) {
+    if RE.is_match(price) {
+        price_from_float(currency, price.parse::<f64>().unwrap())
+    } else {
+        warn!("doesn't look like a float: '{}'", price);
+        Err(RequestErrorKind::BadResponseError.into())
+    }
+}
+
+pub fn price_from_float(currency: &String, price: f64) -> RequestResult<Money> {
+    let currency: Currency = match with_code(currency) {
+        Some(currency) => currency,
         None => {
-            warn!("doesn't look like a float: '{}'", price);
-            Err(RequestError::BadResponseError)
-        }
-        Some(captures) => {
-            let currency: Currency = with_code(currency).unwrap();
-            if let Some(_) = captures.get(2) {
-                Ok(Money::of_major_minor(
-                    currency,
-                    captures[1].parse::<i32>().unwrap(),
-                    captures[3].parse::<i32>().unwrap(),
-                ))
-            } else {
-                Ok(Money::of_major_minor(
-                    currency,
-                    captures[1].parse::<i32>().unwrap(),
-                    0,
-                ))
-            }
+            warn!("doesn't look like a currency code: '{}'", currency);
+            return Err(RequestErrorKind::BadResponseError.into());
         }
+    };
+    let minor = (price * 10f64.powi(i32::from(currency.decimal_places()))).round();
+    if minor.is_finite() && minor >= f64::from(i32::MIN) && minor <= f64::from(i32::MAX) {
+        Ok(Money::of_minor(currency, minor as i32))
+    } else {
+        warn!("float out of range for a price: '{}'", price);
+        Err(RequestErrorKind::BadResponseError.into())
     }
 }
 
-pub fn price_from_float(currency: &String, price: f64) -> RequestResult<Money> {
-    // this isn't efficient, but deconstructing floats is a black art
-    price_from_string(currency, &format!("{}", price))
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use steel_cent::currency::USD;
+
+    #[test]
+    fn test_price_from_string() {
+        let usd = "USD".to_string();
+        assert_eq!(
+            price_from_string(&usd, &"199.5".to_string()).unwrap(),
+            Money::of_major_minor(USD, 199, 50)
+        );
+        assert_eq!(
+            price_from_string(&usd, &"-0.05".to_string()).unwrap(),
+            Money::of_minor(USD, -5)
+        );
+        assert_eq!(
+            price_from_float(&usd, 0.1).unwrap(),
+            Money::of_major_minor(USD, 0, 10)
+        );
+        assert!(price_from_string(&usd, &"1.2.3".to_string()).is_err());
+        assert!(price_from_float(&usd, f64::NAN).is_err());
+        assert!(price_from_float(&usd, f64::INFINITY).is_err());
+        assert!(price_from_float(&usd, 1e12).is_err());
+        assert!(price_from_float(&usd, -1e12).is_err());
+        assert!(price_from_float(&"usd".to_string(), 1.0).is_err());
+        assert!(price_from_float(&"XYZ".to_string(), 1.0).is_err());
+    }
 }
@@ -4,21 +4,14 @@ Environment variable wrappers.
 
 use std::env;
 
-use fin_model::request::RequestError;
+use fin_model::request::{RequestError, RequestErrorKind};
 
 // ------------------------------------------------------------------------------------------------
 // Public Functions
 // ------------------------------------------------------------------------------------------------
 
 pub fn missing_environment(env_key: &'static str) -> RequestError {
-    RequestError::ConfigurationError(format!("no {} environment variable", env_key))
-}
-
-pub fn invalid_environment(env_key: &'static str, value: String) -> RequestError {
-    RequestError::ConfigurationError(format!(
-        "invalid value {} for environment variable {}",
-        value, env_key
-    ))
+    RequestErrorKind::ConfigurationError(format!("no {} environment variable", env_key)).into()
 }
 
 pub fn get_from_environment(env_key: &'static str, or_else: Option<String>) -> Option<String> {
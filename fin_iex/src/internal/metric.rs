@@ -20,7 +20,16 @@ pub enum ApiName {
     TargetPrice,
     ConsensusEPS,
     ConsensusRatings,
+    Earnings,
+    Income,
+    BalanceSheet,
+    CashFlow,
+    Stats,
     NewsFeed,
+    OptionExpirations,
+    Options,
+    Dividends,
+    Splits,
 }
 
 lazy_static! {
@@ -35,6 +44,16 @@ lazy_static! {
         m.insert(ApiName::TargetPrice, 500);
         m.insert(ApiName::ConsensusEPS, 10_000);
         m.insert(ApiName::ConsensusRatings, 1_000);
+        m.insert(ApiName::Earnings, 1_000);
+        m.insert(ApiName::Income, 1_000);
+        m.insert(ApiName::BalanceSheet, 3_000);
+        m.insert(ApiName::CashFlow, 1_000);
+        m.insert(ApiName::Stats, 5);
+        m.insert(ApiName::NewsFeed, 10);
+        m.insert(ApiName::OptionExpirations, 1);
+        m.insert(ApiName::Options, 1_000);
+        m.insert(ApiName::Dividends, 10);
+        m.insert(ApiName::Splits, 10);
         m
     };
     static ref RECEIVER: Receiver = Receiver::builder()
@@ -52,18 +71,41 @@ pub fn record_api_use(api: ApiName) {
     record_api_usage(api, 1)
 }
 
-pub fn record_api_usage(api: ApiName, count: u16) {
+pub fn record_api_usage(api: ApiName, count: usize) {
     debug!("recording API usage for {:?} x {}", api, count);
-    let cost_per_call = COSTS.get(&api).unwrap();
+    let count = count as u64;
     RECEIVER
         .get_sink()
-        .record_count("IEX::API::total_cost", (count * cost_per_call) as u64);
+        .record_count("IEX::API::total_cost", count * cost_of(&api));
     RECEIVER
         .get_sink()
-        .record_count(format!("IEX::API::{:?}::count", api), count as u64);
+        .record_count(format!("IEX::API::{:?}::count", api), count);
+}
+
+/// The message cost, in credits, of a single call to, or item returned by, `api`.
+pub fn cost_of(api: &ApiName) -> u64 {
+    *COSTS.get(api).unwrap() as u64
 }
 
 pub fn record_to_log() {
     debug!("committing metrics to log");
     EXPORTER.turn();
 }
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_large_usage() {
+        // these would overflow a u16 count, or a u16 cost.
+        record_api_usage(ApiName::Earnings, 66);
+        record_api_usage(ApiName::BalanceSheet, 22);
+        record_api_usage(ApiName::Earnings, 100_000);
+        assert_eq!(cost_of(&ApiName::Earnings) * 100_000, 100_000_000);
+    }
+}
@@ -2,57 +2,146 @@
 REST API details
 */
 
+use std::time::Duration;
+
 use reqwest;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, RETRY_AFTER};
 
 use serde::de::DeserializeOwned;
 
 use fin_model::prelude::*;
 
+use crate::config::HttpConfig;
+
 // ------------------------------------------------------------------------------------------------
 // Public Functions
 // ------------------------------------------------------------------------------------------------
 
-pub fn make_api_call(api: String) -> RequestResult<String> {
+/// Construct the client used for all requests, with any proxy, timeouts,
+/// and default headers set in `http`.
+pub fn make_client(http: &HttpConfig) -> RequestResult<reqwest::Client> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(proxy) = &http.proxy {
+        let proxy = reqwest::Proxy::all(proxy.as_str())
+            .map_err(|err| configuration_error("invalid proxy URL").with_source(err))?;
+        builder = builder.proxy(proxy);
+    }
+    if let Some(timeout) = http.connect_timeout {
+        builder = builder.connect_timeout(timeout);
+    }
+    if let Some(timeout) = http.read_timeout {
+        builder = builder.timeout(timeout);
+    }
+    if !http.headers.is_empty() {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &http.headers {
+            let header_name = HeaderName::from_bytes(name.as_bytes()).map_err(|err| {
+                configuration_error(&format!("invalid header name {}", name)).with_source(err)
+            })?;
+            let header_value = HeaderValue::from_str(value).map_err(|err| {
+                configuration_error(&format!("invalid value for header {}", name)).with_source(err)
+            })?;
+            let _ = headers.insert(header_name, header_value);
+        }
+        builder = builder.default_headers(headers);
+    }
+    builder
+        .build()
+        .map_err(|err| configuration_error("could not construct HTTP client").with_source(err))
+}
+
+pub fn make_api_call(client: &reqwest::Client, api: String) -> RequestResult<String> {
     info!("reqwest::get {}", api);
-    match reqwest::get(api.as_str()) {
-        Err(err) => match err.status() {
-            Some(s) => Err(RequestError::from_u16(s.as_u16()).unwrap()),
-            None => Err(RequestError::CommunicationError),
-        },
+    match client.get(api.as_str()).send() {
+        Err(err) => Err(from_reqwest_error(&api, err)),
         Ok(mut r) => {
             if r.status().is_success() {
                 Ok(r.text().unwrap_or("".to_string()))
             } else {
-                Err(RequestError::from_u16(r.status().as_u16()).unwrap())
+                Err(from_response(&api, &r))
             }
         }
     }
 }
 
-pub fn make_json_call<T: DeserializeOwned>(api: String) -> RequestResult<T> {
+pub fn make_json_call<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    api: String,
+) -> RequestResult<T> {
     info!("reqwest::get {}", api);
-    let client = reqwest::Client::new();
     match client.get(api.as_str()).send() {
-        Err(err) => match err.status() {
-            Some(s) => {
-                warn!("response status {}", s);
-                Err(RequestError::from_u16(s.as_u16()).unwrap())
-            }
-            None => Err(RequestError::CommunicationError),
-        },
+        Err(err) => Err(from_reqwest_error(&api, err)),
         Ok(mut r) => {
             if r.status().is_success() {
                 let json: reqwest::Result<T> = r.json();
                 match json {
                     Err(err) => {
                         warn!("response error: {:?}", err);
-                        Err(RequestError::BadResponseError)
+                        Err(
+                            with_context(RequestErrorKind::BadResponseError.into(), &api)
+                                .with_source(err),
+                        )
                     }
                     Ok(j) => Ok(j),
                 }
             } else {
-                Err(RequestError::from_u16(r.status().as_u16()).unwrap())
+                Err(from_response(&api, &r))
             }
         }
     }
 }
+
+/// Parse a response body, returned from `api`, as JSON.
+pub fn parse_json<T: DeserializeOwned>(api: &str, body: &str) -> RequestResult<T> {
+    serde_json::from_str(body).map_err(|err| {
+        warn!("response error: {:?}", err);
+        with_context(RequestErrorKind::BadResponseError.into(), api).with_source(err)
+    })
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+pub const PROVIDER_NAME: &str = "IEX Cloud";
+
+fn configuration_error(message: &str) -> RequestError {
+    RequestError::new(RequestErrorKind::ConfigurationError(message.to_string()))
+        .with_provider(PROVIDER_NAME)
+}
+
+fn from_reqwest_error(api: &str, err: reqwest::Error) -> RequestError {
+    let error = match err.status() {
+        Some(s) => {
+            warn!("response status {}", s);
+            RequestError::from_u16(s.as_u16()).unwrap()
+        }
+        None => RequestErrorKind::CommunicationError.into(),
+    };
+    with_context(error, api).with_source(err)
+}
+
+fn from_response(api: &str, response: &reqwest::Response) -> RequestError {
+    let status = response.status();
+    warn!("response status {}", status);
+    let error = with_context(RequestError::from_u16(status.as_u16()).unwrap(), api);
+    let retry_after = response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok());
+    match retry_after {
+        Some(seconds) => error.with_retry_after(Duration::from_secs(seconds)),
+        None => error,
+    }
+}
+
+/// Add the provider name and endpoint, the endpoint is the API URL without
+/// the query string so that the token is never included in an error.
+fn with_context(error: RequestError, api: &str) -> RequestError {
+    let endpoint = match api.find('?') {
+        Some(idx) => &api[..idx],
+        None => api,
+    };
+    error.with_provider(PROVIDER_NAME).with_endpoint(endpoint)
+}
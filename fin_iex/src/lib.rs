@@ -26,6 +26,7 @@ extern crate reqwest; // HTTP
 extern crate serde; // serialization
 extern crate serde_json; // JSON serialization
 extern crate steel_cent; // money
+extern crate toml; // configuration
 
 #[macro_use]
 extern crate lazy_static;
@@ -39,6 +40,16 @@ extern crate fin_data;
 // Public Modules/Exports
 // ------------------------------------------------------------------------------------------------
 
+pub mod budget;
+
+pub use budget::{BudgetedProvider, CreditBudget};
+
+pub mod config;
+
+pub use config::IEXConfig;
+
+pub mod fixture;
+
 pub mod provider;
 
 pub use provider::IEXProvider;
@@ -49,8 +60,14 @@ pub use provider::IEXProvider;
 
 mod analysis;
 
+mod company;
+
+mod corporate;
+
 mod news;
 
+mod options;
+
 mod quote;
 
 // ------------------------------------------------------------------------------------------------
@@ -7,19 +7,17 @@ use serde::{Deserialize, Serialize};
 
 use fin_model::news::*;
 use fin_model::prelude::*;
-use fin_model::symbol::is_valid;
 
 use crate::internal::convert::*;
 use crate::internal::metric::{record_api_use, ApiName};
-use crate::internal::request;
 use crate::IEXProvider;
 
 // ------------------------------------------------------------------------------------------------
 // API Types (internal)
 // ------------------------------------------------------------------------------------------------
 
-#[serde(rename_all = "camelCase")]
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct IEXNewsItem {
     pub datetime: String,
     pub headline: String,
@@ -47,7 +45,7 @@ impl FetchNews for IEXProvider {
         assert_is_valid!(for_symbol);
 
         if max_items < 1 || max_items > 50 {
-            return Err(RequestError::BadRequestError);
+            return Err(RequestErrorKind::BadRequestError.into());
         }
 
         let api_url = self.make_api_url(
@@ -55,7 +53,7 @@ impl FetchNews for IEXProvider {
             None,
         );
 
-        let response: RequestResult<IEXNewsFeed> = request::make_json_call(api_url);
+        let response: RequestResult<IEXNewsFeed> = self.get_json(api_url);
         match response {
             Ok(values) => {
                 record_api_use(ApiName::NewsFeed);
@@ -84,7 +82,7 @@ impl FetchNews for IEXProvider {
             for_symbol, start_date, max_items
         );
         assert_is_valid!(for_symbol);
-        Err(RequestError::Unsupported)
+        Err(RequestErrorKind::Unsupported.into())
     }
 }
 
@@ -103,3 +101,31 @@ fn to_news_item(v: &IEXNewsItem) -> RequestResult<Snapshot<NewsItem>> {
         },
     })
 }
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture::replay_provider;
+    use fin_model::news::FetchNews;
+    use fin_model::prelude::*;
+
+    #[test]
+    fn test_latest_news() {
+        let news = replay_provider()
+            .latest_news("AAPL".parse().unwrap(), 2)
+            .unwrap();
+        assert_eq!(news.len(), 2);
+        assert_eq!(
+            news[0].date,
+            "2019-06-28T00:00:00".parse::<DateTime>().unwrap()
+        );
+        assert_eq!(
+            news[0].data.headline,
+            "Apple shifts Mac Pro production".to_string()
+        );
+        assert_eq!(news[1].data.language, Some("en".to_string()));
+    }
+}
@@ -0,0 +1,321 @@
+/*!
+IEX API wrapper
+*/
+
+use chrono::{Datelike, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+use fin_model::options::*;
+use fin_model::prelude::*;
+
+use crate::internal::convert::*;
+use crate::internal::metric::{record_api_use, ApiName};
+use crate::IEXProvider;
+
+// ------------------------------------------------------------------------------------------------
+// API Types (internal)
+// ------------------------------------------------------------------------------------------------
+
+type IEXExpirations = Vec<String>;
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct IEXOption {
+    pub symbol: String,
+    pub id: Option<String>,
+    pub expiration_date: String,
+    pub contract_size: Option<u32>,
+    pub strike_price: f64,
+    pub closing_price: Option<f64>,
+    pub side: String,
+    pub volume: Option<u64>,
+    pub open_interest: Option<u64>,
+    pub bid: Option<f64>,
+    pub ask: Option<f64>,
+    pub last_updated: Option<String>,
+}
+
+type IEXOptions = Vec<IEXOption>;
+
+// ------------------------------------------------------------------------------------------------
+// Trait Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl FetchOptionChain for IEXProvider {
+    /// Note that IEX lists expirations by month, the expirations returned are
+    /// those of the contracts listed in each month, including weekly and
+    /// holiday-adjusted expirations. This requires a call for the contracts of
+    /// each month, as well as the call for the months themselves.
+    fn expirations(&self, for_symbol: Symbol) -> RequestResult<Vec<Date>> {
+        debug!(
+            "IEXProvider::<FetchOptionChain>::expirations for_symbol: {}",
+            for_symbol
+        );
+        assert_is_valid!(for_symbol);
+
+        let mut expirations: Vec<Date> = Vec::new();
+        for month in self.expiration_months(&for_symbol)? {
+            expirations.extend(self.month_expirations(&for_symbol, &month)?);
+        }
+        expirations.sort();
+        expirations.dedup();
+        Ok(expirations)
+    }
+
+    /// Note that IEX returns the contracts for all expirations in the month of
+    /// `expiration`, only those expiring on `expiration` are included in the
+    /// chain. IEX does not report implied volatility, and the last price is the
+    /// closing price of the contract.
+    fn option_chain(
+        &self,
+        for_symbol: Symbol,
+        expiration: Date,
+    ) -> RequestResult<Snapshot<OptionChain>> {
+        debug!(
+            "IEXProvider::<FetchOptionChain>::option_chain for_symbol: {}, expiration: {}",
+            for_symbol, expiration
+        );
+        assert_is_valid!(for_symbol);
+
+        let options = self.month_options(&for_symbol, &expiration.format("%Y%m").to_string())?;
+        let dc = self.get_default_currency();
+        let mut quotes: Vec<OptionQuote> = Vec::new();
+        let mut date: Option<DateTime> = None;
+        for v in options {
+            if expiration_from_string(&v.expiration_date)? != expiration {
+                continue;
+            }
+            if let Some(updated) = optional_date_from_string(&v.last_updated)? {
+                let updated = match updated.and_hms_opt(0, 0, 0) {
+                    Some(updated) => updated,
+                    None => return Err(RequestErrorKind::BadResponseError.into()),
+                };
+                date = Some(date.map_or(updated, |date| date.max(updated)));
+            }
+            quotes.push(to_option_quote(dc, &for_symbol, v)?);
+        }
+        quotes.sort_by_key(|quote| {
+            (
+                quote.contract.strike.minor_amount(),
+                quote.contract.option_type == OptionType::Put,
+            )
+        });
+        Ok(Snapshot {
+            date: date.unwrap_or_else(|| Utc::now().naive_utc()),
+            data: OptionChain {
+                underlying: for_symbol,
+                expiration,
+                quotes,
+            },
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl IEXProvider {
+    /// The months, as `YYYYMM`, in which IEX lists contracts on `for_symbol`.
+    pub(crate) fn expiration_months(&self, for_symbol: &Symbol) -> RequestResult<Vec<String>> {
+        let api_url = self.make_api_url(format!("/stock/{}/options", for_symbol), None);
+
+        let response: RequestResult<IEXExpirations> = self.get_json(api_url);
+        match response {
+            Ok(months) => {
+                record_api_use(ApiName::OptionExpirations);
+                Ok(months)
+            }
+            Err(err) => {
+                warn!(
+                    "IEXProvider::<FetchOptionChain>::expirations returning error: {:?}",
+                    err
+                );
+                Err(err)
+            }
+        }
+    }
+
+    /// The distinct expirations of the contracts on `for_symbol` listed in `month`.
+    pub(crate) fn month_expirations(
+        &self,
+        for_symbol: &Symbol,
+        month: &str,
+    ) -> RequestResult<Vec<Date>> {
+        let mut expirations = self
+            .month_options(for_symbol, month)?
+            .iter()
+            .map(|v| expiration_from_string(&v.expiration_date))
+            .collect::<RequestResult<Vec<Date>>>()?;
+        expirations.sort();
+        expirations.dedup();
+        Ok(expirations)
+    }
+
+    fn month_options(&self, for_symbol: &Symbol, month: &str) -> RequestResult<IEXOptions> {
+        let api_url = self.make_api_url(format!("/stock/{}/options/{}", for_symbol, month), None);
+
+        let response: RequestResult<IEXOptions> = self.get_json(api_url);
+        match response {
+            Ok(options) => {
+                record_api_use(ApiName::Options);
+                Ok(options)
+            }
+            Err(err) => {
+                warn!(
+                    "IEXProvider::<FetchOptionChain>::option_chain returning error: {:?}",
+                    err
+                );
+                Err(err)
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Implementations
+// ------------------------------------------------------------------------------------------------
+
+/// IEX expirations are either a month, `YYYYMM`, or a date, `YYYYMMDD`.
+fn expiration_from_string(expiration: &str) -> RequestResult<Date> {
+    let parsed = match expiration.len() {
+        6 => Date::parse_from_str(&format!("{}01", expiration), "%Y%m%d").map(third_friday),
+        8 => Date::parse_from_str(expiration, "%Y%m%d"),
+        _ => Date::parse_from_str(expiration, "%Y-%m-%d"),
+    };
+    match parsed {
+        Err(err) => {
+            warn!(
+                "doesn't look like an expiration: '{}', error: {}",
+                expiration, err
+            );
+            Err(RequestErrorKind::BadResponseError.into())
+        }
+        Ok(date) => Ok(date),
+    }
+}
+
+fn third_friday(first_of_month: Date) -> Date {
+    let offset = (Weekday::Fri.num_days_from_monday() + 7
+        - first_of_month.weekday().num_days_from_monday())
+        % 7;
+    first_of_month.with_day(1 + offset + 14).unwrap()
+}
+
+/// IEX options are listed in the U.S. and so are American style.
+fn to_option_quote(dc: &String, underlying: &Symbol, v: IEXOption) -> RequestResult<OptionQuote> {
+    let money = |value: Option<f64>| value.map(|v| price_from_float(dc, v)).transpose();
+    Ok(OptionQuote {
+        contract: OptionContract {
+            underlying: underlying.clone(),
+            expiration: expiration_from_string(&v.expiration_date)?,
+            strike: price_from_float(dc, v.strike_price)?,
+            option_type: match v.side.to_lowercase().as_str() {
+                "call" => OptionType::Call,
+                "put" => OptionType::Put,
+                side => {
+                    warn!("doesn't look like an option side: '{}'", side);
+                    return Err(RequestErrorKind::BadResponseError.into());
+                }
+            },
+            style: ExerciseStyle::American,
+            multiplier: v.contract_size.unwrap_or(100),
+        },
+        bid: money(v.bid)?,
+        ask: money(v.ask)?,
+        last: money(v.closing_price)?,
+        volume: v.volume,
+        open_interest: v.open_interest,
+        implied_volatility: None,
+    })
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::replay_provider;
+    use steel_cent::currency::USD;
+
+    fn aapl() -> Symbol {
+        "AAPL".parse().unwrap()
+    }
+
+    #[test]
+    fn test_expirations() {
+        let expirations = replay_provider().expirations(aapl()).unwrap();
+        assert_eq!(
+            expirations,
+            vec![
+                Date::from_ymd_opt(2019, 10, 18).unwrap(),
+                Date::from_ymd_opt(2019, 10, 25).unwrap(),
+                Date::from_ymd_opt(2019, 11, 8).unwrap(),
+                Date::from_ymd_opt(2019, 11, 15).unwrap(),
+                Date::from_ymd_opt(2019, 12, 20).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_option_chain() {
+        let chain = replay_provider()
+            .option_chain(aapl(), Date::from_ymd_opt(2019, 10, 18).unwrap())
+            .unwrap();
+        assert_eq!(
+            chain.date,
+            Date::from_ymd_opt(2019, 10, 11)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+        );
+        assert_eq!(chain.data.quotes.len(), 3);
+        assert_eq!(chain.data.calls().count(), 2);
+
+        let put = chain
+            .data
+            .quote_for(OptionType::Put, &Money::of_major(USD, 210))
+            .unwrap();
+        assert_eq!(put.contract.occ_symbol(), "AAPL  191018P00210000");
+        assert_eq!(put.bid, Some(Money::of_major_minor(USD, 0, 53)));
+        assert_eq!(put.ask, Some(Money::of_major_minor(USD, 0, 56)));
+        assert_eq!(put.open_interest, Some(26_741));
+        assert_eq!(put.implied_volatility, None);
+        assert_eq!(chain.data.quotes[2].contract, put.contract);
+    }
+
+    #[test]
+    fn test_option_chain_weekly() {
+        let chain = replay_provider()
+            .option_chain(aapl(), Date::from_ymd_opt(2019, 10, 25).unwrap())
+            .unwrap();
+        assert_eq!(chain.data.quotes.len(), 1);
+        let call = chain
+            .data
+            .quote_for(OptionType::Call, &Money::of_major(USD, 210))
+            .unwrap();
+        assert_eq!(
+            call.contract.expiration,
+            Date::from_ymd_opt(2019, 10, 25).unwrap()
+        );
+        assert_eq!(call.contract.occ_symbol(), "AAPL  191025C00210000");
+    }
+
+    #[test]
+    fn test_third_friday() {
+        assert_eq!(
+            third_friday(Date::from_ymd(2019, 11, 1)),
+            Date::from_ymd(2019, 11, 15)
+        );
+        assert_eq!(
+            third_friday(Date::from_ymd(2020, 5, 1)),
+            Date::from_ymd(2020, 5, 15)
+        );
+        assert_eq!(
+            third_friday(Date::from_ymd(2020, 6, 1)),
+            Date::from_ymd(2020, 6, 19)
+        );
+    }
+}
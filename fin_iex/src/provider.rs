@@ -1,8 +1,9 @@
 /*!
 An Implementation of the Financial Model `Provider` trait for IEX.
 
-Note that this implementation uses a number of environment variables
-for configuration of the IEX endpoint URL.
+Note that `Provider::new` uses a number of environment variables
+for configuration of the IEX endpoint URL, use `IEXProvider::with_config`
+to construct a provider from explicit values or a configuration file.
 
 ```bash
 $ export IEX_HOST=cloud
@@ -12,16 +13,23 @@ $
 $ IEX_TOKEN={your-dev-token} cargo run iext
 ```
 
+Requests may be sent to a server other than IEX Cloud, through a proxy,
+with timeouts and additional headers, by setting the `base_url` and `http`
+values of the configuration.
+
 */
 
 use std::collections::HashMap;
 
-use steel_cent::currency::with_code;
+use serde::de::DeserializeOwned;
 
 use fin_model::prelude::*;
+use fin_model::provider::{Capabilities, Operation};
 
-use crate::internal::env;
+use crate::config::IEXConfig;
+use crate::fixture::Fixtures;
 use crate::internal::metric;
+use crate::internal::request;
 
 // ------------------------------------------------------------------------------------------------
 // Public Types & Traits
@@ -30,13 +38,32 @@ use crate::internal::metric;
 /// This is the provider type for IEX, it contains communication parameters
 /// and defaults used when making requests to the IEX endpoints.
 pub struct IEXProvider {
-    host: String,
-    version: String,
+    base_url: String,
     token: String,
     default_currency: String,
+    fixtures: Option<Fixtures>,
+    client: reqwest::Client,
 }
 
 impl IEXProvider {
+    /// Construct a new provider from an explicit configuration, the
+    /// configuration is validated before the provider is returned.
+    pub fn with_config(config: IEXConfig) -> RequestResult<Self> {
+        config.validate()?;
+        let base_url = config.api_base_url();
+        info!(
+            "IEXProvider::with_config base_url: {}, token: <<private>>, default_currency: {}",
+            base_url, config.default_currency
+        );
+        Ok(IEXProvider {
+            base_url,
+            token: config.token,
+            default_currency: config.default_currency,
+            fixtures: config.fixtures,
+            client: request::make_client(&config.http)?,
+        })
+    }
+
     /// Construct a valid URL from the endpoint path and any additional query
     /// parameters, such as `format=json`.
     pub fn make_api_url(
@@ -54,83 +81,72 @@ impl IEXProvider {
         };
         params.push(format!("token={}", self.token));
         format!(
-            "https://{}.iexapis.com/{}/{}{}{}",
-            self.host,
-            self.version,
+            "{}/{}{}{}",
+            self.base_url,
             path,
             if path.contains("?") { "&" } else { "?" },
             params.join("&")
         )
     }
+
+    /// Make a call to `api_url`, returning the response body; if fixtures
+    /// are configured the response is recorded or replayed.
+    pub(crate) fn get_text(&self, api_url: String) -> RequestResult<String> {
+        match &self.fixtures {
+            Some(fixtures) => fixtures.get(&self.client, &self.base_url, api_url, &self.token),
+            None => request::make_api_call(&self.client, api_url),
+        }
+    }
+
+    /// Make a call to `api_url`, returning the response body parsed as JSON;
+    /// if fixtures are configured the response is recorded or replayed.
+    pub(crate) fn get_json<T: DeserializeOwned>(&self, api_url: String) -> RequestResult<T> {
+        match &self.fixtures {
+            Some(fixtures) => {
+                let body =
+                    fixtures.get(&self.client, &self.base_url, api_url.clone(), &self.token)?;
+                request::parse_json(&api_url, &body)
+            }
+            None => request::make_json_call(&self.client, api_url),
+        }
+    }
 }
 
 // ------------------------------------------------------------------------------------------------
 // Trait Implementations
 // ------------------------------------------------------------------------------------------------
 
-const ENV_HOST: &str = "IEX_HOST";
-const ENV_VERSION: &str = "IEX_VERSION";
-const ENV_TOKEN: &str = "IEX_TOKEN";
-
-const DEFAULT_CURRENCY: &str = "USD";
+/// IEX Cloud covers securities listed on U.S. markets only.
+const MARKETS: &[&str] = &["XNAS", "XNYS", "XASE", "ARCX", "BATS", "IEXG"];
+
+/// The operations implemented, note that `FetchPriceRangeSeries::from`,
+/// `FetchPriceRangeSeries::for_period`, and `FetchNews::news_from` are not.
+const OPERATIONS: &[Operation] = &[
+    Operation::Peers,
+    Operation::TargetPrice,
+    Operation::ConsensusRating,
+    Operation::ConsensusEPS,
+    Operation::Earnings,
+    Operation::ReportedIncome,
+    Operation::ReportedBalanceSheet,
+    Operation::ReportedCashFlow,
+    Operation::CoreStatistics,
+    Operation::LatestNews,
+    Operation::OptionExpirations,
+    Operation::OptionChain,
+    Operation::LatestPriceOnly,
+    Operation::RealTime,
+    Operation::Delayed,
+    Operation::IntraDay,
+    Operation::Last,
+    Operation::Dividends,
+    Operation::Splits,
+    Operation::CorporateActions,
+];
 
 impl Provider for IEXProvider {
     fn new() -> RequestResult<Self> {
-        // https://iextrading.com/api-exhibit-a/
-        let host = match env::get_from_environment(ENV_HOST, Some("cloud".to_string())) {
-            Some(host) => {
-                if host == "cloud" || host == "sandbox" {
-                    debug!("setting IEX host to {}", host);
-                    host
-                } else {
-                    return Err(env::invalid_environment(ENV_HOST, host));
-                }
-            }
-            None => return Err(env::missing_environment(ENV_HOST)),
-        };
-        let version = match env::get_from_environment(ENV_VERSION, Some("stable".to_string())) {
-            Some(version) => {
-                if version == "stable" || host == "beta" {
-                    debug!("setting IEX version to {}", version);
-                    version
-                } else {
-                    return Err(env::invalid_environment(ENV_VERSION, version));
-                }
-            }
-            None => return Err(env::missing_environment(ENV_VERSION)),
-        };
-        let token = match env::get_from_environment(ENV_TOKEN, None) {
-            Some(token) => {
-                if token.starts_with("Tpk_") || token.starts_with("pk_") || token.starts_with("sk_")
-                {
-                    debug!("setting IEX version to <<private>>");
-                    token
-                } else {
-                    return Err(env::invalid_environment(ENV_TOKEN, token));
-                }
-            }
-            None => return Err(env::missing_environment(ENV_TOKEN)),
-        };
-        match with_code(DEFAULT_CURRENCY) {
-            Some(_) => (),
-            None => {
-                return Err(RequestError::ConfigurationError(format!(
-                    "invalid currency code: {}",
-                    DEFAULT_CURRENCY
-                )))
-            }
-        }
-
-        info!(
-            "IEXProvider::<Provider>::new host: {}, version: {}, token: {}, default_currency: {}",
-            host, version, token, DEFAULT_CURRENCY
-        );
-        Ok(IEXProvider {
-            host,
-            version,
-            token,
-            default_currency: DEFAULT_CURRENCY.to_string(),
-        })
+        IEXProvider::with_config(IEXConfig::from_environment()?)
     }
 
     fn attribution(&self) -> String {
@@ -145,7 +161,67 @@ impl Provider for IEXProvider {
         &self.default_currency
     }
 
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            operations: OPERATIONS.iter().cloned().collect(),
+            real_time: true,
+            delayed_by: Some(15),
+            markets: Some(MARKETS.iter().map(|mic| mic.to_string()).collect()),
+            currencies: vec![self.default_currency.clone()].into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
     fn finish(&self) {
         metric::record_to_log()
     }
 }
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fin_model::provider::RequestTrait;
+
+    #[test]
+    fn test_make_api_url() {
+        let provider = IEXProvider::with_config(IEXConfig::new("pk_123")).unwrap();
+        assert_eq!(
+            provider.make_api_url("stock/AAPL/quote".to_string(), None),
+            "https://cloud.iexapis.com/stable/stock/AAPL/quote?token=pk_123".to_string()
+        );
+
+        let provider = IEXProvider::with_config(
+            IEXConfig::new("pk_123")
+                .with_base_url("http://localhost:8080/iex")
+                .with_header("X-Gateway-Key", "example"),
+        )
+        .unwrap();
+        assert_eq!(
+            provider.make_api_url("stock/AAPL/chart/1m?chartByDay=true".to_string(), None),
+            "http://localhost:8080/iex/stable/stock/AAPL/chart/1m?chartByDay=true&token=pk_123"
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        assert!(IEXProvider::with_config(
+            IEXConfig::new("pk_123").with_header("Bad Name", "value")
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let provider = IEXProvider::with_config(IEXConfig::new("pk_123")).unwrap();
+        let capabilities = provider.capabilities();
+        assert!(capabilities.supports(Operation::RealTime));
+        assert!(capabilities.covers_market("XNAS"));
+        assert!(!capabilities.supports_any(RequestTrait::FetchPriceQuoteBatch));
+        assert_eq!(capabilities.max_batch_size(Operation::RealTimeBatch), None);
+    }
+}
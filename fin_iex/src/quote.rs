@@ -10,19 +10,17 @@ use serde::{Deserialize, Serialize};
 use fin_model::prelude::*;
 use fin_model::quote::*;
 use fin_model::reporting::FinancialPeriod;
-use fin_model::symbol::is_valid;
 
 use crate::internal::convert::*;
 use crate::internal::metric::{record_api_usage, record_api_use, ApiName};
-use crate::internal::request;
 use crate::IEXProvider;
 
 // ------------------------------------------------------------------------------------------------
 // API Types (internal)
 // ------------------------------------------------------------------------------------------------
 
-#[serde(rename_all = "camelCase")]
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
 struct IEXQuote {
     pub symbol: String,
     pub company_name: String,
@@ -75,8 +73,8 @@ struct IEXQuote {
     pub avg_total_volume: f64,
 }
 
-#[serde(rename_all = "camelCase")]
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
 struct IEXDelayedQuote {
     symbol: String,
     delayed_price: f64,
@@ -88,8 +86,8 @@ struct IEXDelayedQuote {
     total_volume: Option<f64>,
 }
 
-#[serde(rename_all = "camelCase")]
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct IEXHistoricalPrice {
     pub date: String,
     pub label: String,
@@ -118,8 +116,8 @@ pub struct IEXHistoricalPrice {
 
 type IEXHistoricalPrices = Vec<IEXHistoricalPrice>;
 
-#[serde(rename_all = "camelCase")]
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct IEXIntradayPrice {
     pub date: String,
     pub minute: String,
@@ -164,7 +162,7 @@ impl FetchPriceQuote for IEXProvider {
 
         let api_url = self.make_api_url(format!("stock/{}/price", for_symbol), None);
 
-        match request::make_api_call(api_url) {
+        match self.get_text(api_url) {
             Ok(raw_price) => match price_from_string(self.get_default_currency(), &raw_price) {
                 Ok(price) => {
                     record_api_use(ApiName::Price);
@@ -172,7 +170,7 @@ impl FetchPriceQuote for IEXProvider {
                 }
                 Err(err) => {
                     warn!("IEXProvider::<FetchPriceQuote>::latest_price_only parser error: {:?} in {}", err, raw_price);
-                    Err(RequestError::BadResponseError)
+                    Err(RequestErrorKind::BadResponseError.into())
                 }
             },
             Err(err) => {
@@ -194,7 +192,7 @@ impl FetchPriceQuote for IEXProvider {
 
         let api_url = self.make_api_url(format!("stock/{}/quote", for_symbol), None);
 
-        let response: RequestResult<IEXQuote> = request::make_json_call(api_url);
+        let response: RequestResult<IEXQuote> = self.get_json(api_url);
         let dc = self.get_default_currency();
         match response {
             Ok(quote) => {
@@ -252,7 +250,7 @@ impl FetchPriceQuote for IEXProvider {
 
         let api_url = self.make_api_url(format!("stock/{}/delayed-quote", for_symbol), None);
 
-        let response: RequestResult<IEXDelayedQuote> = request::make_json_call(api_url);
+        let response: RequestResult<IEXDelayedQuote> = self.get_json(api_url);
         let dc = self.get_default_currency();
         match response {
             Ok(quote) => {
@@ -308,11 +306,11 @@ impl FetchPriceRangeSeries for IEXProvider {
             None,
         );
 
-        let response: RequestResult<IEXIntradayPrices> = request::make_json_call(api_url);
+        let response: RequestResult<IEXIntradayPrices> = self.get_json(api_url);
         let dc = self.get_default_currency();
         match response {
             Ok(values) => {
-                record_api_usage(ApiName::Intraday, values.len() as u16);
+                record_api_usage(ApiName::Intraday, values.len());
                 let series: RequestResult<Vec<Snapshot<PriceRange>>> = values
                     .iter()
                     .map(|v| intraday_to_price_range(dc, v))
@@ -320,7 +318,7 @@ impl FetchPriceRangeSeries for IEXProvider {
                 match series {
                     Ok(data) => Ok(Some(PriceRangeSeries {
                         interval: SeriesInterval::Day,
-                        intra_interval: Some(Duration::new((60 * interval_minutes) as u64, 0)),
+                        intra_interval: Some(Duration::new(60 * interval_minutes as u64, 0)),
                         series: data,
                     })),
                     Err(err) => Err(err),
@@ -346,7 +344,64 @@ impl FetchPriceRangeSeries for IEXProvider {
             for_symbol, interval
         );
         assert_is_valid!(for_symbol);
+        self.historical(for_symbol, interval, true)
+    }
+
+    fn from(
+        &self,
+        for_symbol: Symbol,
+        start_date: DateTime,
+        interval: SeriesInterval,
+    ) -> RequestResult<PriceRangeSeries> {
+        debug!(
+            "IEXProvider::<FetchPriceRangeSeries>::from for_symbol: {}, start: {}, interval: {:?}",
+            for_symbol, start_date, interval
+        );
+        assert_is_valid!(for_symbol);
+        Err(RequestErrorKind::Unsupported.into())
+    }
+
+    fn for_period(
+        &self,
+        for_symbol: Symbol,
+        period: FinancialPeriod,
+    ) -> RequestResult<PriceRangeSeries> {
+        debug!(
+            "IEXProvider::<FetchPriceRangeSeries>::for_period for_symbol: {}, period: {}",
+            for_symbol, period
+        );
+        assert_is_valid!(for_symbol);
+        Err(RequestErrorKind::Unsupported.into())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl IEXProvider {
+    /// As `FetchPriceRangeSeries::last`, but returns the prices and volumes
+    /// as traded, without any adjustment for splits or dividends. These can
+    /// then be adjusted with `fin_model::corporate::adjusted`.
+    pub fn unadjusted_last(
+        &self,
+        for_symbol: Symbol,
+        interval: SeriesInterval,
+    ) -> RequestResult<PriceRangeSeries> {
+        debug!(
+            "IEXProvider::unadjusted_last for_symbol: {}, interval: {:?}",
+            for_symbol, interval
+        );
+        assert_is_valid!(for_symbol);
+        self.historical(for_symbol, interval, false)
+    }
 
+    fn historical(
+        &self,
+        for_symbol: Symbol,
+        interval: SeriesInterval,
+        adjusted: bool,
+    ) -> RequestResult<PriceRangeSeries> {
         let range = match interval {
             SeriesInterval::Day => "1d",
             SeriesInterval::FiveDays => "5d",
@@ -364,14 +419,14 @@ impl FetchPriceRangeSeries for IEXProvider {
             None,
         );
 
-        let response: RequestResult<IEXHistoricalPrices> = request::make_json_call(api_url);
+        let response: RequestResult<IEXHistoricalPrices> = self.get_json(api_url);
         let dc = self.get_default_currency();
         match response {
             Ok(values) => {
-                record_api_usage(ApiName::Historical, values.len() as u16);
+                record_api_usage(ApiName::Historical, values.len());
                 let series: RequestResult<Vec<Snapshot<PriceRange>>> = values
                     .iter()
-                    .map(|v| historical_to_price_range(dc, v))
+                    .map(|v| historical_to_price_range(dc, v, adjusted))
                     .collect();
                 match series {
                     Ok(data) => Ok(PriceRangeSeries {
@@ -383,41 +438,11 @@ impl FetchPriceRangeSeries for IEXProvider {
                 }
             }
             Err(err) => {
-                println!(
-                    "IEXProvider::<FetchPriceRangeSeries>::intra_day returning error: {:?}",
-                    err
-                );
+                warn!("IEXProvider::historical returning error: {:?}", err);
                 Err(err)
             }
         }
     }
-
-    fn from(
-        &self,
-        for_symbol: Symbol,
-        start_date: DateTime,
-        interval: SeriesInterval,
-    ) -> RequestResult<PriceRangeSeries> {
-        debug!(
-            "IEXProvider::<FetchPriceRangeSeries>::from for_symbol: {}, start: {}, interval: {:?}",
-            for_symbol, start_date, interval
-        );
-        assert_is_valid!(for_symbol);
-        Err(RequestError::Unsupported)
-    }
-
-    fn for_period(
-        &self,
-        for_symbol: Symbol,
-        period: FinancialPeriod,
-    ) -> RequestResult<PriceRangeSeries> {
-        debug!(
-            "IEXProvider::<FetchPriceRangeSeries>::for_period for_symbol: {}, period: {}",
-            for_symbol, period
-        );
-        assert_is_valid!(for_symbol);
-        Err(RequestError::Unsupported)
-    }
 }
 
 // ------------------------------------------------------------------------------------------------
@@ -443,15 +468,137 @@ fn intraday_to_price_range(
 fn historical_to_price_range(
     dc: &String,
     v: &IEXHistoricalPrice,
+    adjusted: bool,
 ) -> RequestResult<Snapshot<PriceRange>> {
+    let (open, high, low, close, volume) = if adjusted {
+        (v.open, v.high, v.low, v.close, v.volume)
+    } else {
+        (
+            v.unadjusted_open,
+            v.unadjusted_high,
+            v.unadjusted_low,
+            v.unadjusted_close,
+            v.unadjusted_volume,
+        )
+    };
     Ok(Snapshot {
         date: datetime_from_date_string(&v.date)?,
         data: PriceRange {
-            open: price_from_float(dc, v.high)?,
-            close: price_from_float(dc, v.high)?,
-            high: price_from_float(dc, v.high)?,
-            low: price_from_float(dc, v.low)?,
-            volume: Some(v.volume as u64),
+            open: price_from_float(dc, open)?,
+            close: price_from_float(dc, close)?,
+            high: price_from_float(dc, high)?,
+            low: price_from_float(dc, low)?,
+            volume: Some(volume as u64),
         },
     })
 }
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture::replay_provider;
+    use fin_model::corporate::{split_adjusted, FetchCorporateActions};
+    use fin_model::prelude::*;
+    use fin_model::quote::{FetchPriceQuote, FetchPriceRangeSeries, SeriesInterval};
+    use std::time::Duration;
+    use steel_cent::currency::USD;
+
+    fn aapl() -> Symbol {
+        "AAPL".parse().unwrap()
+    }
+
+    #[test]
+    fn test_latest_price_only() {
+        let price = replay_provider().latest_price_only(aapl()).unwrap();
+        assert_eq!(price, Money::of_major_minor(USD, 201, 55));
+    }
+
+    #[test]
+    fn test_real_time() {
+        let quote = replay_provider().real_time(aapl()).unwrap();
+        assert_eq!(quote.data.latest.price, Money::of_major_minor(USD, 197, 92));
+        let range = quote.data.range.unwrap();
+        assert_eq!(range.close, Money::of_major_minor(USD, 197, 92));
+        assert_eq!(range.volume, Some(31_110_642));
+        assert_eq!(
+            quote.data.extended.unwrap().price,
+            Money::of_major_minor(USD, 198, 15)
+        );
+    }
+
+    #[test]
+    fn test_delayed() {
+        let quote = replay_provider().delayed(aapl()).unwrap();
+        assert_eq!(quote.data.latest.price, Money::of_major_minor(USD, 197, 92));
+        assert_eq!(quote.data.delayed_by, 15);
+        assert_eq!(quote.data.trade_size, Some(1_500));
+        assert_eq!(quote.data.volume, Some(31_110_642));
+    }
+
+    #[test]
+    fn test_intra_day() {
+        let series = replay_provider().intra_day(aapl(), 5).unwrap().unwrap();
+        assert_eq!(series.intra_interval, Some(Duration::from_secs(300)));
+        assert_eq!(series.series.len(), 2);
+        assert_eq!(
+            series.series[0].date,
+            "2019-06-28T09:30:00".parse::<DateTime>().unwrap()
+        );
+        assert_eq!(series.series[1].data.volume, Some(9_233));
+    }
+
+    #[test]
+    fn test_last() {
+        let provider = replay_provider();
+        let series = provider.last(aapl(), SeriesInterval::FiveDays).unwrap();
+        assert_eq!(series.interval, SeriesInterval::FiveDays);
+        assert_eq!(series.series.len(), 2);
+        assert_eq!(
+            series.series[0].date,
+            "2019-06-27T00:00:00".parse::<DateTime>().unwrap()
+        );
+        assert_eq!(
+            series.series[0].data.low,
+            Money::of_major_minor(USD, 199, 57)
+        );
+        assert_eq!(
+            series.series[0].data.open,
+            Money::of_major_minor(USD, 200, 29)
+        );
+        assert_eq!(
+            series.series[0].data.close,
+            Money::of_major_minor(USD, 199, 74)
+        );
+
+        let error = provider
+            .last(aapl(), SeriesInterval::OneYear)
+            .err()
+            .unwrap();
+        assert!(matches!(
+            error.kind(),
+            RequestErrorKind::ConfigurationError(_)
+        ));
+    }
+
+    #[test]
+    fn test_unadjusted_last() {
+        let provider = replay_provider();
+        let unadjusted = provider
+            .unadjusted_last(aapl(), SeriesInterval::OneMonth)
+            .unwrap();
+        assert_eq!(
+            unadjusted.series[0].data.close,
+            Money::of_major_minor(USD, 645, 57)
+        );
+        assert_eq!(unadjusted.series[0].data.volume, Some(12_497_800));
+
+        let splits = provider.corporate_actions(aapl(), SeriesInterval::FiveYears);
+        let adjusted = split_adjusted(&unadjusted, &splits.unwrap());
+        let expected = provider.last(aapl(), SeriesInterval::OneMonth).unwrap();
+        assert_eq!(adjusted.series[0].data.close, expected.series[0].data.close);
+        assert_eq!(adjusted.series[1].data.close, expected.series[1].data.close);
+    }
+}
@@ -4,8 +4,12 @@ Provides structs and traits that represent common market analysis.
 
 use std::collections::HashMap;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::prelude::*;
 use crate::reporting::FinancialPeriod;
+use crate::series::amount;
 
 // ------------------------------------------------------------------------------------------------
 // Public Types
@@ -15,7 +19,8 @@ use crate::reporting::FinancialPeriod;
 pub type Counter = u32;
 
 /// The type of an analyst recommendation/position.
-#[derive(PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum RatingType {
     /// Also known as strong buy and _on the recommended list_. Needless to say,
     /// buy is a recommendation to purchase a specific security.
@@ -37,6 +42,8 @@ pub enum RatingType {
 }
 
 /// The set of recommendation trends over some period of time.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Ratings {
     /// a mapping of available rating types to counts, not all types may be available
     pub ratings: HashMap<RatingType, Counter>,
@@ -45,20 +52,28 @@ pub struct Ratings {
 }
 
 /// Consensus price targets; high, low, and average.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PriceTarget {
     /// anticipated high price
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::money"))]
     pub high: Money,
     /// anticipated low price
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::money"))]
     pub low: Money,
     /// anticipated average price
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::money"))]
     pub average: Money,
     /// number of analysts that provided recommendations
     pub number_of_analysts: Counter,
 }
 
 /// Consensus Earnings per Share (EPS) targets for some fiscal period.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EPSConsensus {
     /// anticipated earnings per share
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::money"))]
     pub consensus: Money,
     /// number of analysts that provided recommendations
     pub number_of_estimates: Counter,
@@ -70,6 +85,46 @@ pub struct EPSConsensus {
     pub next_report_date: Date,
 }
 
+/// When, relative to the trading day, earnings were announced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ReportTiming {
+    /// announced before the market opened.
+    BeforeMarketOpen,
+    /// announced during market hours.
+    DuringMarketHours,
+    /// announced after the market closed.
+    AfterMarketClose,
+}
+
+/// Actual reported Earnings per Share (EPS) for some fiscal period, compared
+/// with the consensus estimate for the same period.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EarningsReport {
+    /// reported earnings per share
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::money"))]
+    pub actual_eps: Money,
+    /// consensus earnings per share, if there were any estimates
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::option_money"))]
+    pub consensus_eps: Option<Money>,
+    /// number of analysts that provided estimates
+    pub number_of_estimates: Option<Counter>,
+    /// the amount by which `actual_eps` exceeded `consensus_eps`, negative for a miss
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::option_money"))]
+    pub surprise: Option<Money>,
+    /// `surprise` as a percentage of the absolute value of `consensus_eps`
+    pub surprise_percent: Option<f64>,
+    /// reported for this period
+    pub fiscal_period: FinancialPeriod,
+    /// the company's end date for `fiscal_period`
+    pub fiscal_end_date: Option<Date>,
+    /// the date earnings were reported
+    pub report_date: Date,
+    /// when, on `report_date`, earnings were reported
+    pub timing: Option<ReportTiming>,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Public Traits
 // ------------------------------------------------------------------------------------------------
@@ -93,6 +148,21 @@ impl Ratings {
     }
 }
 
+impl EarningsReport {
+    /// Calculate the surprise, and surprise percentage, of `actual` over `consensus`;
+    /// the percentage is `None` when `consensus` is zero.
+    pub fn surprise_of(actual: &Money, consensus: &Money) -> (Money, Option<f64>) {
+        let surprise = *actual - *consensus;
+        let consensus = amount(consensus);
+        let percent = if consensus == 0.0 {
+            None
+        } else {
+            Some(amount(&surprise) / consensus.abs() * 100.0)
+        };
+        (surprise, percent)
+    }
+}
+
 /// This trait is implemented by providers to return a set of symbols that are expected
 /// to represent peer companies to `for_symbol`. This set of peers could be provided by
 /// the market or the service provider itself.
@@ -112,3 +182,36 @@ pub trait AnalystRecommendations {
     /// Return the consensus earnings per share (EPS) for the symbol
     fn consensus_eps(&self, for_symbol: Symbol) -> RequestResult<Vec<EPSConsensus>>;
 }
+
+/// This trait is implemented by providers to return historical earnings reports.
+pub trait FetchEarnings {
+    /// Return the `last` earnings reports for the symbol, most recent first.
+    fn earnings(&self, for_symbol: Symbol, last: usize) -> RequestResult<Vec<EarningsReport>>;
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use steel_cent::currency::USD;
+
+    #[test]
+    fn test_surprise_of() {
+        let (surprise, percent) = EarningsReport::surprise_of(
+            &Money::of_major_minor(USD, 2, 18),
+            &Money::of_major_minor(USD, 2, 10),
+        );
+        assert_eq!(surprise, Money::of_major_minor(USD, 0, 8));
+        assert!((percent.unwrap() - 3.8095).abs() < 0.0001);
+
+        let (surprise, percent) = EarningsReport::surprise_of(
+            &Money::of_major_minor(USD, 0, 5),
+            &Money::of_major_minor(USD, 0, 0),
+        );
+        assert_eq!(surprise, Money::of_major_minor(USD, 0, 5));
+        assert_eq!(percent, None);
+    }
+}
@@ -0,0 +1,1001 @@
+/*!
+Provides a caching decorator for any provider.
+
+The `CachingProvider` wraps a provider and implements each of the _request
+traits_ the wrapped provider implements. Successful responses are stored in
+a `CacheBackend`, keyed by operation and arguments, and returned until the
+time-to-live (TTL) configured for the operation in `CacheSettings` expires.
+Errors are never cached.
+
+The default settings cache quotes for seconds, statistics and analyst data
+for a day, and company information and filings for a week; see
+`CacheSettings::default` for the complete list. Hit and miss counts are
+recorded per operation and available from `CachingProvider::statistics`.
+
+Two backends are provided, `MemoryCache` which holds values in memory, and
+`DiskCache` which writes values as JSON files, named `fin-cache-{hash}.json`,
+to a directory and so survives process restarts. The disk cache requires the
+`disk-cache` feature.
+
+## Example
+
+```rust
+use fin_model::cache::CachingProvider;
+use fin_model::prelude::*;
+use fin_model::provider::Operation;
+use fin_model::quote::{DelayedQuote, FetchPriceQuote, Quote};
+# use steel_cent::currency::USD;
+# struct ExampleProvider;
+# impl Provider for ExampleProvider {
+#     fn new() -> RequestResult<Self> { Ok(ExampleProvider) }
+#     fn attribution(&self) -> String { "Example".to_string() }
+#     fn url(&self) -> String { String::new() }
+#     fn get_default_currency(&self) -> &String { unimplemented!() }
+#     fn finish(&self) {}
+# }
+# impl FetchPriceQuote for ExampleProvider {
+#     fn latest_price_only(&self, _: Symbol) -> RequestResult<Money> { Ok(Money::of_major(USD, 1)) }
+#     fn real_time(&self, _: Symbol) -> RequestResult<Quote> { unimplemented!() }
+#     fn delayed(&self, _: Symbol) -> RequestResult<DelayedQuote> { unimplemented!() }
+# }
+
+let provider = CachingProvider::in_memory(ExampleProvider::new().unwrap());
+let symbol: Symbol = "AAPL".parse().unwrap();
+
+let first = provider.latest_price_only(symbol.clone()).unwrap();
+let second = provider.latest_price_only(symbol).unwrap();
+assert_eq!(first, second);
+
+let statistics = provider.statistics(Operation::LatestPriceOnly);
+assert_eq!((statistics.hits, statistics.misses), (1, 1));
+```
+*/
+
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::*;
+use crate::company::*;
+use crate::corporate::*;
+use crate::news::*;
+use crate::options::*;
+use crate::prelude::*;
+use crate::provider::{Capabilities, Operation};
+use crate::quote::*;
+use crate::reporting::FinancialPeriod;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The time-to-live for cached values, by operation. An operation with no
+/// time-to-live is not cached.
+#[derive(Debug, Clone)]
+pub struct CacheSettings {
+    ttl: HashMap<Operation, Duration>,
+}
+
+/// Hit and miss counts for cached operations.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CacheStatistics {
+    /// the number of requests answered from the cache
+    pub hits: u64,
+    /// the number of requests passed to the wrapped provider
+    pub misses: u64,
+}
+
+/// A value stored in a `CacheBackend`, the time it was stored, and the time
+/// after which a backend may evict it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry<T> {
+    pub value: T,
+    pub stored: SystemTime,
+    pub expires: SystemTime,
+}
+
+/// A simple in-memory cache backend.
+#[derive(Default)]
+pub struct MemoryCache {
+    entries: Mutex<HashMap<String, MemoryEntry>>,
+}
+
+/// A cache backend that stores each value as a JSON file in a directory.
+#[cfg(feature = "disk-cache")]
+#[derive(Debug)]
+pub struct DiskCache {
+    directory: std::path::PathBuf,
+}
+
+/// A provider that caches the responses of a wrapped provider.
+pub struct CachingProvider<P, B = MemoryCache> {
+    provider: P,
+    backend: B,
+    settings: CacheSettings,
+    statistics: Mutex<HashMap<Operation, CacheStatistics>>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Traits
+// ------------------------------------------------------------------------------------------------
+
+/// Values that may be stored in a cache, any backend may be used and so cached
+/// values must be serializable.
+pub trait Cacheable: Clone + Send + Sync + Serialize + DeserializeOwned + 'static {}
+
+/// Storage for cached values, keyed by string.
+pub trait CacheBackend {
+    /// Return the entry stored for `key`, if any.
+    fn get<T: Cacheable>(&self, key: &str) -> Option<CacheEntry<T>>;
+
+    /// Store `entry` for `key`, replacing any existing entry.
+    fn put<T: Cacheable>(&self, key: &str, entry: CacheEntry<T>);
+
+    /// Remove any entry stored for `key`.
+    fn remove(&self, key: &str);
+
+    /// Remove all entries.
+    fn clear(&self);
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+/// A type-erased value, the time it was stored, and the time it expires.
+type MemoryEntry = (Box<dyn Any + Send + Sync>, SystemTime, SystemTime);
+
+/// An entry as written to disk, the key is retained as file names are only a
+/// hash of the key.
+#[cfg(feature = "disk-cache")]
+#[derive(Debug, Serialize, Deserialize)]
+struct DiskEntry<T> {
+    key: String,
+    entry: CacheEntry<T>,
+}
+
+/// `Money` does not implement the serde traits, so prices are wrapped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedPrice {
+    #[serde(with = "crate::serialize::money")]
+    price: Money,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+const SECOND: u64 = 1;
+const MINUTE: u64 = 60 * SECOND;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+#[cfg(feature = "disk-cache")]
+const DISK_CACHE_PREFIX: &str = "fin-cache-";
+#[cfg(feature = "disk-cache")]
+const DISK_CACHE_SUFFIX: &str = ".json";
+
+impl CacheSettings {
+    /// Settings with no time-to-live for any operation, nothing is cached.
+    pub fn none() -> Self {
+        CacheSettings {
+            ttl: HashMap::new(),
+        }
+    }
+
+    /// Set the time-to-live for values returned by `operation`.
+    pub fn set_ttl(&mut self, operation: Operation, ttl: Duration) -> &mut Self {
+        self.ttl.insert(operation, ttl);
+        self
+    }
+
+    /// Do not cache values returned by `operation`.
+    pub fn disable(&mut self, operation: Operation) -> &mut Self {
+        self.ttl.remove(&operation);
+        self
+    }
+
+    /// The time-to-live for values returned by `operation`, if cached.
+    pub fn ttl(&self, operation: Operation) -> Option<Duration> {
+        self.ttl.get(&operation).cloned()
+    }
+}
+
+impl CacheStatistics {
+    /// The proportion of requests answered from the cache.
+    pub fn hit_ratio(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+#[cfg(feature = "disk-cache")]
+impl DiskCache {
+    /// Construct a disk cache writing files to `directory`, which is
+    /// created if it does not exist.
+    pub fn new<P: AsRef<std::path::Path>>(directory: P) -> std::io::Result<Self> {
+        std::fs::create_dir_all(directory.as_ref())?;
+        Ok(DiskCache {
+            directory: directory.as_ref().to_path_buf(),
+        })
+    }
+
+    /// Keys may be long, and contain any character, so files are named for a
+    /// hash of the key; `get` checks the stored key to detect collisions. The
+    /// hash is FNV-1a, so that file names do not change between Rust releases.
+    fn file_for(&self, key: &str) -> std::path::PathBuf {
+        self.directory.join(format!(
+            "{}{:016x}{}",
+            DISK_CACHE_PREFIX,
+            fnv1a(key.as_bytes()),
+            DISK_CACHE_SUFFIX
+        ))
+    }
+
+    /// Returns `true` if `file_name` is that of a file written by `put`.
+    fn is_cache_file(file_name: &str) -> bool {
+        match file_name
+            .strip_prefix(DISK_CACHE_PREFIX)
+            .and_then(|name| name.strip_suffix(DISK_CACHE_SUFFIX))
+        {
+            Some(hash) => hash.len() == 16 && hash.chars().all(|c| c.is_ascii_hexdigit()),
+            None => false,
+        }
+    }
+}
+
+impl<P> CachingProvider<P, MemoryCache> {
+    /// Wrap `provider` with an in-memory cache and the default settings.
+    pub fn in_memory(provider: P) -> Self {
+        CachingProvider::with_backend(provider, MemoryCache::default())
+    }
+}
+
+impl<P, B: CacheBackend> CachingProvider<P, B> {
+    /// Wrap `provider` with the cache `backend` and the default settings.
+    pub fn with_backend(provider: P, backend: B) -> Self {
+        CachingProvider {
+            provider,
+            backend,
+            settings: CacheSettings::default(),
+            statistics: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Replace the cache settings.
+    pub fn set_settings(&mut self, settings: CacheSettings) -> &mut Self {
+        self.settings = settings;
+        self
+    }
+
+    /// The current cache settings.
+    pub fn settings(&self) -> &CacheSettings {
+        &self.settings
+    }
+
+    /// The wrapped provider.
+    pub fn inner(&self) -> &P {
+        &self.provider
+    }
+
+    /// The hit and miss counts for `operation`.
+    pub fn statistics(&self, operation: Operation) -> CacheStatistics {
+        match self.statistics.lock().unwrap().get(&operation) {
+            Some(statistics) => *statistics,
+            None => CacheStatistics::default(),
+        }
+    }
+
+    /// The hit and miss counts across all operations.
+    pub fn total_statistics(&self) -> CacheStatistics {
+        self.statistics.lock().unwrap().values().fold(
+            CacheStatistics::default(),
+            |total, statistics| CacheStatistics {
+                hits: total.hits + statistics.hits,
+                misses: total.misses + statistics.misses,
+            },
+        )
+    }
+
+    /// Remove all cached values; the hit and miss counts are not reset.
+    pub fn clear(&self) {
+        self.backend.clear()
+    }
+
+    fn cached<T, F>(&self, operation: Operation, arguments: String, fetch: F) -> RequestResult<T>
+    where
+        T: Cacheable,
+        F: FnOnce() -> RequestResult<T>,
+    {
+        let ttl = match self.settings.ttl(operation) {
+            Some(ttl) => ttl,
+            None => return fetch(),
+        };
+        let key = format!("{:?}/{}", operation, arguments);
+        if let Some(entry) = self.backend.get::<T>(&key) {
+            match entry.stored.elapsed() {
+                Ok(age) if age < ttl => {
+                    self.record(operation, true);
+                    return Ok(entry.value);
+                }
+                _ => self.backend.remove(&key),
+            }
+        }
+        self.record(operation, false);
+        let value = fetch()?;
+        let stored = SystemTime::now();
+        self.backend.put(
+            &key,
+            CacheEntry {
+                value: value.clone(),
+                stored,
+                expires: stored + ttl,
+            },
+        );
+        Ok(value)
+    }
+
+    fn record(&self, operation: Operation, hit: bool) {
+        let mut statistics = self.statistics.lock().unwrap();
+        let statistics = statistics.entry(operation).or_default();
+        if hit {
+            statistics.hits += 1;
+        } else {
+            statistics.misses += 1;
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Trait Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Default for CacheSettings {
+    /// The default time-to-live values; quotes 15 seconds, intra-day series a
+    /// minute, news and option chains 5 minutes, historical series an hour,
+    /// statistics, financials, corporate actions, option expirations, peers, and
+    /// analyst data a day, and company information and filings a week.
+    fn default() -> Self {
+        let ttls: &[(Operation, u64)] = &[
+            (Operation::LatestPriceOnly, 15 * SECOND),
+            (Operation::RealTime, 15 * SECOND),
+            (Operation::Delayed, 15 * SECOND),
+            (Operation::RealTimeBatch, 15 * SECOND),
+            (Operation::DelayedBatch, 15 * SECOND),
+            (Operation::IntraDay, MINUTE),
+            (Operation::LatestNews, 5 * MINUTE),
+            (Operation::NewsFrom, 5 * MINUTE),
+            (Operation::LatestCategoryNews, 5 * MINUTE),
+            (Operation::CategoryNewsFrom, 5 * MINUTE),
+            (Operation::OptionChain, 5 * MINUTE),
+            (Operation::Last, HOUR),
+            (Operation::From, HOUR),
+            (Operation::ForPeriod, HOUR),
+            (Operation::OptionExpirations, DAY),
+            (Operation::CoreStatistics, DAY),
+            (Operation::Dividends, DAY),
+            (Operation::Splits, DAY),
+            (Operation::CorporateActions, DAY),
+            (Operation::ReportedIncome, DAY),
+            (Operation::ReportedBalanceSheet, DAY),
+            (Operation::ReportedCashFlow, DAY),
+            (Operation::Peers, DAY),
+            (Operation::TargetPrice, DAY),
+            (Operation::ConsensusRating, DAY),
+            (Operation::ConsensusEPS, DAY),
+            (Operation::Earnings, DAY),
+            (Operation::About, WEEK),
+            (Operation::Filings, WEEK),
+        ];
+        CacheSettings {
+            ttl: ttls
+                .iter()
+                .map(|(operation, seconds)| (*operation, Duration::from_secs(*seconds)))
+                .collect(),
+        }
+    }
+}
+
+impl<T: Clone + Send + Sync + Serialize + DeserializeOwned + 'static> Cacheable for T {}
+
+impl CacheBackend for MemoryCache {
+    /// An expired entry is removed, rather than returned.
+    fn get<T: Cacheable>(&self, key: &str) -> Option<CacheEntry<T>> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some((_, _, expires)) if *expires <= SystemTime::now() => {
+                entries.remove(key);
+                None
+            }
+            Some((value, stored, expires)) => value.downcast_ref::<T>().map(|value| CacheEntry {
+                value: value.clone(),
+                stored: *stored,
+                expires: *expires,
+            }),
+            None => None,
+        }
+    }
+
+    /// Any expired entries are removed before `entry` is stored.
+    fn put<T: Cacheable>(&self, key: &str, entry: CacheEntry<T>) {
+        let mut entries = self.entries.lock().unwrap();
+        let now = SystemTime::now();
+        entries.retain(|_, (_, _, expires)| *expires > now);
+        entries.insert(
+            key.to_string(),
+            (Box::new(entry.value), entry.stored, entry.expires),
+        );
+    }
+
+    fn remove(&self, key: &str) {
+        self.entries.lock().unwrap().remove(key);
+    }
+
+    fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+#[cfg(feature = "disk-cache")]
+impl CacheBackend for DiskCache {
+    fn get<T: Cacheable>(&self, key: &str) -> Option<CacheEntry<T>> {
+        let content = std::fs::read_to_string(self.file_for(key)).ok()?;
+        match serde_json::from_str::<DiskEntry<T>>(&content) {
+            Ok(stored) if stored.key == key => Some(stored.entry),
+            _ => None,
+        }
+    }
+
+    /// Write the entry to disk, a value that cannot be written is simply not cached.
+    fn put<T: Cacheable>(&self, key: &str, entry: CacheEntry<T>) {
+        let entry = DiskEntry {
+            key: key.to_string(),
+            entry,
+        };
+        if let Ok(content) = serde_json::to_string(&entry) {
+            let _ = std::fs::write(self.file_for(key), content);
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let _ = std::fs::remove_file(self.file_for(key));
+    }
+
+    /// Only the files written by this cache are removed, any other files in
+    /// the directory are left in place.
+    fn clear(&self) {
+        if let Ok(files) = std::fs::read_dir(&self.directory) {
+            for file in files.flatten() {
+                if DiskCache::is_cache_file(&file.file_name().to_string_lossy()) {
+                    let _ = std::fs::remove_file(file.path());
+                }
+            }
+        }
+    }
+}
+
+impl<P, B> Provider for CachingProvider<P, B>
+where
+    P: Provider,
+    B: CacheBackend + Default,
+{
+    /// Create the wrapped provider, with a default backend and settings.
+    fn new() -> RequestResult<Self> {
+        Ok(CachingProvider::with_backend(P::new()?, B::default()))
+    }
+
+    fn attribution(&self) -> String {
+        self.provider.attribution()
+    }
+
+    fn url(&self) -> String {
+        self.provider.url()
+    }
+
+    fn get_default_currency(&self) -> &String {
+        self.provider.get_default_currency()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.provider.capabilities()
+    }
+
+    fn finish(&self) {
+        self.provider.finish()
+    }
+}
+
+impl<P: Peers, B: CacheBackend> Peers for CachingProvider<P, B> {
+    fn peers(&self, for_symbol: Symbol) -> RequestResult<Symbols> {
+        self.cached(Operation::Peers, for_symbol.to_string(), || {
+            self.provider.peers(for_symbol.clone())
+        })
+    }
+}
+
+impl<P: AnalystRecommendations, B: CacheBackend> AnalystRecommendations for CachingProvider<P, B> {
+    fn target_price(&self, for_symbol: Symbol) -> RequestResult<Snapshot<PriceTarget>> {
+        self.cached(Operation::TargetPrice, for_symbol.to_string(), || {
+            self.provider.target_price(for_symbol.clone())
+        })
+    }
+
+    fn consensus_rating(&self, for_symbol: Symbol) -> RequestResult<Vec<Bounded<Ratings>>> {
+        self.cached(Operation::ConsensusRating, for_symbol.to_string(), || {
+            self.provider.consensus_rating(for_symbol.clone())
+        })
+    }
+
+    fn consensus_eps(&self, for_symbol: Symbol) -> RequestResult<Vec<EPSConsensus>> {
+        self.cached(Operation::ConsensusEPS, for_symbol.to_string(), || {
+            self.provider.consensus_eps(for_symbol.clone())
+        })
+    }
+}
+
+impl<P: FetchCompanyInformation, B: CacheBackend> FetchCompanyInformation
+    for CachingProvider<P, B>
+{
+    fn about(&self, for_symbol: Symbol) -> RequestResult<About> {
+        self.cached(Operation::About, for_symbol.to_string(), || {
+            self.provider.about(for_symbol.clone())
+        })
+    }
+
+    fn filings(
+        &self,
+        for_symbol: Symbol,
+        start_date: Option<Date>,
+        form_type: Option<String>,
+    ) -> RequestResult<RegulatoryFilings> {
+        let arguments = format!("{}/{:?}/{:?}", for_symbol, start_date, form_type);
+        self.cached(Operation::Filings, arguments, || {
+            self.provider
+                .filings(for_symbol.clone(), start_date, form_type.clone())
+        })
+    }
+}
+
+impl<P: FetchCompanyFinancials, B: CacheBackend> FetchCompanyFinancials for CachingProvider<P, B> {
+    fn reported_income(
+        &self,
+        for_symbol: Symbol,
+        last: u8,
+        period: ReportingPeriod,
+    ) -> RequestResult<Vec<Snapshot<IncomeStatement>>> {
+        let arguments = format!("{}/{}/{:?}", for_symbol, last, period);
+        self.cached(Operation::ReportedIncome, arguments, || {
+            self.provider
+                .reported_income(for_symbol.clone(), last, period)
+        })
+    }
+
+    fn reported_balance_sheet(
+        &self,
+        for_symbol: Symbol,
+        last: u8,
+        period: ReportingPeriod,
+    ) -> RequestResult<Vec<Snapshot<BalanceSheet>>> {
+        let arguments = format!("{}/{}/{:?}", for_symbol, last, period);
+        self.cached(Operation::ReportedBalanceSheet, arguments, || {
+            self.provider
+                .reported_balance_sheet(for_symbol.clone(), last, period)
+        })
+    }
+
+    fn reported_cash_flow(
+        &self,
+        for_symbol: Symbol,
+        last: u8,
+        period: ReportingPeriod,
+    ) -> RequestResult<Vec<Snapshot<CashFlowStatement>>> {
+        let arguments = format!("{}/{}/{:?}", for_symbol, last, period);
+        self.cached(Operation::ReportedCashFlow, arguments, || {
+            self.provider
+                .reported_cash_flow(for_symbol.clone(), last, period)
+        })
+    }
+}
+
+impl<P: FetchCompanyStatistics, B: CacheBackend> FetchCompanyStatistics for CachingProvider<P, B> {
+    fn core_statistics(&self, for_symbol: Symbol) -> RequestResult<Statistics> {
+        self.cached(Operation::CoreStatistics, for_symbol.to_string(), || {
+            self.provider.core_statistics(for_symbol.clone())
+        })
+    }
+}
+
+impl<P: FetchEarnings, B: CacheBackend> FetchEarnings for CachingProvider<P, B> {
+    fn earnings(&self, for_symbol: Symbol, last: usize) -> RequestResult<Vec<EarningsReport>> {
+        let arguments = format!("{}/{}", for_symbol, last);
+        self.cached(Operation::Earnings, arguments, || {
+            self.provider.earnings(for_symbol.clone(), last)
+        })
+    }
+}
+
+impl<P: FetchCorporateActions, B: CacheBackend> FetchCorporateActions for CachingProvider<P, B> {
+    fn dividends(
+        &self,
+        for_symbol: Symbol,
+        interval: SeriesInterval,
+    ) -> RequestResult<Vec<Dividend>> {
+        let arguments = format!("{}/{:?}", for_symbol, interval);
+        self.cached(Operation::Dividends, arguments, || {
+            self.provider.dividends(for_symbol.clone(), interval)
+        })
+    }
+
+    fn splits(&self, for_symbol: Symbol, interval: SeriesInterval) -> RequestResult<Vec<Split>> {
+        let arguments = format!("{}/{:?}", for_symbol, interval);
+        self.cached(Operation::Splits, arguments, || {
+            self.provider.splits(for_symbol.clone(), interval)
+        })
+    }
+
+    fn corporate_actions(
+        &self,
+        for_symbol: Symbol,
+        interval: SeriesInterval,
+    ) -> RequestResult<Vec<CorporateAction>> {
+        let arguments = format!("{}/{:?}", for_symbol, interval);
+        self.cached(Operation::CorporateActions, arguments, || {
+            self.provider
+                .corporate_actions(for_symbol.clone(), interval)
+        })
+    }
+}
+
+impl<P: FetchNews, B: CacheBackend> FetchNews for CachingProvider<P, B> {
+    fn latest_news(&self, for_symbol: Symbol, max_items: usize) -> RequestResult<NewsFeed> {
+        let arguments = format!("{}/{}", for_symbol, max_items);
+        self.cached(Operation::LatestNews, arguments, || {
+            self.provider.latest_news(for_symbol.clone(), max_items)
+        })
+    }
+
+    fn news_from(
+        &self,
+        for_symbol: Symbol,
+        start_date: Date,
+        max_items: usize,
+    ) -> RequestResult<NewsFeed> {
+        let arguments = format!("{}/{}/{}", for_symbol, start_date, max_items);
+        self.cached(Operation::NewsFrom, arguments, || {
+            self.provider
+                .news_from(for_symbol.clone(), start_date, max_items)
+        })
+    }
+}
+
+impl<P: FetchCategoryNews, B: CacheBackend> FetchCategoryNews for CachingProvider<P, B> {
+    fn latest_news(
+        &self,
+        category: String,
+        sub_category: Option<String>,
+        max_items: usize,
+    ) -> RequestResult<NewsFeed> {
+        let arguments = format!("{}/{:?}/{}", category, sub_category, max_items);
+        self.cached(Operation::LatestCategoryNews, arguments, || {
+            self.provider
+                .latest_news(category.clone(), sub_category.clone(), max_items)
+        })
+    }
+
+    fn news_from(
+        &self,
+        category: String,
+        sub_category: Option<String>,
+        start_date: Date,
+        max_items: usize,
+    ) -> RequestResult<NewsFeed> {
+        let arguments = format!(
+            "{}/{:?}/{}/{}",
+            category, sub_category, start_date, max_items
+        );
+        self.cached(Operation::CategoryNewsFrom, arguments, || {
+            self.provider.news_from(
+                category.clone(),
+                sub_category.clone(),
+                start_date,
+                max_items,
+            )
+        })
+    }
+}
+
+impl<P: FetchOptionChain, B: CacheBackend> FetchOptionChain for CachingProvider<P, B> {
+    fn expirations(&self, for_symbol: Symbol) -> RequestResult<Vec<Date>> {
+        self.cached(Operation::OptionExpirations, for_symbol.to_string(), || {
+            self.provider.expirations(for_symbol.clone())
+        })
+    }
+
+    fn option_chain(
+        &self,
+        for_symbol: Symbol,
+        expiration: Date,
+    ) -> RequestResult<Snapshot<OptionChain>> {
+        let arguments = format!("{}/{}", for_symbol, expiration);
+        self.cached(Operation::OptionChain, arguments, || {
+            self.provider.option_chain(for_symbol.clone(), expiration)
+        })
+    }
+}
+
+impl<P: FetchPriceQuote, B: CacheBackend> FetchPriceQuote for CachingProvider<P, B> {
+    fn latest_price_only(&self, for_symbol: Symbol) -> RequestResult<Money> {
+        self.cached(Operation::LatestPriceOnly, for_symbol.to_string(), || {
+            self.provider
+                .latest_price_only(for_symbol.clone())
+                .map(|price| CachedPrice { price })
+        })
+        .map(|cached| cached.price)
+    }
+
+    fn real_time(&self, for_symbol: Symbol) -> RequestResult<Quote> {
+        self.cached(Operation::RealTime, for_symbol.to_string(), || {
+            self.provider.real_time(for_symbol.clone())
+        })
+    }
+
+    fn delayed(&self, for_symbol: Symbol) -> RequestResult<DelayedQuote> {
+        self.cached(Operation::Delayed, for_symbol.to_string(), || {
+            self.provider.delayed(for_symbol.clone())
+        })
+    }
+}
+
+impl<P: FetchPriceQuoteBatch, B: CacheBackend> FetchPriceQuoteBatch for CachingProvider<P, B> {
+    fn real_time(&self, for_symbols: Symbols) -> RequestResult<HashMap<Symbol, Quote>> {
+        self.cached(Operation::RealTimeBatch, symbols_key(&for_symbols), || {
+            self.provider.real_time(for_symbols.clone())
+        })
+    }
+
+    fn delayed(&self, for_symbols: Symbols) -> RequestResult<HashMap<Symbol, DelayedQuote>> {
+        self.cached(Operation::DelayedBatch, symbols_key(&for_symbols), || {
+            self.provider.delayed(for_symbols.clone())
+        })
+    }
+}
+
+impl<P: FetchPriceRangeSeries, B: CacheBackend> FetchPriceRangeSeries for CachingProvider<P, B> {
+    fn intra_day(
+        &self,
+        for_symbol: Symbol,
+        interval_minutes: u8,
+    ) -> RequestResult<Option<PriceRangeSeries>> {
+        let arguments = format!("{}/{}", for_symbol, interval_minutes);
+        self.cached(Operation::IntraDay, arguments, || {
+            self.provider
+                .intra_day(for_symbol.clone(), interval_minutes)
+        })
+    }
+
+    fn last(
+        &self,
+        for_symbol: Symbol,
+        interval: SeriesInterval,
+    ) -> RequestResult<PriceRangeSeries> {
+        let arguments = format!("{}/{:?}", for_symbol, interval);
+        self.cached(Operation::Last, arguments, || {
+            self.provider.last(for_symbol.clone(), interval)
+        })
+    }
+
+    fn from(
+        &self,
+        for_symbol: Symbol,
+        start_date: DateTime,
+        interval: SeriesInterval,
+    ) -> RequestResult<PriceRangeSeries> {
+        let arguments = format!("{}/{}/{:?}", for_symbol, start_date, interval);
+        self.cached(Operation::From, arguments, || {
+            self.provider.from(for_symbol.clone(), start_date, interval)
+        })
+    }
+
+    fn for_period(
+        &self,
+        for_symbol: Symbol,
+        period: FinancialPeriod,
+    ) -> RequestResult<PriceRangeSeries> {
+        let arguments = format!("{}/{}", for_symbol, period);
+        self.cached(Operation::ForPeriod, arguments, || {
+            self.provider.for_period(for_symbol.clone(), period)
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+/// The 64-bit FNV-1a hash of `bytes`.
+#[cfg(feature = "disk-cache")]
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// A key for a set of symbols, independent of the set's iteration order.
+fn symbols_key(symbols: &HashSet<Symbol>) -> String {
+    let mut symbols: Vec<&str> = symbols.iter().map(|s| s.as_str()).collect();
+    symbols.sort_unstable();
+    symbols.join(",")
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::{CacheBackend, CacheEntry, CacheSettings, CachingProvider, MemoryCache};
+    use crate::prelude::*;
+    use crate::provider::Operation;
+    use crate::quote::{DelayedQuote, FetchPriceQuote, Quote};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, SystemTime};
+    use steel_cent::currency::with_code;
+
+    #[derive(Default)]
+    struct CountingProvider {
+        calls: AtomicUsize,
+    }
+
+    impl Provider for CountingProvider {
+        fn new() -> RequestResult<Self> {
+            Ok(CountingProvider::default())
+        }
+        fn attribution(&self) -> String {
+            "counting".to_string()
+        }
+        fn url(&self) -> String {
+            String::new()
+        }
+        fn get_default_currency(&self) -> &String {
+            unimplemented!()
+        }
+        fn finish(&self) {}
+    }
+
+    impl FetchPriceQuote for CountingProvider {
+        fn latest_price_only(&self, for_symbol: Symbol) -> RequestResult<Money> {
+            let calls = self.calls.fetch_add(1, Ordering::SeqCst) as i32;
+            if for_symbol.as_str() == "FAIL" {
+                Err(RequestErrorKind::CommunicationError.into())
+            } else {
+                Ok(Money::of_major(with_code("USD").unwrap(), calls))
+            }
+        }
+        fn real_time(&self, _for_symbol: Symbol) -> RequestResult<Quote> {
+            Err(RequestErrorKind::Unsupported.into())
+        }
+        fn delayed(&self, _for_symbol: Symbol) -> RequestResult<DelayedQuote> {
+            Err(RequestErrorKind::Unsupported.into())
+        }
+    }
+
+    fn symbol(s: &str) -> Symbol {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_hits_and_misses() {
+        let provider = CachingProvider::in_memory(CountingProvider::default());
+        let first = provider.latest_price_only(symbol("AAPL")).unwrap();
+        assert_eq!(provider.latest_price_only(symbol("AAPL")).unwrap(), first);
+        assert_ne!(provider.latest_price_only(symbol("MSFT")).unwrap(), first);
+        assert!(provider.latest_price_only(symbol("FAIL")).is_err());
+        assert!(provider.latest_price_only(symbol("FAIL")).is_err());
+
+        let statistics = provider.statistics(Operation::LatestPriceOnly);
+        assert_eq!(statistics.hits, 1);
+        assert_eq!(statistics.misses, 4);
+        assert_eq!(provider.total_statistics(), statistics);
+        assert_eq!(provider.inner().calls.load(Ordering::SeqCst), 4);
+
+        provider.clear();
+        provider.latest_price_only(symbol("AAPL")).unwrap();
+        assert_eq!(provider.inner().calls.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn test_ttl_settings() {
+        let mut provider = CachingProvider::in_memory(CountingProvider::default());
+        let mut settings = CacheSettings::default();
+        settings.set_ttl(Operation::LatestPriceOnly, Duration::from_secs(0));
+        provider.set_settings(settings);
+        provider.latest_price_only(symbol("AAPL")).unwrap();
+        provider.latest_price_only(symbol("AAPL")).unwrap();
+        assert_eq!(provider.statistics(Operation::LatestPriceOnly).hits, 0);
+
+        provider.set_settings(CacheSettings::none());
+        provider.latest_price_only(symbol("AAPL")).unwrap();
+        assert_eq!(provider.statistics(Operation::LatestPriceOnly).misses, 2);
+        assert_eq!(provider.inner().calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[cfg(feature = "disk-cache")]
+    #[test]
+    fn test_disk_cache() {
+        use super::DiskCache;
+        let directory = std::env::temp_dir().join("fin_model_test_disk_cache");
+        let backend = DiskCache::new(&directory).unwrap();
+        let provider = CachingProvider::with_backend(CountingProvider::default(), backend);
+        provider.clear();
+        let first = provider.latest_price_only(symbol("AAPL")).unwrap();
+
+        let backend = DiskCache::new(&directory).unwrap();
+        let provider = CachingProvider::with_backend(CountingProvider::default(), backend);
+        assert_eq!(provider.latest_price_only(symbol("AAPL")).unwrap(), first);
+        assert_eq!(provider.inner().calls.load(Ordering::SeqCst), 0);
+        provider.clear();
+    }
+
+    #[cfg(feature = "disk-cache")]
+    #[test]
+    fn test_disk_cache_long_key() {
+        use super::DiskCache;
+        let directory = std::env::temp_dir().join("fin_model_test_disk_cache_long_key");
+        let backend = DiskCache::new(&directory).unwrap();
+        let key = "RealTime/".repeat(100);
+        backend.put(&key, entry(1, Duration::from_secs(60)));
+        assert_eq!(backend.get::<u32>(&key).unwrap().value, 1);
+        assert!(backend.get::<u32>("RealTime/").is_none());
+        backend.clear();
+    }
+
+    #[cfg(feature = "disk-cache")]
+    #[test]
+    fn test_fnv1a() {
+        use super::fnv1a;
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[cfg(feature = "disk-cache")]
+    #[test]
+    fn test_disk_cache_clear() {
+        use super::DiskCache;
+        let directory = std::env::temp_dir().join("fin_model_test_disk_cache_clear");
+        let backend = DiskCache::new(&directory).unwrap();
+        let other = directory.join("settings.json");
+        std::fs::write(&other, "{}").unwrap();
+        backend.put("RealTime/AAPL", entry(1, Duration::from_secs(60)));
+        backend.clear();
+        assert!(backend.get::<u32>("RealTime/AAPL").is_none());
+        assert!(other.exists());
+        std::fs::remove_file(&other).unwrap();
+    }
+
+    #[test]
+    fn test_memory_cache_eviction() {
+        let backend = MemoryCache::default();
+        backend.put("expired", entry(1, Duration::from_secs(0)));
+        backend.put("live", entry(2, Duration::from_secs(60)));
+        assert_eq!(backend.entries.lock().unwrap().len(), 1);
+        assert!(backend.get::<u32>("expired").is_none());
+        assert_eq!(backend.get::<u32>("live").unwrap().value, 2);
+    }
+
+    fn entry(value: u32, ttl: Duration) -> CacheEntry<u32> {
+        let stored = SystemTime::now();
+        CacheEntry {
+            value,
+            stored,
+            expires: stored + ttl,
+        }
+    }
+}
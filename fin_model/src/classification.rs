@@ -10,6 +10,9 @@ A classification scheme is an implementation of the
 lookup of code identifiers.
 */
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 // ------------------------------------------------------------------------------------------------
 // Public Types
 // ------------------------------------------------------------------------------------------------
@@ -18,6 +21,7 @@ lookup of code identifiers.
 /// of the code's identifier. The use of `parent_code` is optional; when present
 /// it allows simple hierarchies.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Code<T> {
     /// the code identifier
     pub code: T,
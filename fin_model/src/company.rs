@@ -5,7 +5,14 @@ The majority of the data provided here is information published by
 the company itself usually in regulatory filings.
 */
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashSet;
+
+use crate::identifier::SecurityIdentifier;
 use crate::prelude::*;
+use crate::reporting::FiscalPeriod;
 
 // ------------------------------------------------------------------------------------------------
 // Public Types
@@ -15,17 +22,23 @@ use crate::prelude::*;
 /// includes the market and issue type for the security. Finally, for
 /// those providers that enable it, a sector classification is included;
 /// this value may be provided by the underlying market or simply the
-/// provider itself.
+/// provider itself. Where known, standard identifiers such as the ISIN
+/// or CUSIP for the security are also included.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Security {
     pub symbol: String,
     pub market: String,
     pub security_name: String,
     pub issue_type: Option<String>,
     pub sector: Option<String>,
+    pub identifiers: Option<HashSet<SecurityIdentifier>>,
 }
 
 /// Basic information reported about a company. This information, other
 /// than the number of employees, rarely changes and can easily be cached.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct About {
     pub company_name: String,
     pub web_site: String,
@@ -38,114 +51,198 @@ pub struct About {
 
 /// For data returned below, this indicates the time-scale represented
 /// by the data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ReportingPeriod {
     Quarter,
     Annual,
 }
 
-/// Reported income, categorized.
-pub struct IncomeStatement {
-    pub total_revenue: i64,
-    pub cost_of_revenue: i64,
-    pub gross_profit: i64,
-
-    pub research_and_development: i64,
-    pub sales_and_administration: i64,
-    pub non_recurring: i64,
-    pub other_operating_expense: i64,
-    pub operating_income: i64,
-
-    pub additional_income_or_expense: i64,
-    pub earnings_before_interest_and_tax: i64,
-    pub interest_expense: i64,
-    pub earnings_before_tax: i64,
-    pub income_tax_expense: i64,
-    pub minority_interest: i64,
-    pub equity_earnings: i64,
-    pub net_income: i64,
-    pub net_income_to_shareholders: i64,
-}
-
-/// Reported balance sheet information in detail.
-pub struct BalanceSheet {
-    pub current_cash: i64,
-    pub short_term_investments: i64,
-    pub net_receivables: i64,
-    pub inventory: i64,
-    pub other_current_assets: i64,
-    pub total_current_assets: i64,
-
-    pub long_term_investments: i64,
-    pub property_plant_equipment: i64,
-    pub goodwill: i64,
-    pub intangible_assets: i64,
-    pub accumulated_amortization: i64,
-    pub other_assets: i64,
-    pub deferred_asset_charges: i64,
-    pub total_assets: i64,
-
-    pub accounts_payable: i64,
-    pub current_long_term_debt: i64,
-    pub other_current_liabilities: i64,
-    pub total_current_liabilities: i64,
-
-    pub long_term_debt: i64,
-    pub other_liabilities: i64,
-    pub deferred_liability_charges: i64,
-    pub minority_interest: i64,
-    pub total_liabilities: i64,
-
-    pub redeemable_preferred_stock: i64,
-    pub preferred_stock: i64,
-    pub common_stock: i64,
-    pub retained_earnings: i64,
-    pub treasury_stock: i64,
-    pub capital_surplus: i64,
-    pub other_shareholder_equity: i64,
-    pub total_shareholder_equity: i64,
-    pub net_tangible_assets: i64,
-}
-
-/// Major statistic calculated over time.
-pub struct Statistics {
-    pub shares_outstanding: u64,
-    pub market_cap: i64,
-    pub share_float: u64,
-    pub pe_ratio: f64,
-    pub beta: f64,
-
-    pub t52w_high: Money,
-    pub t52w_low: Money,
-    pub t52w_change: f64,
-
-    pub t10d_average_volume: u64,
-    pub t30d_average_volume: u64,
-
-    pub t50d_moving_average: Money,
-    pub t200d_moving_average: Money,
+/// The scale in which the values of a financial statement are reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ReportingScale {
+    Units,
+    Thousands,
+    Millions,
+    Billions,
+}
 
-    pub t12m_eps: Money,
+/// Describes how, and for what period, the values in a financial statement
+/// are reported. Values should only be compared, or combined, across
+/// statements with the same currency, after conversion to units.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StatementInfo {
+    /// the ISO 4217 currency code for all monetary values
+    pub currency: String,
+    /// the scale of all monetary values
+    pub scale: ReportingScale,
+    /// the fiscal period covered by the statement, for a balance sheet the
+    /// period at the end of which the balances are reported
+    pub fiscal_period: FiscalPeriod,
+    /// the date the statement was filed, if known
+    pub filing_date: Option<Date>,
+    /// `true` if these values restate those originally filed for the period
+    pub restated: bool,
+}
 
-    pub t12m_dividend_rate: f64,
-    pub dividend_yield: f64,
+/// Reported income, categorized. Line items not reported are `None`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IncomeStatement {
+    pub info: StatementInfo,
+
+    pub total_revenue: Option<i64>,
+    pub cost_of_revenue: Option<i64>,
+    pub gross_profit: Option<i64>,
+
+    pub research_and_development: Option<i64>,
+    pub sales_and_administration: Option<i64>,
+    pub non_recurring: Option<i64>,
+    pub other_operating_expense: Option<i64>,
+    pub operating_income: Option<i64>,
+
+    pub additional_income_or_expense: Option<i64>,
+    pub earnings_before_interest_and_tax: Option<i64>,
+    pub interest_expense: Option<i64>,
+    pub earnings_before_tax: Option<i64>,
+    pub income_tax_expense: Option<i64>,
+    pub minority_interest: Option<i64>,
+    pub equity_earnings: Option<i64>,
+    pub net_income: Option<i64>,
+    pub net_income_to_shareholders: Option<i64>,
+}
 
-    pub last_ex_dividend_date: Date,
-    pub next_ex_dividend_date: Date,
-    pub next_earnings_date: Date,
+/// Reported balance sheet information in detail. Line items not reported are `None`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BalanceSheet {
+    pub info: StatementInfo,
+
+    pub current_cash: Option<i64>,
+    pub short_term_investments: Option<i64>,
+    pub net_receivables: Option<i64>,
+    pub inventory: Option<i64>,
+    pub other_current_assets: Option<i64>,
+    pub total_current_assets: Option<i64>,
+
+    pub long_term_investments: Option<i64>,
+    pub property_plant_equipment: Option<i64>,
+    pub goodwill: Option<i64>,
+    pub intangible_assets: Option<i64>,
+    pub accumulated_amortization: Option<i64>,
+    pub other_assets: Option<i64>,
+    pub deferred_asset_charges: Option<i64>,
+    pub total_assets: Option<i64>,
+
+    pub accounts_payable: Option<i64>,
+    pub current_long_term_debt: Option<i64>,
+    pub other_current_liabilities: Option<i64>,
+    pub total_current_liabilities: Option<i64>,
+
+    pub long_term_debt: Option<i64>,
+    pub other_liabilities: Option<i64>,
+    pub deferred_liability_charges: Option<i64>,
+    pub minority_interest: Option<i64>,
+    pub total_liabilities: Option<i64>,
+
+    pub redeemable_preferred_stock: Option<i64>,
+    pub preferred_stock: Option<i64>,
+    pub common_stock: Option<i64>,
+    pub retained_earnings: Option<i64>,
+    pub treasury_stock: Option<i64>,
+    pub capital_surplus: Option<i64>,
+    pub other_shareholder_equity: Option<i64>,
+    pub total_shareholder_equity: Option<i64>,
+    pub net_tangible_assets: Option<i64>,
+}
 
-    pub t5d_change_percentage: f64,
-    pub t30d_change_percentage: f64,
-    pub t1m_change_percentage: f64,
-    pub t3m_change_percentage: f64,
-    pub t6m_change_percentage: f64,
-    pub t1y_change_percentage: f64,
-    pub t2y_change_percentage: f64,
-    pub t5y_change_percentage: f64,
+/// Reported cash flow, categorized into operating, investing, and financing
+/// activities. Cash inflows are positive, and outflows such as capital
+/// expenditures and dividends paid are negative. Line items not reported
+/// are `None`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CashFlowStatement {
+    pub info: StatementInfo,
+
+    pub net_income: Option<i64>,
+    pub depreciation: Option<i64>,
+    pub changes_in_receivables: Option<i64>,
+    pub changes_in_inventories: Option<i64>,
+    pub other_operating_activities: Option<i64>,
+    pub operating_cash_flow: Option<i64>,
+
+    pub capital_expenditures: Option<i64>,
+    pub investments: Option<i64>,
+    pub other_investing_activities: Option<i64>,
+    pub investing_cash_flow: Option<i64>,
+
+    pub dividends_paid: Option<i64>,
+    pub net_borrowings: Option<i64>,
+    pub other_financing_activities: Option<i64>,
+    pub financing_cash_flow: Option<i64>,
+
+    pub exchange_rate_effect: Option<i64>,
+    pub net_change_in_cash: Option<i64>,
+}
 
-    pub ytd_change_percentage: f64,
-    pub max_change_percentage: f64,
+/// Major statistic calculated over time. Any statistic may be `None` where the
+/// provider has no value, for example the P/E ratio of a loss-making company or
+/// the next ex-dividend date of a company that does not pay dividends.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Statistics {
+    /// the ISO 4217 currency code for the market capitalization, and all money values
+    pub currency: String,
+
+    pub shares_outstanding: Option<u64>,
+    pub market_cap: Option<i64>,
+    pub share_float: Option<u64>,
+    pub pe_ratio: Option<f64>,
+    pub beta: Option<f64>,
+
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::option_money"))]
+    pub t52w_high: Option<Money>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::option_money"))]
+    pub t52w_low: Option<Money>,
+    pub t52w_change: Option<f64>,
+
+    pub t10d_average_volume: Option<u64>,
+    pub t30d_average_volume: Option<u64>,
+
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::option_money"))]
+    pub t50d_moving_average: Option<Money>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::option_money"))]
+    pub t200d_moving_average: Option<Money>,
+
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::option_money"))]
+    pub t12m_eps: Option<Money>,
+
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::option_money"))]
+    pub t12m_dividend_rate: Option<Money>,
+    pub dividend_yield: Option<f64>,
+
+    pub last_ex_dividend_date: Option<Date>,
+    pub next_ex_dividend_date: Option<Date>,
+    pub next_earnings_date: Option<Date>,
+
+    pub t5d_change_percentage: Option<f64>,
+    pub t30d_change_percentage: Option<f64>,
+    pub t1m_change_percentage: Option<f64>,
+    pub t3m_change_percentage: Option<f64>,
+    pub t6m_change_percentage: Option<f64>,
+    pub t1y_change_percentage: Option<f64>,
+    pub t2y_change_percentage: Option<f64>,
+    pub t5y_change_percentage: Option<f64>,
+
+    pub ytd_change_percentage: Option<f64>,
+    pub max_change_percentage: Option<f64>,
 }
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TypedUrl {
     HTML(String),
     PDF(String),
@@ -154,6 +251,8 @@ pub enum TypedUrl {
 }
 
 /// Details of an individual filing.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RegulatoryFiling {
     pub identifier: String,
     pub form_type: String,
@@ -194,9 +293,50 @@ pub trait FetchCompanyFinancials {
         last: u8,
         period: ReportingPeriod,
     ) -> RequestResult<Vec<Snapshot<BalanceSheet>>>;
+
+    fn reported_cash_flow(
+        &self,
+        for_symbol: Symbol,
+        last: u8,
+        period: ReportingPeriod,
+    ) -> RequestResult<Vec<Snapshot<CashFlowStatement>>>;
 }
 
 /// Fetch basic statistics regarding a company.
 pub trait FetchCompanyStatistics {
     fn core_statistics(&self, for_symbol: Symbol) -> RequestResult<Statistics>;
 }
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl ReportingScale {
+    /// The number of units represented by a single reported value.
+    pub fn multiplier(&self) -> i64 {
+        match self {
+            ReportingScale::Units => 1,
+            ReportingScale::Thousands => 1_000,
+            ReportingScale::Millions => 1_000_000,
+            ReportingScale::Billions => 1_000_000_000,
+        }
+    }
+}
+
+impl StatementInfo {
+    /// Convert a reported `value` to units of `currency`; `None` if the value
+    /// was not reported, or cannot be represented.
+    pub fn in_units(&self, value: Option<i64>) -> Option<i64> {
+        value.and_then(|value| value.checked_mul(self.scale.multiplier()))
+    }
+}
+
+impl CashFlowStatement {
+    /// Free cash flow, the operating cash flow less capital expenditures; `None`
+    /// if operating cash flow was not reported, capital expenditures are
+    /// assumed to be zero if not reported.
+    pub fn free_cash_flow(&self) -> Option<i64> {
+        self.operating_cash_flow
+            .map(|cash_flow| cash_flow + self.capital_expenditures.unwrap_or_default())
+    }
+}
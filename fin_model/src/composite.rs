@@ -0,0 +1,1143 @@
+/*!
+Provides a composite provider that routes requests across a number of
+underlying providers.
+
+The `CompositeProvider` implements all of the _request traits_ by routing
+each call to one of the providers added for that trait. Routing is:
+
+* **by trait**, a provider is only called for the traits it was added for,
+  and, if its `Capabilities` declare any operations, only for those;
+* **by market**, if a market resolver is set, a symbol's market is compared
+  to the markets declared by the provider, or listed in its `Route`;
+* **by priority**, the remaining providers are tried in priority order, lower
+  values first, and providers with the same priority in the order added.
+
+If a provider returns an error of kind `Unsupported`, `RequestThrottled`,
+`QuotaExceeded`, or `CommunicationError` the next provider is tried; any
+other error is returned to the caller immediately. If no provider can be
+called the result is an `Unsupported` error.
+
+## Example
+
+```rust
+use std::collections::HashMap;
+use std::sync::Arc;
+use fin_model::composite::{CompositeProvider, Route};
+use fin_model::prelude::*;
+use fin_model::quote::{DelayedQuote, FetchPriceQuote, Quote};
+# struct ExampleProvider;
+# impl Provider for ExampleProvider {
+#     fn new() -> RequestResult<Self> { Ok(ExampleProvider) }
+#     fn attribution(&self) -> String { "Example".to_string() }
+#     fn url(&self) -> String { String::new() }
+#     fn get_default_currency(&self) -> &String { unimplemented!() }
+#     fn finish(&self) {}
+# }
+# impl FetchPriceQuote for ExampleProvider {
+#     fn latest_price_only(&self, _: Symbol) -> RequestResult<Money> { unimplemented!() }
+#     fn real_time(&self, _: Symbol) -> RequestResult<Quote> { unimplemented!() }
+#     fn delayed(&self, _: Symbol) -> RequestResult<DelayedQuote> { unimplemented!() }
+# }
+
+let us_provider = Arc::new(ExampleProvider::new().unwrap());
+let uk_provider = Arc::new(ExampleProvider::new().unwrap());
+
+let mut markets: HashMap<Symbol, String> = HashMap::new();
+markets.insert("VOD".parse().unwrap(), "XLON".to_string());
+
+let mut provider = CompositeProvider::default();
+provider
+    .add_price_quote(us_provider, Route::with_priority(1).for_markets(&["XNAS", "XNYS"]))
+    .add_price_quote(uk_provider, Route::with_priority(2).for_markets(&["XLON"]))
+    .set_market_resolver(move |symbol| markets.get(symbol).cloned());
+```
+*/
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::analysis::*;
+use crate::company::*;
+use crate::corporate::*;
+use crate::news::*;
+use crate::options::*;
+use crate::prelude::*;
+use crate::provider::{Capabilities, Operation, RequestTrait};
+use crate::quote::*;
+use crate::reporting::FinancialPeriod;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// Routing options for a provider added to a `CompositeProvider`.
+#[derive(Debug, Clone, Default)]
+pub struct Route {
+    /// the priority of the provider, lower values are tried first
+    pub priority: u32,
+    /// the MIC codes of markets to route to this provider, if `None` the
+    /// markets declared in the provider's `Capabilities` are used
+    pub markets: Option<HashSet<String>>,
+}
+
+/// A provider that implements the _request traits_ by routing each call
+/// across a number of underlying providers.
+pub struct CompositeProvider {
+    default_currency: String,
+    market_resolver: Option<Box<MarketResolver>>,
+    providers: Vec<ProviderInfo>,
+    peers: Vec<Member<dyn Peers + Send + Sync>>,
+    analyst_recommendations: Vec<Member<dyn AnalystRecommendations + Send + Sync>>,
+    earnings: Vec<Member<dyn FetchEarnings + Send + Sync>>,
+    company_information: Vec<Member<dyn FetchCompanyInformation + Send + Sync>>,
+    company_financials: Vec<Member<dyn FetchCompanyFinancials + Send + Sync>>,
+    company_statistics: Vec<Member<dyn FetchCompanyStatistics + Send + Sync>>,
+    corporate_actions: Vec<Member<dyn FetchCorporateActions + Send + Sync>>,
+    news: Vec<Member<dyn FetchNews + Send + Sync>>,
+    category_news: Vec<Member<dyn FetchCategoryNews + Send + Sync>>,
+    option_chain: Vec<Member<dyn FetchOptionChain + Send + Sync>>,
+    price_quote: Vec<Member<dyn FetchPriceQuote + Send + Sync>>,
+    price_quote_batch: Vec<Member<dyn FetchPriceQuoteBatch + Send + Sync>>,
+    price_range_series: Vec<Member<dyn FetchPriceRangeSeries + Send + Sync>>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+type MarketResolver = dyn Fn(&Symbol) -> Option<String> + Send + Sync;
+
+struct ProviderInfo {
+    id: usize,
+    attribution: String,
+    url: String,
+    capabilities: Capabilities,
+    finish: Box<dyn Fn() + Send + Sync>,
+}
+
+struct Member<T: ?Sized> {
+    provider: Arc<T>,
+    priority: u32,
+    markets: Option<HashSet<String>>,
+    operations: HashSet<Operation>,
+}
+
+const DEFAULT_CURRENCY: &str = "USD";
+
+const COMPOSITE_NAME: &str = "composite";
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Route {
+    /// Construct a route with the given priority, lower values are tried first.
+    pub fn with_priority(priority: u32) -> Self {
+        Route {
+            priority,
+            markets: None,
+        }
+    }
+
+    /// Only route symbols in the markets identified by `mics` to this provider.
+    pub fn for_markets(self, mics: &[&str]) -> Self {
+        Route {
+            markets: Some(mics.iter().map(|mic| mic.to_string()).collect()),
+            ..self
+        }
+    }
+}
+
+impl CompositeProvider {
+    /// Set the currency code returned from `Provider::get_default_currency`.
+    pub fn set_default_currency(&mut self, code: &str) -> &mut Self {
+        self.default_currency = code.to_string();
+        self
+    }
+
+    /// Set the function used to determine the market, the MIC code, of a
+    /// symbol. Without a resolver requests are routed by trait and priority only.
+    pub fn set_market_resolver<F>(&mut self, resolver: F) -> &mut Self
+    where
+        F: Fn(&Symbol) -> Option<String> + Send + Sync + 'static,
+    {
+        self.market_resolver = Some(Box::new(resolver));
+        self
+    }
+
+    /// Route `Peers` requests to `provider`.
+    pub fn add_peers<P>(&mut self, provider: Arc<P>, route: Route) -> &mut Self
+    where
+        P: Provider + Peers + Send + Sync + 'static,
+    {
+        let member: Member<dyn Peers + Send + Sync> =
+            self.member(&provider, route, RequestTrait::Peers, provider.clone());
+        insert_member(&mut self.peers, member);
+        self
+    }
+
+    /// Route `AnalystRecommendations` requests to `provider`.
+    pub fn add_analyst_recommendations<P>(&mut self, provider: Arc<P>, route: Route) -> &mut Self
+    where
+        P: Provider + AnalystRecommendations + Send + Sync + 'static,
+    {
+        let member: Member<dyn AnalystRecommendations + Send + Sync> = self.member(
+            &provider,
+            route,
+            RequestTrait::AnalystRecommendations,
+            provider.clone(),
+        );
+        insert_member(&mut self.analyst_recommendations, member);
+        self
+    }
+
+    /// Route `FetchEarnings` requests to `provider`.
+    pub fn add_earnings<P>(&mut self, provider: Arc<P>, route: Route) -> &mut Self
+    where
+        P: Provider + FetchEarnings + Send + Sync + 'static,
+    {
+        let member: Member<dyn FetchEarnings + Send + Sync> = self.member(
+            &provider,
+            route,
+            RequestTrait::FetchEarnings,
+            provider.clone(),
+        );
+        insert_member(&mut self.earnings, member);
+        self
+    }
+
+    /// Route `FetchCompanyInformation` requests to `provider`.
+    pub fn add_company_information<P>(&mut self, provider: Arc<P>, route: Route) -> &mut Self
+    where
+        P: Provider + FetchCompanyInformation + Send + Sync + 'static,
+    {
+        let member: Member<dyn FetchCompanyInformation + Send + Sync> = self.member(
+            &provider,
+            route,
+            RequestTrait::FetchCompanyInformation,
+            provider.clone(),
+        );
+        insert_member(&mut self.company_information, member);
+        self
+    }
+
+    /// Route `FetchCompanyFinancials` requests to `provider`.
+    pub fn add_company_financials<P>(&mut self, provider: Arc<P>, route: Route) -> &mut Self
+    where
+        P: Provider + FetchCompanyFinancials + Send + Sync + 'static,
+    {
+        let member: Member<dyn FetchCompanyFinancials + Send + Sync> = self.member(
+            &provider,
+            route,
+            RequestTrait::FetchCompanyFinancials,
+            provider.clone(),
+        );
+        insert_member(&mut self.company_financials, member);
+        self
+    }
+
+    /// Route `FetchCompanyStatistics` requests to `provider`.
+    pub fn add_company_statistics<P>(&mut self, provider: Arc<P>, route: Route) -> &mut Self
+    where
+        P: Provider + FetchCompanyStatistics + Send + Sync + 'static,
+    {
+        let member: Member<dyn FetchCompanyStatistics + Send + Sync> = self.member(
+            &provider,
+            route,
+            RequestTrait::FetchCompanyStatistics,
+            provider.clone(),
+        );
+        insert_member(&mut self.company_statistics, member);
+        self
+    }
+
+    /// Route `FetchCorporateActions` requests to `provider`.
+    pub fn add_corporate_actions<P>(&mut self, provider: Arc<P>, route: Route) -> &mut Self
+    where
+        P: Provider + FetchCorporateActions + Send + Sync + 'static,
+    {
+        let member: Member<dyn FetchCorporateActions + Send + Sync> = self.member(
+            &provider,
+            route,
+            RequestTrait::FetchCorporateActions,
+            provider.clone(),
+        );
+        insert_member(&mut self.corporate_actions, member);
+        self
+    }
+
+    /// Route `FetchNews` requests to `provider`.
+    pub fn add_news<P>(&mut self, provider: Arc<P>, route: Route) -> &mut Self
+    where
+        P: Provider + FetchNews + Send + Sync + 'static,
+    {
+        let member: Member<dyn FetchNews + Send + Sync> =
+            self.member(&provider, route, RequestTrait::FetchNews, provider.clone());
+        insert_member(&mut self.news, member);
+        self
+    }
+
+    /// Route `FetchCategoryNews` requests to `provider`.
+    pub fn add_category_news<P>(&mut self, provider: Arc<P>, route: Route) -> &mut Self
+    where
+        P: Provider + FetchCategoryNews + Send + Sync + 'static,
+    {
+        let member: Member<dyn FetchCategoryNews + Send + Sync> = self.member(
+            &provider,
+            route,
+            RequestTrait::FetchCategoryNews,
+            provider.clone(),
+        );
+        insert_member(&mut self.category_news, member);
+        self
+    }
+
+    /// Route `FetchOptionChain` requests to `provider`.
+    pub fn add_option_chain<P>(&mut self, provider: Arc<P>, route: Route) -> &mut Self
+    where
+        P: Provider + FetchOptionChain + Send + Sync + 'static,
+    {
+        let member: Member<dyn FetchOptionChain + Send + Sync> = self.member(
+            &provider,
+            route,
+            RequestTrait::FetchOptionChain,
+            provider.clone(),
+        );
+        insert_member(&mut self.option_chain, member);
+        self
+    }
+
+    /// Route `FetchPriceQuote` requests to `provider`.
+    pub fn add_price_quote<P>(&mut self, provider: Arc<P>, route: Route) -> &mut Self
+    where
+        P: Provider + FetchPriceQuote + Send + Sync + 'static,
+    {
+        let member: Member<dyn FetchPriceQuote + Send + Sync> = self.member(
+            &provider,
+            route,
+            RequestTrait::FetchPriceQuote,
+            provider.clone(),
+        );
+        insert_member(&mut self.price_quote, member);
+        self
+    }
+
+    /// Route `FetchPriceQuoteBatch` requests to `provider`.
+    pub fn add_price_quote_batch<P>(&mut self, provider: Arc<P>, route: Route) -> &mut Self
+    where
+        P: Provider + FetchPriceQuoteBatch + Send + Sync + 'static,
+    {
+        let member: Member<dyn FetchPriceQuoteBatch + Send + Sync> = self.member(
+            &provider,
+            route,
+            RequestTrait::FetchPriceQuoteBatch,
+            provider.clone(),
+        );
+        insert_member(&mut self.price_quote_batch, member);
+        self
+    }
+
+    /// Route `FetchPriceRangeSeries` requests to `provider`.
+    pub fn add_price_range_series<P>(&mut self, provider: Arc<P>, route: Route) -> &mut Self
+    where
+        P: Provider + FetchPriceRangeSeries + Send + Sync + 'static,
+    {
+        let member: Member<dyn FetchPriceRangeSeries + Send + Sync> = self.member(
+            &provider,
+            route,
+            RequestTrait::FetchPriceRangeSeries,
+            provider.clone(),
+        );
+        insert_member(&mut self.price_range_series, member);
+        self
+    }
+
+    fn member<P, T>(
+        &mut self,
+        provider: &Arc<P>,
+        route: Route,
+        request_trait: RequestTrait,
+        routed: Arc<T>,
+    ) -> Member<T>
+    where
+        P: Provider + Send + Sync + 'static,
+        T: ?Sized,
+    {
+        let id = Arc::as_ptr(provider) as *const () as usize;
+        let capabilities = provider.capabilities();
+        if !self.providers.iter().any(|info| info.id == id) {
+            let finisher = provider.clone();
+            self.providers.push(ProviderInfo {
+                id,
+                attribution: provider.attribution(),
+                url: provider.url(),
+                capabilities: capabilities.clone(),
+                finish: Box::new(move || finisher.finish()),
+            });
+        }
+        Member {
+            provider: routed,
+            priority: route.priority,
+            markets: match route.markets {
+                Some(markets) => Some(markets),
+                None => capabilities.markets.clone(),
+            },
+            operations: routed_operations(&capabilities, request_trait),
+        }
+    }
+
+    fn route<T, R, F>(
+        &self,
+        members: &[Member<T>],
+        operation: Operation,
+        symbols: &[&Symbol],
+        call: F,
+    ) -> RequestResult<R>
+    where
+        T: ?Sized,
+        F: Fn(&T) -> RequestResult<R>,
+    {
+        let markets: Vec<String> = match &self.market_resolver {
+            Some(resolver) => symbols.iter().filter_map(|s| resolver(s)).collect(),
+            None => Vec::new(),
+        };
+        let mut last_error: Option<RequestError> = None;
+        for member in members {
+            if !member.operations.contains(&operation) || !member.covers(&markets) {
+                continue;
+            }
+            match call(member.provider.as_ref()) {
+                Err(err) if should_fall_back(&err) => last_error = Some(err),
+                result => return result,
+            }
+        }
+        Err(match last_error {
+            Some(err) => err,
+            None => RequestError::new(RequestErrorKind::Unsupported).with_provider(COMPOSITE_NAME),
+        })
+    }
+
+    /// The operations, and markets, routed across all traits; the markets are
+    /// `None` if no operations are routed, or if any provider is not restricted
+    /// to specific markets.
+    fn routed(&self) -> (HashSet<Operation>, Option<HashSet<String>>) {
+        fn add<T: ?Sized>(
+            members: &[Member<T>],
+            operations: &mut HashSet<Operation>,
+            markets: &mut Option<HashSet<String>>,
+        ) {
+            for member in members {
+                operations.extend(member.operations.iter().cloned());
+                *markets = match (markets.take(), &member.markets) {
+                    (Some(mut all), Some(covered)) => {
+                        all.extend(covered.iter().cloned());
+                        Some(all)
+                    }
+                    _ => None,
+                };
+            }
+        }
+        let mut operations = HashSet::new();
+        let mut markets = Some(HashSet::new());
+        let (ops, mics) = (&mut operations, &mut markets);
+        add(&self.peers, ops, mics);
+        add(&self.analyst_recommendations, ops, mics);
+        add(&self.earnings, ops, mics);
+        add(&self.company_information, ops, mics);
+        add(&self.company_financials, ops, mics);
+        add(&self.company_statistics, ops, mics);
+        add(&self.corporate_actions, ops, mics);
+        add(&self.news, ops, mics);
+        add(&self.category_news, ops, mics);
+        add(&self.option_chain, ops, mics);
+        add(&self.price_quote, ops, mics);
+        add(&self.price_quote_batch, ops, mics);
+        add(&self.price_range_series, ops, mics);
+        if operations.is_empty() {
+            markets = None;
+        }
+        (operations, markets)
+    }
+}
+
+impl<T: ?Sized> Member<T> {
+    fn covers(&self, markets: &[String]) -> bool {
+        match &self.markets {
+            Some(covered) => markets.iter().all(|mic| covered.contains(mic)),
+            None => true,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Trait Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Default for CompositeProvider {
+    fn default() -> Self {
+        CompositeProvider {
+            default_currency: DEFAULT_CURRENCY.to_string(),
+            market_resolver: None,
+            providers: Vec::new(),
+            peers: Vec::new(),
+            analyst_recommendations: Vec::new(),
+            earnings: Vec::new(),
+            company_information: Vec::new(),
+            company_financials: Vec::new(),
+            company_statistics: Vec::new(),
+            corporate_actions: Vec::new(),
+            news: Vec::new(),
+            category_news: Vec::new(),
+            option_chain: Vec::new(),
+            price_quote: Vec::new(),
+            price_quote_batch: Vec::new(),
+            price_range_series: Vec::new(),
+        }
+    }
+}
+
+impl Provider for CompositeProvider {
+    /// Create a new, empty, composite provider; use the `add_` functions to
+    /// add underlying providers.
+    fn new() -> RequestResult<Self> {
+        Ok(CompositeProvider::default())
+    }
+
+    fn attribution(&self) -> String {
+        let attributions: Vec<&str> = self
+            .providers
+            .iter()
+            .map(|info| info.attribution.as_str())
+            .collect();
+        attributions.join(", ")
+    }
+
+    fn url(&self) -> String {
+        match self.providers.first() {
+            Some(info) => info.url.clone(),
+            None => String::new(),
+        }
+    }
+
+    fn get_default_currency(&self) -> &String {
+        &self.default_currency
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        let (operations, markets) = self.routed();
+        let mut capabilities = Capabilities {
+            operations,
+            markets,
+            ..Default::default()
+        };
+        for info in &self.providers {
+            let other = &info.capabilities;
+            capabilities.real_time |= other.real_time;
+            capabilities.delayed_by = match (capabilities.delayed_by, other.delayed_by) {
+                (Some(lhs), Some(rhs)) => Some(lhs.min(rhs)),
+                (lhs, rhs) => lhs.or(rhs),
+            };
+            capabilities
+                .currencies
+                .extend(other.currencies.iter().cloned());
+            for (operation, size) in &other.max_batch_sizes {
+                let entry = capabilities
+                    .max_batch_sizes
+                    .entry(*operation)
+                    .or_insert(*size);
+                *entry = (*entry).min(*size);
+            }
+        }
+        // a provider that declares no currencies covers any currency.
+        if self
+            .providers
+            .iter()
+            .any(|info| info.capabilities.currencies.is_empty())
+        {
+            capabilities.currencies.clear();
+        }
+        capabilities
+    }
+
+    fn finish(&self) {
+        for info in &self.providers {
+            (info.finish)()
+        }
+    }
+}
+
+impl Peers for CompositeProvider {
+    fn peers(&self, for_symbol: Symbol) -> RequestResult<Symbols> {
+        self.route(&self.peers, Operation::Peers, &[&for_symbol], |p| {
+            p.peers(for_symbol.clone())
+        })
+    }
+}
+
+impl AnalystRecommendations for CompositeProvider {
+    fn target_price(&self, for_symbol: Symbol) -> RequestResult<Snapshot<PriceTarget>> {
+        self.route(
+            &self.analyst_recommendations,
+            Operation::TargetPrice,
+            &[&for_symbol],
+            |p| p.target_price(for_symbol.clone()),
+        )
+    }
+
+    fn consensus_rating(&self, for_symbol: Symbol) -> RequestResult<Vec<Bounded<Ratings>>> {
+        self.route(
+            &self.analyst_recommendations,
+            Operation::ConsensusRating,
+            &[&for_symbol],
+            |p| p.consensus_rating(for_symbol.clone()),
+        )
+    }
+
+    fn consensus_eps(&self, for_symbol: Symbol) -> RequestResult<Vec<EPSConsensus>> {
+        self.route(
+            &self.analyst_recommendations,
+            Operation::ConsensusEPS,
+            &[&for_symbol],
+            |p| p.consensus_eps(for_symbol.clone()),
+        )
+    }
+}
+
+impl FetchEarnings for CompositeProvider {
+    fn earnings(&self, for_symbol: Symbol, last: usize) -> RequestResult<Vec<EarningsReport>> {
+        self.route(&self.earnings, Operation::Earnings, &[&for_symbol], |p| {
+            p.earnings(for_symbol.clone(), last)
+        })
+    }
+}
+
+impl FetchCompanyInformation for CompositeProvider {
+    fn about(&self, for_symbol: Symbol) -> RequestResult<About> {
+        self.route(
+            &self.company_information,
+            Operation::About,
+            &[&for_symbol],
+            |p| p.about(for_symbol.clone()),
+        )
+    }
+
+    fn filings(
+        &self,
+        for_symbol: Symbol,
+        start_date: Option<Date>,
+        form_type: Option<String>,
+    ) -> RequestResult<RegulatoryFilings> {
+        self.route(
+            &self.company_information,
+            Operation::Filings,
+            &[&for_symbol],
+            |p| p.filings(for_symbol.clone(), start_date, form_type.clone()),
+        )
+    }
+}
+
+impl FetchCompanyFinancials for CompositeProvider {
+    fn reported_income(
+        &self,
+        for_symbol: Symbol,
+        last: u8,
+        period: ReportingPeriod,
+    ) -> RequestResult<Vec<Snapshot<IncomeStatement>>> {
+        self.route(
+            &self.company_financials,
+            Operation::ReportedIncome,
+            &[&for_symbol],
+            |p| p.reported_income(for_symbol.clone(), last, period),
+        )
+    }
+
+    fn reported_balance_sheet(
+        &self,
+        for_symbol: Symbol,
+        last: u8,
+        period: ReportingPeriod,
+    ) -> RequestResult<Vec<Snapshot<BalanceSheet>>> {
+        self.route(
+            &self.company_financials,
+            Operation::ReportedBalanceSheet,
+            &[&for_symbol],
+            |p| p.reported_balance_sheet(for_symbol.clone(), last, period),
+        )
+    }
+
+    fn reported_cash_flow(
+        &self,
+        for_symbol: Symbol,
+        last: u8,
+        period: ReportingPeriod,
+    ) -> RequestResult<Vec<Snapshot<CashFlowStatement>>> {
+        self.route(
+            &self.company_financials,
+            Operation::ReportedCashFlow,
+            &[&for_symbol],
+            |p| p.reported_cash_flow(for_symbol.clone(), last, period),
+        )
+    }
+}
+
+impl FetchCompanyStatistics for CompositeProvider {
+    fn core_statistics(&self, for_symbol: Symbol) -> RequestResult<Statistics> {
+        self.route(
+            &self.company_statistics,
+            Operation::CoreStatistics,
+            &[&for_symbol],
+            |p| p.core_statistics(for_symbol.clone()),
+        )
+    }
+}
+
+impl FetchCorporateActions for CompositeProvider {
+    fn dividends(
+        &self,
+        for_symbol: Symbol,
+        interval: SeriesInterval,
+    ) -> RequestResult<Vec<Dividend>> {
+        self.route(
+            &self.corporate_actions,
+            Operation::Dividends,
+            &[&for_symbol],
+            |p| p.dividends(for_symbol.clone(), interval),
+        )
+    }
+
+    fn splits(&self, for_symbol: Symbol, interval: SeriesInterval) -> RequestResult<Vec<Split>> {
+        self.route(
+            &self.corporate_actions,
+            Operation::Splits,
+            &[&for_symbol],
+            |p| p.splits(for_symbol.clone(), interval),
+        )
+    }
+
+    fn corporate_actions(
+        &self,
+        for_symbol: Symbol,
+        interval: SeriesInterval,
+    ) -> RequestResult<Vec<CorporateAction>> {
+        self.route(
+            &self.corporate_actions,
+            Operation::CorporateActions,
+            &[&for_symbol],
+            |p| p.corporate_actions(for_symbol.clone(), interval),
+        )
+    }
+}
+
+impl FetchNews for CompositeProvider {
+    fn latest_news(&self, for_symbol: Symbol, max_items: usize) -> RequestResult<NewsFeed> {
+        self.route(&self.news, Operation::LatestNews, &[&for_symbol], |p| {
+            p.latest_news(for_symbol.clone(), max_items)
+        })
+    }
+
+    fn news_from(
+        &self,
+        for_symbol: Symbol,
+        start_date: Date,
+        max_items: usize,
+    ) -> RequestResult<NewsFeed> {
+        self.route(&self.news, Operation::NewsFrom, &[&for_symbol], |p| {
+            p.news_from(for_symbol.clone(), start_date, max_items)
+        })
+    }
+}
+
+impl FetchCategoryNews for CompositeProvider {
+    fn latest_news(
+        &self,
+        category: String,
+        sub_category: Option<String>,
+        max_items: usize,
+    ) -> RequestResult<NewsFeed> {
+        self.route(
+            &self.category_news,
+            Operation::LatestCategoryNews,
+            &[],
+            |p| p.latest_news(category.clone(), sub_category.clone(), max_items),
+        )
+    }
+
+    fn news_from(
+        &self,
+        category: String,
+        sub_category: Option<String>,
+        start_date: Date,
+        max_items: usize,
+    ) -> RequestResult<NewsFeed> {
+        self.route(&self.category_news, Operation::CategoryNewsFrom, &[], |p| {
+            p.news_from(
+                category.clone(),
+                sub_category.clone(),
+                start_date,
+                max_items,
+            )
+        })
+    }
+}
+
+impl FetchOptionChain for CompositeProvider {
+    fn expirations(&self, for_symbol: Symbol) -> RequestResult<Vec<Date>> {
+        self.route(
+            &self.option_chain,
+            Operation::OptionExpirations,
+            &[&for_symbol],
+            |p| p.expirations(for_symbol.clone()),
+        )
+    }
+
+    fn option_chain(
+        &self,
+        for_symbol: Symbol,
+        expiration: Date,
+    ) -> RequestResult<Snapshot<OptionChain>> {
+        self.route(
+            &self.option_chain,
+            Operation::OptionChain,
+            &[&for_symbol],
+            |p| p.option_chain(for_symbol.clone(), expiration),
+        )
+    }
+}
+
+impl FetchPriceQuote for CompositeProvider {
+    fn latest_price_only(&self, for_symbol: Symbol) -> RequestResult<Money> {
+        self.route(
+            &self.price_quote,
+            Operation::LatestPriceOnly,
+            &[&for_symbol],
+            |p| p.latest_price_only(for_symbol.clone()),
+        )
+    }
+
+    fn real_time(&self, for_symbol: Symbol) -> RequestResult<Quote> {
+        self.route(
+            &self.price_quote,
+            Operation::RealTime,
+            &[&for_symbol],
+            |p| p.real_time(for_symbol.clone()),
+        )
+    }
+
+    fn delayed(&self, for_symbol: Symbol) -> RequestResult<DelayedQuote> {
+        self.route(&self.price_quote, Operation::Delayed, &[&for_symbol], |p| {
+            p.delayed(for_symbol.clone())
+        })
+    }
+}
+
+impl FetchPriceQuoteBatch for CompositeProvider {
+    fn real_time(&self, for_symbols: Symbols) -> RequestResult<HashMap<Symbol, Quote>> {
+        let symbols: Vec<&Symbol> = for_symbols.iter().collect();
+        self.route(
+            &self.price_quote_batch,
+            Operation::RealTimeBatch,
+            &symbols,
+            |p| p.real_time(for_symbols.clone()),
+        )
+    }
+
+    fn delayed(&self, for_symbols: Symbols) -> RequestResult<HashMap<Symbol, DelayedQuote>> {
+        let symbols: Vec<&Symbol> = for_symbols.iter().collect();
+        self.route(
+            &self.price_quote_batch,
+            Operation::DelayedBatch,
+            &symbols,
+            |p| p.delayed(for_symbols.clone()),
+        )
+    }
+}
+
+impl FetchPriceRangeSeries for CompositeProvider {
+    fn intra_day(
+        &self,
+        for_symbol: Symbol,
+        interval_minutes: u8,
+    ) -> RequestResult<Option<PriceRangeSeries>> {
+        self.route(
+            &self.price_range_series,
+            Operation::IntraDay,
+            &[&for_symbol],
+            |p| p.intra_day(for_symbol.clone(), interval_minutes),
+        )
+    }
+
+    fn last(
+        &self,
+        for_symbol: Symbol,
+        interval: SeriesInterval,
+    ) -> RequestResult<PriceRangeSeries> {
+        self.route(
+            &self.price_range_series,
+            Operation::Last,
+            &[&for_symbol],
+            |p| p.last(for_symbol.clone(), interval),
+        )
+    }
+
+    fn from(
+        &self,
+        for_symbol: Symbol,
+        start_date: DateTime,
+        interval: SeriesInterval,
+    ) -> RequestResult<PriceRangeSeries> {
+        self.route(
+            &self.price_range_series,
+            Operation::From,
+            &[&for_symbol],
+            |p| p.from(for_symbol.clone(), start_date, interval),
+        )
+    }
+
+    fn for_period(
+        &self,
+        for_symbol: Symbol,
+        period: FinancialPeriod,
+    ) -> RequestResult<PriceRangeSeries> {
+        self.route(
+            &self.price_range_series,
+            Operation::ForPeriod,
+            &[&for_symbol],
+            |p| p.for_period(for_symbol.clone(), period),
+        )
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn insert_member<T: ?Sized>(members: &mut Vec<Member<T>>, member: Member<T>) {
+    let index = members
+        .iter()
+        .position(|existing| existing.priority > member.priority)
+        .unwrap_or(members.len());
+    members.insert(index, member);
+}
+
+/// The operations a member is routed for; a provider that declares no
+/// operations at all is assumed to support every operation of the trait it
+/// was added for.
+fn routed_operations(
+    capabilities: &Capabilities,
+    request_trait: RequestTrait,
+) -> HashSet<Operation> {
+    let operations = request_trait.operations().into_iter();
+    if capabilities.operations.is_empty() {
+        operations.collect()
+    } else {
+        operations
+            .filter(|operation| capabilities.supports(*operation))
+            .collect()
+    }
+}
+
+fn should_fall_back(error: &RequestError) -> bool {
+    matches!(
+        error.kind(),
+        RequestErrorKind::Unsupported
+            | RequestErrorKind::RequestThrottled
+            | RequestErrorKind::QuotaExceeded
+            | RequestErrorKind::CommunicationError
+    )
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::{CompositeProvider, Route};
+    use crate::prelude::*;
+    use crate::provider::{Capabilities, RequestTrait};
+    use crate::quote::{DelayedQuote, FetchPriceQuote, Quote};
+    use std::sync::Arc;
+    use steel_cent::currency::with_code;
+
+    struct TestProvider {
+        name: &'static str,
+        price: RequestResult<i32>,
+        markets: Option<&'static [&'static str]>,
+    }
+
+    impl Provider for TestProvider {
+        fn new() -> RequestResult<Self> {
+            Err(RequestErrorKind::Unsupported.into())
+        }
+        fn attribution(&self) -> String {
+            self.name.to_string()
+        }
+        fn url(&self) -> String {
+            String::new()
+        }
+        fn get_default_currency(&self) -> &String {
+            unimplemented!()
+        }
+        fn capabilities(&self) -> Capabilities {
+            let mut capabilities = Capabilities::default();
+            capabilities.add_trait(RequestTrait::FetchPriceQuote);
+            capabilities.markets = self
+                .markets
+                .map(|mics| mics.iter().map(|mic| mic.to_string()).collect());
+            capabilities
+        }
+        fn finish(&self) {}
+    }
+
+    impl FetchPriceQuote for TestProvider {
+        fn latest_price_only(&self, _for_symbol: Symbol) -> RequestResult<Money> {
+            match &self.price {
+                Ok(price) => Ok(Money::of_major(with_code("USD").unwrap(), *price)),
+                Err(err) => Err(err.kind().clone().into()),
+            }
+        }
+        fn real_time(&self, _for_symbol: Symbol) -> RequestResult<Quote> {
+            Err(RequestErrorKind::Unsupported.into())
+        }
+        fn delayed(&self, _for_symbol: Symbol) -> RequestResult<DelayedQuote> {
+            Err(RequestErrorKind::Unsupported.into())
+        }
+    }
+
+    fn provider(name: &'static str, price: RequestResult<i32>) -> Arc<TestProvider> {
+        Arc::new(TestProvider {
+            name,
+            price,
+            markets: None,
+        })
+    }
+
+    fn price(composite: &CompositeProvider, symbol: &str) -> RequestResult<i32> {
+        composite
+            .latest_price_only(symbol.parse().unwrap())
+            .map(|money| money.minor_amount() / 100)
+    }
+
+    #[test]
+    fn test_priority_and_fallback() {
+        let mut composite = CompositeProvider::default();
+        composite
+            .add_price_quote(provider("second", Ok(2)), Route::with_priority(2))
+            .add_price_quote(
+                provider("first", Err(RequestErrorKind::RequestThrottled.into())),
+                Route::with_priority(1),
+            );
+        assert_eq!(price(&composite, "AAPL").unwrap(), 2);
+        assert_eq!(composite.attribution(), "second, first".to_string());
+
+        let mut composite = CompositeProvider::default();
+        composite
+            .add_price_quote(
+                provider("first", Err(RequestErrorKind::BadRequestError.into())),
+                Route::default(),
+            )
+            .add_price_quote(provider("second", Ok(2)), Route::default());
+        assert_eq!(
+            price(&composite, "AAPL").err().unwrap().kind(),
+            &RequestErrorKind::BadRequestError
+        );
+    }
+
+    #[test]
+    fn test_market_routing() {
+        let mut composite = CompositeProvider::default();
+        composite
+            .add_price_quote(
+                Arc::new(TestProvider {
+                    name: "us",
+                    price: Ok(1),
+                    markets: Some(&["XNAS"]),
+                }),
+                Route::default(),
+            )
+            .add_price_quote(
+                provider("uk", Ok(2)),
+                Route::default().for_markets(&["XLON"]),
+            )
+            .set_market_resolver(|symbol| match symbol.as_str() {
+                "VOD" => Some("XLON".to_string()),
+                "AAPL" => Some("XNAS".to_string()),
+                _ => Some("XTKS".to_string()),
+            });
+        assert_eq!(price(&composite, "AAPL").unwrap(), 1);
+        assert_eq!(price(&composite, "VOD").unwrap(), 2);
+        assert_eq!(
+            price(&composite, "TM").err().unwrap().kind(),
+            &RequestErrorKind::Unsupported
+        );
+        assert!(composite.capabilities().covers_market("XLON"));
+    }
+
+    #[test]
+    fn test_unrouted_trait() {
+        let mut composite = CompositeProvider::default();
+        composite.add_price_quote(provider("only", Ok(1)), Route::default());
+        assert_eq!(
+            composite
+                .real_time("AAPL".parse().unwrap())
+                .err()
+                .unwrap()
+                .kind(),
+            &RequestErrorKind::Unsupported
+        );
+        assert!(!composite
+            .capabilities()
+            .supports_any(RequestTrait::FetchNews));
+    }
+
+    struct PlainProvider;
+
+    impl Provider for PlainProvider {
+        fn new() -> RequestResult<Self> {
+            Ok(PlainProvider)
+        }
+        fn attribution(&self) -> String {
+            "plain".to_string()
+        }
+        fn url(&self) -> String {
+            String::new()
+        }
+        fn get_default_currency(&self) -> &String {
+            unimplemented!()
+        }
+        fn finish(&self) {}
+    }
+
+    impl FetchPriceQuote for PlainProvider {
+        fn latest_price_only(&self, _for_symbol: Symbol) -> RequestResult<Money> {
+            Ok(Money::of_major(with_code("USD").unwrap(), 3))
+        }
+        fn real_time(&self, _for_symbol: Symbol) -> RequestResult<Quote> {
+            Err(RequestErrorKind::Unsupported.into())
+        }
+        fn delayed(&self, _for_symbol: Symbol) -> RequestResult<DelayedQuote> {
+            Err(RequestErrorKind::Unsupported.into())
+        }
+    }
+
+    #[test]
+    fn test_default_capabilities() {
+        let mut composite = CompositeProvider::default();
+        composite.add_price_quote(Arc::new(PlainProvider), Route::default());
+        assert_eq!(price(&composite, "AAPL").unwrap(), 3);
+        assert!(composite
+            .capabilities()
+            .supports_all(RequestTrait::FetchPriceQuote));
+        assert!(!composite
+            .capabilities()
+            .supports_any(RequestTrait::FetchNews));
+    }
+
+    #[test]
+    fn test_empty_capabilities() {
+        let composite = CompositeProvider::default();
+        let capabilities = composite.capabilities();
+        assert!(capabilities.operations.is_empty());
+        assert_eq!(capabilities.markets, None);
+    }
+}
@@ -0,0 +1,420 @@
+/*!
+Provides structs and traits that represent corporate actions, and functions to
+adjust prices for them.
+
+Corporate actions are the events, decided by a company, that change its shares
+and so the meaning of historical prices; cash dividends, stock splits (including
+reverse splits), spin-offs, and symbol changes. The _request trait_
+`FetchCorporateActions` fetches these for a symbol.
+
+## Price Adjustment
+
+Providers often return prices as they were traded, _unadjusted_, and a drop in
+price on the ex-date of a split or dividend then looks like a fall in value.
+The functions `split_adjusted` and `adjusted` take a raw `PriceRangeSeries` and
+a list of actions and return the series with all prices before each ex-date
+adjusted to be comparable with prices on and after it.
+
+* For a split of `to_factor` shares for each `from_factor` shares, prices are
+  divided by, and volumes multiplied by, `to_factor / from_factor`.
+* For a cash dividend, prices are multiplied by `1 - amount / close`, where
+  `close` is the last close before the ex-date; volumes are unchanged.
+* A spin-off with a `value_per_share` is adjusted as a cash dividend of that
+  value, a symbol change requires no adjustment.
+
+```rust
+use fin_model::corporate::{split_adjusted, CorporateAction, Split};
+use fin_model::prelude::*;
+use fin_model::quote::{PriceRange, PriceRangeSeries, SeriesInterval};
+use steel_cent::currency::USD;
+
+let day = |day: u32, close: i32, volume: u64| Snapshot {
+    date: Date::from_ymd(2020, 8, day).and_hms(0, 0, 0),
+    data: PriceRange {
+        open: Money::of_major(USD, close),
+        close: Money::of_major(USD, close),
+        high: Money::of_major(USD, close),
+        low: Money::of_major(USD, close),
+        volume: Some(volume),
+    },
+};
+let raw: PriceRangeSeries = Series {
+    interval: SeriesInterval::OneMonth,
+    intra_interval: None,
+    series: vec![day(28, 500, 100), day(31, 125, 400)],
+};
+let four_for_one = CorporateAction::Split(Split {
+    ex_date: Date::from_ymd(2020, 8, 31),
+    declared_date: None,
+    from_factor: 1.0,
+    to_factor: 4.0,
+    description: None,
+});
+
+let adjusted = split_adjusted(&raw, &[four_for_one]);
+assert_eq!(adjusted.series[0].data.close, Money::of_major(USD, 125));
+assert_eq!(adjusted.series[0].data.volume, Some(400));
+```
+*/
+
+use std::cmp::Reverse;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::quote::{PriceRangeSeries, SeriesInterval};
+use crate::series::amount;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// How often a company pays dividends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DividendFrequency {
+    Annual,
+    SemiAnnual,
+    Quarterly,
+    Monthly,
+    Irregular,
+}
+
+/// A cash dividend.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Dividend {
+    /// the first date on which shares trade without the dividend
+    pub ex_date: Date,
+    /// the (optional) date on which the company announced the dividend
+    pub declared_date: Option<Date>,
+    /// the (optional) date by which a holder must be recorded to receive the dividend
+    pub record_date: Option<Date>,
+    /// the (optional) date on which the dividend is paid
+    pub payment_date: Option<Date>,
+    /// the amount paid per share
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::money"))]
+    pub amount: Money,
+    /// the (optional) frequency of the dividend
+    pub frequency: Option<DividendFrequency>,
+    /// an (optional) description of the dividend
+    pub description: Option<String>,
+}
+
+/// A stock split, `to_factor` shares for every `from_factor` shares; a reverse
+/// split has a `to_factor` less than its `from_factor`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Split {
+    /// the first date on which shares trade at the split-adjusted price
+    pub ex_date: Date,
+    /// the (optional) date on which the company announced the split
+    pub declared_date: Option<Date>,
+    /// the number of shares held before the split
+    pub from_factor: f64,
+    /// the number of shares held after the split
+    pub to_factor: f64,
+    /// an (optional) description of the split
+    pub description: Option<String>,
+}
+
+/// The distribution of shares in a new company to holders of shares in the parent.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SpinOff {
+    /// the first date on which the parent's shares trade without the distribution
+    pub ex_date: Date,
+    /// the symbol of the new company
+    pub new_symbol: Symbol,
+    /// the number of shares in the new company distributed for each parent share
+    pub shares_per_share: f64,
+    /// the (optional) value of the shares distributed for each parent share,
+    /// used to adjust prices
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::option_money"))]
+    pub value_per_share: Option<Money>,
+}
+
+/// A change of the symbol a security trades under.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SymbolChange {
+    /// the first date on which the security trades under the new symbol
+    pub effective_date: Date,
+    /// the symbol before the change
+    pub old_symbol: Symbol,
+    /// the symbol after the change
+    pub new_symbol: Symbol,
+}
+
+/// One of the corporate actions that affect a security.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CorporateAction {
+    Dividend(Dividend),
+    Split(Split),
+    SpinOff(SpinOff),
+    SymbolChange(SymbolChange),
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Traits
+// ------------------------------------------------------------------------------------------------
+
+/// This trait is implemented by providers that are able to provide the corporate
+/// actions for a security, over the specified `SeriesInterval` going back from
+/// the current day.
+pub trait FetchCorporateActions {
+    /// Return the cash dividends for the symbol specified.
+    fn dividends(
+        &self,
+        for_symbol: Symbol,
+        interval: SeriesInterval,
+    ) -> RequestResult<Vec<Dividend>>;
+
+    /// Return the stock splits for the symbol specified.
+    fn splits(&self, for_symbol: Symbol, interval: SeriesInterval) -> RequestResult<Vec<Split>>;
+
+    /// Return all corporate actions for the symbol specified, in ex-date order.
+    fn corporate_actions(
+        &self,
+        for_symbol: Symbol,
+        interval: SeriesInterval,
+    ) -> RequestResult<Vec<CorporateAction>>;
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+/// Return `series` adjusted for the splits in `actions`; all other actions are ignored.
+pub fn split_adjusted(series: &PriceRangeSeries, actions: &[CorporateAction]) -> PriceRangeSeries {
+    adjust(series, actions, false)
+}
+
+/// Return `series` adjusted for the splits, dividends, and valued spin-offs in `actions`.
+pub fn adjusted(series: &PriceRangeSeries, actions: &[CorporateAction]) -> PriceRangeSeries {
+    adjust(series, actions, true)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Split {
+    /// The number of shares held after the split for each share held before it.
+    pub fn ratio(&self) -> f64 {
+        self.to_factor / self.from_factor
+    }
+}
+
+impl CorporateAction {
+    /// The date from which the action takes effect; the ex-date, or for a
+    /// symbol change the effective date.
+    pub fn ex_date(&self) -> Date {
+        match self {
+            CorporateAction::Dividend(dividend) => dividend.ex_date,
+            CorporateAction::Split(split) => split.ex_date,
+            CorporateAction::SpinOff(spin_off) => spin_off.ex_date,
+            CorporateAction::SymbolChange(change) => change.effective_date,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+/// Scale all prices before each action's ex-date by the price factor, and
+/// volumes by the volume factor, of the action.
+fn adjust(series: &PriceRangeSeries, actions: &[CorporateAction], cash: bool) -> PriceRangeSeries {
+    let mut factors: Vec<(Date, f64, f64)> = actions
+        .iter()
+        .filter_map(|action| {
+            let ex_date = action.ex_date();
+            let (price, volume) = match action {
+                CorporateAction::Split(split)
+                    if split.from_factor > 0.0 && split.to_factor > 0.0 =>
+                {
+                    (1.0 / split.ratio(), split.ratio())
+                }
+                CorporateAction::Dividend(dividend) if cash => {
+                    (cash_factor(series, ex_date, &dividend.amount)?, 1.0)
+                }
+                CorporateAction::SpinOff(SpinOff {
+                    value_per_share: Some(value),
+                    ..
+                }) if cash => (cash_factor(series, ex_date, value)?, 1.0),
+                _ => return None,
+            };
+            Some((ex_date, price, volume))
+        })
+        .collect();
+    factors.sort_by_key(|factor| Reverse(factor.0));
+
+    let mut adjusted = series.clone();
+    let (mut price_factor, mut volume_factor) = (1.0, 1.0);
+    let mut pending = factors.iter().peekable();
+    for snapshot in adjusted.series.iter_mut().rev() {
+        while let Some((_, price, volume)) =
+            pending.next_if(|(ex_date, _, _)| snapshot.date.date() < *ex_date)
+        {
+            price_factor *= price;
+            volume_factor *= volume;
+        }
+        let range = &mut snapshot.data;
+        range.open = scale(&range.open, price_factor);
+        range.close = scale(&range.close, price_factor);
+        range.high = scale(&range.high, price_factor);
+        range.low = scale(&range.low, price_factor);
+        range.volume = range
+            .volume
+            .map(|volume| (volume as f64 * volume_factor).round() as u64);
+    }
+    adjusted
+}
+
+/// The price factor for a cash distribution of `value` per share on `ex_date`,
+/// `None` if there is no positive close before the ex-date to adjust from, or
+/// if the distribution is negative or not less than that close.
+fn cash_factor(series: &PriceRangeSeries, ex_date: Date, value: &Money) -> Option<f64> {
+    let close = series
+        .series
+        .iter()
+        .take_while(|snapshot| snapshot.date.date() < ex_date)
+        .last()
+        .map(|snapshot| amount(&snapshot.data.close))?;
+    if close <= 0.0 {
+        return None;
+    }
+    let factor = 1.0 - amount(value) / close;
+    if factor > 0.0 && factor <= 1.0 {
+        Some(factor)
+    } else {
+        None
+    }
+}
+
+fn scale(money: &Money, factor: f64) -> Money {
+    Money::of_minor(
+        money.currency,
+        (f64::from(money.minor_amount()) * factor).round() as i32,
+    )
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quote::PriceRange;
+    use steel_cent::currency::USD;
+
+    fn usd(cents: i32) -> Money {
+        Money::of_minor(USD, cents)
+    }
+
+    fn daily(closes: &[(u32, i32, u64)]) -> PriceRangeSeries {
+        Series {
+            interval: SeriesInterval::OneMonth,
+            intra_interval: None,
+            series: closes
+                .iter()
+                .map(|(day, close, volume)| Snapshot {
+                    date: Date::from_ymd(2019, 8, *day).and_hms(0, 0, 0),
+                    data: PriceRange {
+                        open: usd(*close),
+                        close: usd(*close),
+                        high: usd(*close + 100),
+                        low: usd(*close - 100),
+                        volume: Some(*volume),
+                    },
+                })
+                .collect(),
+        }
+    }
+
+    fn split(day: u32, from_factor: f64, to_factor: f64) -> CorporateAction {
+        CorporateAction::Split(Split {
+            ex_date: Date::from_ymd(2019, 8, day),
+            declared_date: None,
+            from_factor,
+            to_factor,
+            description: None,
+        })
+    }
+
+    fn dividend(day: u32, cents: i32) -> CorporateAction {
+        CorporateAction::Dividend(Dividend {
+            ex_date: Date::from_ymd(2019, 8, day),
+            declared_date: None,
+            record_date: None,
+            payment_date: None,
+            amount: usd(cents),
+            frequency: Some(DividendFrequency::Quarterly),
+            description: None,
+        })
+    }
+
+    fn closes(series: &PriceRangeSeries) -> Vec<i32> {
+        series.closes().map(|close| close.minor_amount()).collect()
+    }
+
+    #[test]
+    fn test_split_adjusted() {
+        let raw = daily(&[(1, 40_000, 100), (2, 20_200, 210), (5, 20_000, 200)]);
+        let adjusted = split_adjusted(&raw, &[split(2, 1.0, 2.0), dividend(5, 100)]);
+        assert_eq!(closes(&adjusted), vec![20_000, 20_200, 20_000]);
+        assert_eq!(adjusted.series[0].data.high, usd(20_050));
+        assert_eq!(adjusted.series[0].data.volume, Some(200));
+        assert_eq!(adjusted.series[2].data.volume, Some(200));
+
+        // a 1-for-4 reverse split.
+        let adjusted = split_adjusted(&raw, &[split(5, 4.0, 1.0)]);
+        assert_eq!(closes(&adjusted), vec![160_000, 80_800, 20_000]);
+        assert_eq!(adjusted.series[1].data.volume, Some(53));
+    }
+
+    #[test]
+    fn test_adjusted() {
+        let raw = daily(&[(1, 40_000, 100), (2, 20_200, 210), (5, 20_000, 200)]);
+        let actions = vec![
+            dividend(5, 202),
+            split(2, 1.0, 2.0),
+            CorporateAction::SymbolChange(SymbolChange {
+                effective_date: Date::from_ymd(2019, 8, 2),
+                old_symbol: "OLD".parse().unwrap(),
+                new_symbol: "NEW".parse().unwrap(),
+            }),
+        ];
+        let result = adjusted(&raw, &actions);
+        // the dividend factor is 1 - 2.02 / 202.00, or 0.99.
+        assert_eq!(closes(&result), vec![19_800, 19_998, 20_000]);
+        assert_eq!(result.series[0].data.volume, Some(200));
+        assert_eq!(result.series[1].data.volume, Some(210));
+
+        // a dividend before the series starts has no effect.
+        let result = adjusted(&raw, &[dividend(1, 500)]);
+        assert_eq!(closes(&result), closes(&raw));
+    }
+
+    #[test]
+    fn test_adjusted_invalid_dividend() {
+        let raw = daily(&[(1, 40_000, 100), (2, 20_200, 210), (5, 20_000, 200)]);
+        // a dividend equal to, or larger than, the close is not applied.
+        let result = adjusted(&raw, &[dividend(5, 20_200)]);
+        assert_eq!(closes(&result), closes(&raw));
+        let result = adjusted(&raw, &[dividend(5, 30_000)]);
+        assert_eq!(closes(&result), closes(&raw));
+        // nor is a negative dividend.
+        let result = adjusted(&raw, &[dividend(5, -100)]);
+        assert_eq!(closes(&result), closes(&raw));
+
+        // nor is a dividend following a zero close.
+        let raw = daily(&[(1, 0, 100), (5, 20_000, 200)]);
+        let result = adjusted(&raw, &[dividend(5, 100)]);
+        assert_eq!(closes(&result), closes(&raw));
+    }
+}
@@ -0,0 +1,519 @@
+/*!
+Provides types for standard security identifiers.
+
+While market symbols identify a security within a market, a number of
+standards exist to identify a security across markets. This module supports
+the following, all of which include a check digit that is validated when
+the identifier is parsed.
+
+* [ISIN](https://www.isin.org/) International Securities Identification
+  Number (ISO 6166), 12 characters; a two letter country code, a nine
+  character national identifier and a check digit.
+* [CUSIP](https://www.cusip.com/) Committee on Uniform Security Identification
+  Procedures, 9 characters; used for North American securities.
+* [SEDOL](https://www.londonstockexchange.com/) Stock Exchange Daily Official
+  List, 7 characters; used for UK and Irish securities.
+* [FIGI](https://www.openfigi.com/) Financial Instrument Global Identifier,
+  12 characters; an open identifier issued by Bloomberg.
+
+Both CUSIP and SEDOL values are used as the national identifier within an
+ISIN and so may be converted using `ISIN::from_cusip` and `ISIN::from_sedol`.
+
+## Example
+
+```rust
+use fin_model::identifier::{CUSIP, ISIN};
+
+let cusip: CUSIP = "037833100".parse().unwrap();
+let isin = ISIN::from_cusip(&cusip, "US").unwrap();
+assert_eq!(isin.to_string(), "US0378331005");
+assert_eq!(isin.to_cusip(), Some(cusip));
+```
+*/
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// An International Securities Identification Number (ISO 6166).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct ISIN(String);
+
+/// A CUSIP identifier, used for North American securities.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct CUSIP(String);
+
+/// A SEDOL identifier, used for UK and Irish securities.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct SEDOL(String);
+
+/// A Financial Instrument Global Identifier.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct FIGI(String);
+
+/// Any one of the supported security identifiers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SecurityIdentifier {
+    ISIN(ISIN),
+    CUSIP(CUSIP),
+    SEDOL(SEDOL),
+    FIGI(FIGI),
+}
+
+/// Errors that can result from parsing an identifier from a string.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdentifierError {
+    /// the string is not the length required by the identifier type
+    InvalidLength(usize),
+    /// the string contains a character not permitted at this position
+    InvalidCharacter(char),
+    /// the country, or prefix, is not valid for the identifier type
+    InvalidPrefix(String),
+    /// the calculated check digit does not match the one provided
+    InvalidCheckDigit,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+macro_rules! identifier_impls {
+    ($name:ident) => {
+        impl $name {
+            /// Return the identifier as a string slice.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Return the check digit.
+            pub fn check_digit(&self) -> u8 {
+                self.0.as_bytes()[self.0.len() - 1] - b'0'
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = IdentifierError;
+
+            fn try_from(s: String) -> Result<Self, Self::Error> {
+                $name::from_str(&s)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+    };
+}
+
+identifier_impls!(ISIN);
+identifier_impls!(CUSIP);
+identifier_impls!(SEDOL);
+identifier_impls!(FIGI);
+
+impl ISIN {
+    /// Construct an ISIN from a CUSIP and country code, usually `"US"` or `"CA"`.
+    pub fn from_cusip(cusip: &CUSIP, country: &str) -> Result<Self, IdentifierError> {
+        ISIN::from_nsin(country, &cusip.0)
+    }
+
+    /// Construct an ISIN from a SEDOL and country code, usually `"GB"` or `"IE"`.
+    /// The SEDOL is padded with two leading zeros to form the national identifier.
+    pub fn from_sedol(sedol: &SEDOL, country: &str) -> Result<Self, IdentifierError> {
+        ISIN::from_nsin(country, &format!("00{}", sedol.0))
+    }
+
+    /// Construct an ISIN from a country code and nine character National
+    /// Securities Identifying Number (NSIN), calculating the check digit.
+    pub fn from_nsin(country: &str, nsin: &str) -> Result<Self, IdentifierError> {
+        let body = format!(
+            "{}{}",
+            country.to_ascii_uppercase(),
+            nsin.to_ascii_uppercase()
+        );
+        check_length(&body, 11)?;
+        check_isin_body(&body)?;
+        let check = isin_check_digit(&body);
+        Ok(ISIN(format!("{}{}", body, check)))
+    }
+
+    /// The two letter country code of the issuer.
+    pub fn country_code(&self) -> &str {
+        &self.0[..2]
+    }
+
+    /// The nine character National Securities Identifying Number (NSIN).
+    pub fn nsin(&self) -> &str {
+        &self.0[2..11]
+    }
+
+    /// Return the CUSIP, if this is a North American ISIN with a valid one.
+    pub fn to_cusip(&self) -> Option<CUSIP> {
+        match self.country_code() {
+            "US" | "CA" => CUSIP::from_str(self.nsin()).ok(),
+            _ => None,
+        }
+    }
+
+    /// Return the SEDOL, if this is a UK or Irish ISIN with a valid one.
+    pub fn to_sedol(&self) -> Option<SEDOL> {
+        match (self.country_code(), &self.nsin()[..2]) {
+            ("GB", "00") | ("IE", "00") => SEDOL::from_str(&self.nsin()[2..]).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for ISIN {
+    type Err = IdentifierError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_ascii_uppercase();
+        check_length(&s, 12)?;
+        check_isin_body(&s[..11])?;
+        check_digit_char(&s, isin_check_digit(&s[..11]))?;
+        Ok(ISIN(s))
+    }
+}
+
+impl FromStr for CUSIP {
+    type Err = IdentifierError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_ascii_uppercase();
+        check_length(&s, 9)?;
+        let mut sum = 0;
+        for (i, c) in s[..8].chars().enumerate() {
+            let value = match c {
+                '*' => 36,
+                '@' => 37,
+                '#' => 38,
+                _ => char_value(c)?,
+            };
+            let value = if i % 2 == 1 { value * 2 } else { value };
+            sum += value / 10 + value % 10;
+        }
+        check_digit_char(&s, ((10 - (sum % 10)) % 10) as u8)?;
+        Ok(CUSIP(s))
+    }
+}
+
+impl FromStr for SEDOL {
+    type Err = IdentifierError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const WEIGHTS: [u32; 6] = [1, 3, 1, 7, 3, 9];
+        let s = s.to_ascii_uppercase();
+        check_length(&s, 7)?;
+        let mut sum = 0;
+        for (i, c) in s[..6].chars().enumerate() {
+            if is_vowel(c) {
+                return Err(IdentifierError::InvalidCharacter(c));
+            }
+            sum += char_value(c)? * WEIGHTS[i];
+        }
+        check_digit_char(&s, ((10 - (sum % 10)) % 10) as u8)?;
+        Ok(SEDOL(s))
+    }
+}
+
+impl FromStr for FIGI {
+    type Err = IdentifierError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const EXCLUDED_PREFIXES: [&str; 7] = ["BS", "BM", "GG", "GB", "GH", "KY", "VG"];
+        let s = s.to_ascii_uppercase();
+        check_length(&s, 12)?;
+        let prefix = &s[..2];
+        if EXCLUDED_PREFIXES.contains(&prefix) || !prefix.chars().all(is_consonant) {
+            return Err(IdentifierError::InvalidPrefix(prefix.to_string()));
+        }
+        if &s[2..3] != "G" {
+            return Err(IdentifierError::InvalidCharacter(
+                s[2..3].chars().next().unwrap(),
+            ));
+        }
+        let mut sum = 0;
+        for (i, c) in s[..11].chars().enumerate() {
+            if !(c.is_ascii_digit() || is_consonant(c)) {
+                return Err(IdentifierError::InvalidCharacter(c));
+            }
+            let value = char_value(c)?;
+            let value = if i % 2 == 1 { value * 2 } else { value };
+            sum += value / 10 + value % 10;
+        }
+        check_digit_char(&s, ((10 - (sum % 10)) % 10) as u8)?;
+        Ok(FIGI(s))
+    }
+}
+
+impl Display for SecurityIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SecurityIdentifier::ISIN(id) => write!(f, "ISIN:{}", id),
+            SecurityIdentifier::CUSIP(id) => write!(f, "CUSIP:{}", id),
+            SecurityIdentifier::SEDOL(id) => write!(f, "SEDOL:{}", id),
+            SecurityIdentifier::FIGI(id) => write!(f, "FIGI:{}", id),
+        }
+    }
+}
+
+impl FromStr for SecurityIdentifier {
+    type Err = IdentifierError;
+
+    /// Parse an identifier in the form produced by `Display`, `"ISIN:{value}"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.find(':') {
+            None => Err(IdentifierError::InvalidPrefix(s.to_string())),
+            Some(idx) => {
+                let value = &s[idx + 1..];
+                match s[..idx].to_ascii_uppercase().as_str() {
+                    "ISIN" => Ok(SecurityIdentifier::ISIN(ISIN::from_str(value)?)),
+                    "CUSIP" => Ok(SecurityIdentifier::CUSIP(CUSIP::from_str(value)?)),
+                    "SEDOL" => Ok(SecurityIdentifier::SEDOL(SEDOL::from_str(value)?)),
+                    "FIGI" => Ok(SecurityIdentifier::FIGI(FIGI::from_str(value)?)),
+                    prefix => Err(IdentifierError::InvalidPrefix(prefix.to_string())),
+                }
+            }
+        }
+    }
+}
+
+impl Display for IdentifierError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IdentifierError::InvalidLength(length) => {
+                write!(f, "identifier has an invalid length {}", length)
+            }
+            IdentifierError::InvalidCharacter(c) => {
+                write!(f, "identifier contains the invalid character '{}'", c)
+            }
+            IdentifierError::InvalidPrefix(prefix) => {
+                write!(f, "identifier has an invalid prefix '{}'", prefix)
+            }
+            IdentifierError::InvalidCheckDigit => write!(f, "identifier check digit is invalid"),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn check_length(s: &str, length: usize) -> Result<(), IdentifierError> {
+    if s.len() == length && s.is_ascii() {
+        Ok(())
+    } else {
+        Err(IdentifierError::InvalidLength(s.chars().count()))
+    }
+}
+
+fn check_isin_body(body: &str) -> Result<(), IdentifierError> {
+    let country = &body[..2];
+    if !country.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(IdentifierError::InvalidPrefix(country.to_string()));
+    }
+    for c in body[2..].chars() {
+        let _ = char_value(c)?;
+    }
+    Ok(())
+}
+
+fn check_digit_char(s: &str, expected: u8) -> Result<(), IdentifierError> {
+    let c = s.chars().last().unwrap();
+    match c.to_digit(10) {
+        None => Err(IdentifierError::InvalidCharacter(c)),
+        Some(d) if d as u8 == expected => Ok(()),
+        Some(_) => Err(IdentifierError::InvalidCheckDigit),
+    }
+}
+
+/// The Luhn check digit over the digit expansion of the characters, where
+/// letters are expanded to their two digit value.
+fn isin_check_digit(body: &str) -> u8 {
+    let digits: Vec<u32> = body
+        .chars()
+        .map(|c| char_value(c).unwrap())
+        .flat_map(|v| {
+            if v >= 10 {
+                vec![v / 10, v % 10]
+            } else {
+                vec![v]
+            }
+        })
+        .collect();
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, d)| {
+            if i % 2 == 0 {
+                let d = d * 2;
+                d / 10 + d % 10
+            } else {
+                *d
+            }
+        })
+        .sum();
+    ((10 - (sum % 10)) % 10) as u8
+}
+
+/// Digits have their own value, letters are numbered from `A` = 10.
+fn char_value(c: char) -> Result<u32, IdentifierError> {
+    match c {
+        '0'..='9' => Ok(c as u32 - '0' as u32),
+        'A'..='Z' => Ok(c as u32 - 'A' as u32 + 10),
+        _ => Err(IdentifierError::InvalidCharacter(c)),
+    }
+}
+
+fn is_vowel(c: char) -> bool {
+    "AEIOU".contains(c)
+}
+
+fn is_consonant(c: char) -> bool {
+    c.is_ascii_uppercase() && !is_vowel(c)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_isin() {
+        let isin = ISIN::from_str("US0378331005").unwrap();
+        assert_eq!(isin.country_code(), "US");
+        assert_eq!(isin.nsin(), "037833100");
+        assert_eq!(isin.check_digit(), 5);
+        assert!(ISIN::from_str("GB0002634946").is_ok());
+        assert!(ISIN::from_str("au0000xvgza3").is_ok());
+
+        assert_eq!(
+            ISIN::from_str("US0378331006"),
+            Err(IdentifierError::InvalidCheckDigit)
+        );
+        assert_eq!(
+            ISIN::from_str("US037833100"),
+            Err(IdentifierError::InvalidLength(11))
+        );
+        assert_eq!(
+            ISIN::from_str("120378331005"),
+            Err(IdentifierError::InvalidPrefix("12".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_cusip() {
+        assert!(CUSIP::from_str("037833100").is_ok());
+        assert!(CUSIP::from_str("38259P508").is_ok());
+        assert_eq!(
+            CUSIP::from_str("037833101"),
+            Err(IdentifierError::InvalidCheckDigit)
+        );
+        assert_eq!(
+            CUSIP::from_str("03783310X"),
+            Err(IdentifierError::InvalidCharacter('X'))
+        );
+    }
+
+    #[test]
+    fn test_sedol() {
+        assert!(SEDOL::from_str("0263494").is_ok());
+        assert!(SEDOL::from_str("B0YBKJ7").is_ok());
+        assert_eq!(
+            SEDOL::from_str("0263495"),
+            Err(IdentifierError::InvalidCheckDigit)
+        );
+        assert_eq!(
+            SEDOL::from_str("A263494"),
+            Err(IdentifierError::InvalidCharacter('A'))
+        );
+    }
+
+    #[test]
+    fn test_figi() {
+        assert!(FIGI::from_str("BBG000BLNNH6").is_ok());
+        assert!(FIGI::from_str("BBG000B9XRY4").is_ok());
+        assert_eq!(
+            FIGI::from_str("BBG000BLNNH7"),
+            Err(IdentifierError::InvalidCheckDigit)
+        );
+        assert_eq!(
+            FIGI::from_str("GBG000BLNNH6"),
+            Err(IdentifierError::InvalidPrefix("GB".to_string()))
+        );
+        assert_eq!(
+            FIGI::from_str("BBG000BLANH6"),
+            Err(IdentifierError::InvalidCharacter('A'))
+        );
+    }
+
+    #[test]
+    fn test_conversions() {
+        let cusip = CUSIP::from_str("037833100").unwrap();
+        let isin = ISIN::from_cusip(&cusip, "US").unwrap();
+        assert_eq!(isin.to_string(), "US0378331005");
+        assert_eq!(isin.to_cusip(), Some(cusip));
+        assert_eq!(isin.to_sedol(), None);
+
+        let sedol = SEDOL::from_str("0263494").unwrap();
+        let isin = ISIN::from_sedol(&sedol, "GB").unwrap();
+        assert_eq!(isin.to_string(), "GB0002634946");
+        assert_eq!(isin.to_sedol(), Some(sedol));
+        assert_eq!(isin.to_cusip(), None);
+    }
+
+    #[test]
+    fn test_security_identifier() {
+        let id = SecurityIdentifier::from_str("isin:US0378331005").unwrap();
+        assert_eq!(
+            id,
+            SecurityIdentifier::ISIN(ISIN::from_str("US0378331005").unwrap())
+        );
+        assert_eq!(id.to_string(), "ISIN:US0378331005");
+        assert_eq!(
+            SecurityIdentifier::from_str("WKN:865985"),
+            Err(IdentifierError::InvalidPrefix("WKN".to_string()))
+        );
+    }
+}
@@ -0,0 +1,647 @@
+/*!
+Provides common technical indicators calculated over a `PriceRangeSeries`.
+
+Each indicator takes a price range series, usually the result of one of the
+`FetchPriceRangeSeries` functions, and returns an `IndicatorSeries` with the
+same interval. Each value in the result is dated with the date of the price
+range it was calculated from; no values are returned for the leading price
+ranges where there is not yet enough data to calculate the indicator, so the
+result may be shorter than, or even empty for, a short series.
+
+| Indicator                   | Function          | Calculated from          |
+|-----------------------------|-------------------|--------------------------|
+| Simple Moving Average       | `sma`             | close                    |
+| Exponential Moving Average  | `ema`             | close                    |
+| Weighted Moving Average     | `wma`             | close                    |
+| Relative Strength Index     | `rsi`             | close                    |
+| MACD                        | `macd`            | close                    |
+| Bollinger Bands             | `bollinger_bands` | close                    |
+| Average True Range          | `atr`             | high, low, close         |
+| Stochastic Oscillator       | `stochastic`      | high, low, close         |
+| On-Balance Volume           | `obv`             | close, volume            |
+| VWAP                        | `vwap`            | high, low, close, volume |
+
+Prices are converted from `Money` to `f64` values in the major currency unit,
+a missing volume is treated as zero.
+
+## Example
+
+```rust,no_run
+use fin_model::indicators::{rsi, sma};
+use fin_model::quote::{FetchPriceRangeSeries, SeriesInterval};
+# use fin_model::prelude::*;
+# fn example(provider: &impl FetchPriceRangeSeries) -> RequestResult<()> {
+
+let series = provider.last("AAPL".parse().unwrap(), SeriesInterval::SixMonths)?;
+let average = sma(&series, 50);
+let strength = rsi(&series, 14);
+if let Some(latest) = strength.series.last() {
+    println!("RSI(14) on {} is {:.2}", latest.date, latest.data);
+}
+# Ok(())
+# }
+```
+*/
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::quote::{PriceRangeSeries, SeriesInterval};
+use crate::series::amount;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// A series of indicator values, each dated with the price range it was calculated from.
+pub type IndicatorSeries<T> = Series<SeriesInterval, Snapshot<T>>;
+
+/// The values of the Moving Average Convergence/Divergence indicator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MACD {
+    /// the fast EMA less the slow EMA
+    pub macd: f64,
+    /// the EMA of the MACD line
+    pub signal: f64,
+    /// the MACD line less the signal line
+    pub histogram: f64,
+}
+
+/// The values of the Bollinger Bands indicator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BollingerBands {
+    /// the middle band plus a multiple of the standard deviation
+    pub upper: f64,
+    /// the simple moving average
+    pub middle: f64,
+    /// the middle band less a multiple of the standard deviation
+    pub lower: f64,
+}
+
+/// The values of the Stochastic Oscillator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Stochastic {
+    /// the position of the close within the high-low range, as a percentage
+    pub k: f64,
+    /// the simple moving average of `k`
+    pub d: f64,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+/// The simple moving average of closing prices over `period` price ranges.
+pub fn sma(series: &PriceRangeSeries, period: usize) -> IndicatorSeries<f64> {
+    let values = sma_values(&closes(series), period);
+    indicator_series(series, period.saturating_sub(1), values)
+}
+
+/// The exponential moving average of closing prices over `period` price ranges,
+/// the first value is the simple moving average.
+pub fn ema(series: &PriceRangeSeries, period: usize) -> IndicatorSeries<f64> {
+    let values = ema_values(&closes(series), period);
+    indicator_series(series, period.saturating_sub(1), values)
+}
+
+/// The linearly weighted moving average of closing prices over `period` price
+/// ranges, the most recent price has weight `period` and the oldest weight 1.
+pub fn wma(series: &PriceRangeSeries, period: usize) -> IndicatorSeries<f64> {
+    let closes = closes(series);
+    let values = if period == 0 {
+        Vec::new()
+    } else {
+        let weights = (period * (period + 1) / 2) as f64;
+        closes
+            .windows(period)
+            .map(|window| {
+                window
+                    .iter()
+                    .enumerate()
+                    .map(|(i, close)| (i + 1) as f64 * close)
+                    .sum::<f64>()
+                    / weights
+            })
+            .collect()
+    };
+    indicator_series(series, period.saturating_sub(1), values)
+}
+
+/// Wilder's Relative Strength Index over `period` price ranges, the first
+/// value requires `period` price changes and so `period + 1` price ranges.
+pub fn rsi(series: &PriceRangeSeries, period: usize) -> IndicatorSeries<f64> {
+    let values = rsi_values(&closes(series), period);
+    indicator_series(series, period, values)
+}
+
+/// The Moving Average Convergence/Divergence of closing prices, usually with
+/// a `fast` period of 12, a `slow` period of 26, and a `signal` period of 9.
+/// Values are returned only once the signal line can be calculated.
+pub fn macd(
+    series: &PriceRangeSeries,
+    fast: usize,
+    slow: usize,
+    signal: usize,
+) -> IndicatorSeries<MACD> {
+    let closes = closes(series);
+    let mut values = Vec::new();
+    if fast > 0 && fast <= slow && signal > 0 {
+        let fast_ema = ema_values(&closes, fast);
+        let slow_ema = ema_values(&closes, slow);
+        let offset = slow - fast;
+        let line: Vec<f64> = slow_ema
+            .iter()
+            .enumerate()
+            .map(|(i, slow)| fast_ema[i + offset] - slow)
+            .collect();
+        values = ema_values(&line, signal)
+            .into_iter()
+            .enumerate()
+            .map(|(i, average)| {
+                let macd = line[i + signal - 1];
+                MACD {
+                    macd,
+                    signal: average,
+                    histogram: macd - average,
+                }
+            })
+            .collect();
+    }
+    indicator_series(series, (slow + signal).saturating_sub(2), values)
+}
+
+/// Bollinger Bands, the simple moving average of closing prices over `period`
+/// price ranges, and bands `multiplier` population standard deviations above
+/// and below it; usually a period of 20 and a multiplier of 2.
+pub fn bollinger_bands(
+    series: &PriceRangeSeries,
+    period: usize,
+    multiplier: f64,
+) -> IndicatorSeries<BollingerBands> {
+    let closes = closes(series);
+    let values = if period == 0 {
+        Vec::new()
+    } else {
+        closes
+            .windows(period)
+            .map(|window| {
+                let middle = mean(window);
+                let variance =
+                    window.iter().map(|c| (c - middle).powi(2)).sum::<f64>() / period as f64;
+                let width = multiplier * variance.sqrt();
+                BollingerBands {
+                    upper: middle + width,
+                    middle,
+                    lower: middle - width,
+                }
+            })
+            .collect()
+    };
+    indicator_series(series, period.saturating_sub(1), values)
+}
+
+/// Wilder's Average True Range over `period` price ranges; the true range of
+/// the first price range is its high less its low.
+pub fn atr(series: &PriceRangeSeries, period: usize) -> IndicatorSeries<f64> {
+    let ranges = ranges(series);
+    let true_ranges: Vec<f64> = ranges
+        .iter()
+        .enumerate()
+        .map(|(i, (high, low, _))| match i {
+            0 => high - low,
+            _ => {
+                let previous = ranges[i - 1].2;
+                (high - low)
+                    .max((high - previous).abs())
+                    .max((low - previous).abs())
+            }
+        })
+        .collect();
+    let mut values = Vec::new();
+    if period > 0 && true_ranges.len() >= period {
+        let n = period as f64;
+        let mut average = mean(&true_ranges[..period]);
+        values.push(average);
+        for true_range in &true_ranges[period..] {
+            average = (average * (n - 1.0) + true_range) / n;
+            values.push(average);
+        }
+    }
+    indicator_series(series, period.saturating_sub(1), values)
+}
+
+/// The Stochastic Oscillator; `%K` over `k_period` price ranges, and `%D`
+/// the simple moving average of `%K` over `d_period` values, usually periods
+/// of 14 and 3. Where the high and low over the period are equal `%K` is 50.
+pub fn stochastic(
+    series: &PriceRangeSeries,
+    k_period: usize,
+    d_period: usize,
+) -> IndicatorSeries<Stochastic> {
+    let ranges = ranges(series);
+    let mut values = Vec::new();
+    if k_period > 0 && d_period > 0 {
+        let k: Vec<f64> = ranges
+            .windows(k_period)
+            .map(|window| {
+                let high = window.iter().map(|r| r.0).fold(f64::MIN, f64::max);
+                let low = window.iter().map(|r| r.1).fold(f64::MAX, f64::min);
+                let close = window[k_period - 1].2;
+                if high > low {
+                    100.0 * (close - low) / (high - low)
+                } else {
+                    50.0
+                }
+            })
+            .collect();
+        values = sma_values(&k, d_period)
+            .into_iter()
+            .enumerate()
+            .map(|(i, d)| Stochastic {
+                k: k[i + d_period - 1],
+                d,
+            })
+            .collect();
+    }
+    indicator_series(series, (k_period + d_period).saturating_sub(2), values)
+}
+
+/// On-Balance Volume, the running total of volume added on an up close and
+/// subtracted on a down close; the first value is zero.
+pub fn obv(series: &PriceRangeSeries) -> IndicatorSeries<f64> {
+    let closes = closes(series);
+    let volumes = volumes(series);
+    let mut total = 0.0;
+    let values = closes
+        .iter()
+        .enumerate()
+        .map(|(i, close)| {
+            if i > 0 {
+                if *close > closes[i - 1] {
+                    total += volumes[i];
+                } else if *close < closes[i - 1] {
+                    total -= volumes[i];
+                }
+            }
+            total
+        })
+        .collect();
+    indicator_series(series, 0, values)
+}
+
+/// The Volume Weighted Average Price, using the typical price of each price
+/// range, `(high + low + close) / 3`. The totals are reset at the start of
+/// each day and so for an intra-day series this is the VWAP of the trading
+/// session; where no volume has been traded in the day the value is the
+/// typical price.
+pub fn vwap(series: &PriceRangeSeries) -> IndicatorSeries<f64> {
+    let ranges = ranges(series);
+    let volumes = volumes(series);
+    let mut day = None;
+    let mut total_value = 0.0;
+    let mut total_volume = 0.0;
+    let values = series
+        .series
+        .iter()
+        .enumerate()
+        .map(|(i, snapshot)| {
+            if day != Some(snapshot.date.date()) {
+                day = Some(snapshot.date.date());
+                total_value = 0.0;
+                total_volume = 0.0;
+            }
+            let (high, low, close) = ranges[i];
+            let typical = (high + low + close) / 3.0;
+            total_value += typical * volumes[i];
+            total_volume += volumes[i];
+            if total_volume > 0.0 {
+                total_value / total_volume
+            } else {
+                typical
+            }
+        })
+        .collect();
+    indicator_series(series, 0, values)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn closes(series: &PriceRangeSeries) -> Vec<f64> {
+    series.closes().map(|close| amount(&close)).collect()
+}
+
+fn volumes(series: &PriceRangeSeries) -> Vec<f64> {
+    series
+        .volumes()
+        .map(|volume| volume.unwrap_or_default() as f64)
+        .collect()
+}
+
+/// The high, low, and close for each price range.
+fn ranges(series: &PriceRangeSeries) -> Vec<(f64, f64, f64)> {
+    series
+        .values()
+        .map(|range| {
+            (
+                amount(&range.high),
+                amount(&range.low),
+                amount(&range.close),
+            )
+        })
+        .collect()
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn sma_values(values: &[f64], period: usize) -> Vec<f64> {
+    if period == 0 {
+        return Vec::new();
+    }
+    values.windows(period).map(mean).collect()
+}
+
+fn ema_values(values: &[f64], period: usize) -> Vec<f64> {
+    if period == 0 || values.len() < period {
+        return Vec::new();
+    }
+    let alpha = 2.0 / (period as f64 + 1.0);
+    let mut average = mean(&values[..period]);
+    let mut averages = vec![average];
+    for value in &values[period..] {
+        average += alpha * (value - average);
+        averages.push(average);
+    }
+    averages
+}
+
+fn rsi_values(closes: &[f64], period: usize) -> Vec<f64> {
+    let mut values = Vec::new();
+    if period > 0 && closes.len() > period {
+        let changes: Vec<f64> = closes.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        let n = period as f64;
+        let mut gain = changes[..period].iter().map(|c| c.max(0.0)).sum::<f64>() / n;
+        let mut loss = changes[..period].iter().map(|c| (-c).max(0.0)).sum::<f64>() / n;
+        values.push(relative_strength(gain, loss));
+        for change in &changes[period..] {
+            gain = (gain * (n - 1.0) + change.max(0.0)) / n;
+            loss = (loss * (n - 1.0) + (-change).max(0.0)) / n;
+            values.push(relative_strength(gain, loss));
+        }
+    }
+    values
+}
+
+fn relative_strength(gain: f64, loss: f64) -> f64 {
+    if loss == 0.0 {
+        100.0
+    } else {
+        100.0 - 100.0 / (1.0 + gain / loss)
+    }
+}
+
+/// Date each value with the price range at the same position, after skipping
+/// the first `skip` price ranges.
+fn indicator_series<T>(
+    series: &PriceRangeSeries,
+    skip: usize,
+    values: Vec<T>,
+) -> IndicatorSeries<T> {
+    Series {
+        interval: series.interval,
+        intra_interval: series.intra_interval,
+        series: series
+            .dates()
+            .skip(skip)
+            .zip(values)
+            .map(|(date, data)| Snapshot { date, data })
+            .collect(),
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quote::PriceRange;
+    use steel_cent::currency::USD;
+
+    fn cents(price: f64) -> Money {
+        Money::of_minor(USD, (price * 100.0).round() as i32)
+    }
+
+    /// A daily series of (high, low, close, volume) values, open is not used
+    /// by any indicator.
+    fn daily(values: &[(f64, f64, f64, u64)]) -> PriceRangeSeries {
+        let start = Date::from_ymd(2019, 1, 1).and_hms(0, 0, 0);
+        Series {
+            interval: SeriesInterval::OneYear,
+            intra_interval: None,
+            series: values
+                .iter()
+                .enumerate()
+                .map(|(i, (high, low, close, volume))| Snapshot {
+                    date: start + chrono::Duration::days(i as i64),
+                    data: PriceRange {
+                        open: cents(*close),
+                        close: cents(*close),
+                        high: cents(*high),
+                        low: cents(*low),
+                        volume: Some(*volume),
+                    },
+                })
+                .collect(),
+        }
+    }
+
+    fn closing(closes: &[f64]) -> PriceRangeSeries {
+        let values: Vec<(f64, f64, f64, u64)> = closes
+            .iter()
+            .map(|close| (*close, *close, *close, 0))
+            .collect();
+        daily(&values)
+    }
+
+    fn assert_values(actual: &[f64], expected: &[f64], tolerance: f64) {
+        assert_eq!(actual.len(), expected.len());
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert!(
+                (actual - expected).abs() <= tolerance,
+                "{} != {}",
+                actual,
+                expected
+            );
+        }
+    }
+
+    fn data<T: Copy>(series: &IndicatorSeries<T>) -> Vec<T> {
+        series.values().cloned().collect()
+    }
+
+    // The 10-day moving average example from the StockCharts ChartSchool
+    // article "Moving Averages - Simple and Exponential"; the published
+    // prices have more decimal places than USD and so the calculations are
+    // tested on the values directly.
+    const MOVING_AVERAGE_CLOSES: &[f64] = &[
+        22.2734, 22.1940, 22.0847, 22.1741, 22.1840, 22.1344, 22.2337, 22.4323, 22.2436, 22.2933,
+        22.1542, 22.3926, 22.3816, 22.6109, 23.3558, 24.0519, 23.7530, 23.8324, 23.9516, 23.6338,
+        23.8225, 23.8722, 23.6537, 23.1870, 23.0976, 23.3260, 22.6805, 23.0976, 22.4025, 22.1725,
+    ];
+
+    // The 14-day RSI example from the StockCharts ChartSchool article
+    // "Relative Strength Index (RSI)".
+    const RSI_CLOSES: &[f64] = &[
+        44.3389, 44.0902, 44.1497, 43.6124, 44.2778, 44.8264, 45.0955, 45.4245, 45.8433, 46.0826,
+        45.8931, 46.0328, 45.6140, 46.2820, 46.2820, 46.0028, 46.0328, 46.4116, 46.2222, 45.6439,
+        46.2122, 46.2521, 45.7137, 46.4515, 45.7835, 45.3548, 44.0288, 44.1783, 44.2181, 44.5672,
+        43.4205, 42.6628, 43.1314,
+    ];
+
+    #[test]
+    fn test_sma() {
+        assert_values(
+            &sma_values(MOVING_AVERAGE_CLOSES, 10),
+            &[
+                22.22, 22.21, 22.23, 22.26, 22.31, 22.42, 22.61, 22.77, 22.91, 23.08, 23.21, 23.38,
+                23.53, 23.65, 23.71, 23.69, 23.61, 23.51, 23.43, 23.28, 23.13,
+            ],
+            0.005,
+        );
+
+        let series = closing(&[1.0, 2.0, 3.0, 4.0]);
+        let result = sma(&series, 2);
+        assert_eq!(result.series[0].date, series.series[1].date);
+        assert_values(&data(&result), &[1.5, 2.5, 3.5], 1e-9);
+    }
+
+    #[test]
+    fn test_ema() {
+        assert_values(
+            &ema_values(MOVING_AVERAGE_CLOSES, 10),
+            &[
+                22.22, 22.21, 22.24, 22.27, 22.33, 22.52, 22.80, 22.97, 23.13, 23.28, 23.34, 23.43,
+                23.51, 23.54, 23.47, 23.40, 23.39, 23.26, 23.23, 23.08, 22.92,
+            ],
+            0.005,
+        );
+    }
+
+    #[test]
+    fn test_rsi() {
+        assert_values(
+            &rsi_values(RSI_CLOSES, 14),
+            &[
+                70.53, 66.32, 66.55, 69.41, 66.36, 57.97, 62.93, 63.26, 56.06, 62.38, 54.71, 50.42,
+                39.99, 41.46, 41.87, 45.46, 37.30, 33.08, 37.77,
+            ],
+            0.005,
+        );
+
+        let series = closing(&[1.0, 2.0, 1.5, 2.5]);
+        let result = rsi(&series, 2);
+        assert_eq!(result.series[0].date, series.series[2].date);
+        assert_values(&data(&result), &[200.0 / 3.0, 600.0 / 7.0], 1e-9);
+    }
+
+    #[test]
+    fn test_wma() {
+        let result = wma(&closing(&[1.0, 2.0, 3.0, 4.0, 5.0]), 3);
+        assert_values(&data(&result), &[14.0 / 6.0, 20.0 / 6.0, 26.0 / 6.0], 1e-9);
+        assert!(wma(&closing(&[1.0, 2.0]), 3).is_empty());
+    }
+
+    #[test]
+    fn test_macd() {
+        let series = closing(&RSI_CLOSES[..20]);
+        let result = macd(&series, 3, 6, 4);
+        let fast = data(&ema(&series, 3));
+        let slow = data(&ema(&series, 6));
+        // the MACD line for each value is the difference of the EMAs.
+        assert_eq!(result.len(), 12);
+        assert_eq!(result.series[0].date, series.series[8].date);
+        for (i, value) in data(&result).iter().enumerate() {
+            assert!((value.macd - (fast[i + 6] - slow[i + 3])).abs() < 1e-9);
+            assert!((value.histogram - (value.macd - value.signal)).abs() < 1e-9);
+        }
+        let line: Vec<f64> = (0..4).map(|i| fast[i + 3] - slow[i]).collect();
+        assert!((result.series[0].data.signal - mean(&line)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bollinger_bands() {
+        let result = bollinger_bands(&closing(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]), 8, 2.0);
+        assert_eq!(
+            data(&result),
+            vec![BollingerBands {
+                upper: 9.0,
+                middle: 5.0,
+                lower: 1.0,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_atr() {
+        let series = daily(&[
+            (10.0, 9.0, 9.5, 0),
+            (11.0, 10.0, 10.5, 0),
+            (10.5, 8.0, 8.5, 0),
+            (9.0, 8.75, 9.0, 0),
+        ]);
+        // true ranges are 1.0, 1.5, 2.5, and 0.5.
+        let result = atr(&series, 3);
+        assert_values(
+            &data(&result),
+            &[5.0 / 3.0, (5.0 / 3.0 * 2.0 + 0.5) / 3.0],
+            1e-9,
+        );
+    }
+
+    #[test]
+    fn test_stochastic() {
+        let series = daily(&[
+            (10.0, 8.0, 9.0, 0),
+            (12.0, 9.0, 11.0, 0),
+            (11.0, 10.0, 10.0, 0),
+            (13.0, 10.0, 13.0, 0),
+        ]);
+        let result = stochastic(&series, 3, 2);
+        // %K is 50.0 then 100.0
+        assert_eq!(data(&result), vec![Stochastic { k: 100.0, d: 75.0 }]);
+        assert_eq!(result.series[0].date, series.series[3].date);
+    }
+
+    #[test]
+    fn test_obv_and_vwap() {
+        let series = daily(&[
+            (10.0, 8.0, 9.0, 100),
+            (12.0, 9.0, 12.0, 200),
+            (11.0, 10.0, 10.5, 150),
+            (13.0, 10.0, 10.5, 50),
+        ]);
+        assert_values(&data(&obv(&series)), &[0.0, 200.0, 50.0, 50.0], 1e-9);
+
+        let mut intraday = series.clone();
+        for snapshot in intraday.series.iter_mut() {
+            snapshot.date = Date::from_ymd(2019, 1, 1).and_hms(10, 0, 0);
+        }
+        let result = vwap(&intraday);
+        assert_values(
+            &data(&result)[..2],
+            &[9.0, (9.0 * 100.0 + 11.0 * 200.0) / 300.0],
+            1e-9,
+        );
+        // a daily series resets each day, so each value is the typical price.
+        assert_values(&data(&vwap(&series)), &[9.0, 11.0, 10.5, 33.5 / 3.0], 1e-9);
+    }
+}
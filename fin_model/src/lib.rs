@@ -17,12 +17,29 @@ price quotes, analyst data, or company information. In the model
 we use the term _request trait_ to indicate a trait that contains
 functions that make a request for data and which use the common
 `RequestResult` response.
+
+## Features
+
+* `serde` derives `Serialize` and `Deserialize` for all model types; `Money`
+  values are written as a currency code and amount, `"USD 12.34"`, and
+  `FinancialPeriod` values in their `Display` form, `"Q2 2019"`.
+* `cache` enables `cache::CachingProvider`, a decorator that caches the
+  responses of any provider in memory; this implies `serde`.
+* `disk-cache` enables `cache::DiskCache`, a cache backend that stores
+  values as JSON files; this implies `cache`.
+* `mock` enables `mock::MockProvider`, an in-memory provider for testing
+  clients, with data built programmatically or loaded from JSON fixtures;
+  this implies `serde`.
 */
 
 extern crate chrono;
 #[macro_use]
 extern crate lazy_static;
 extern crate regex;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(any(feature = "disk-cache", feature = "mock"))]
+extern crate serde_json;
 extern crate steel_cent;
 
 // ------------------------------------------------------------------------------------------------
@@ -33,16 +50,36 @@ pub mod prelude;
 
 pub mod analysis;
 
+#[cfg(feature = "cache")]
+pub mod cache;
+
 pub mod classification;
 
 pub mod company;
 
+pub mod composite;
+
+pub mod corporate;
+
+pub mod identifier;
+
+pub mod indicators;
+
 pub mod market;
 
+#[cfg(feature = "mock")]
+pub mod mock;
+
 pub mod news;
 
+pub mod options;
+
+pub mod pricing;
+
 pub mod quote;
 
+pub mod ratios;
+
 pub mod registry;
 
 pub mod reporting;
@@ -51,4 +88,15 @@ pub mod provider;
 
 pub mod request;
 
+pub mod returns;
+
+pub mod series;
+
 pub mod symbol;
+
+// ------------------------------------------------------------------------------------------------
+// Private Modules
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "serde")]
+mod serialize;
@@ -10,6 +10,9 @@ The market registry is an implementation of the
 lookup of MIC identifiers.
 */
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::prelude::*;
 
 // ------------------------------------------------------------------------------------------------
@@ -19,6 +22,7 @@ use crate::prelude::*;
 /// The current status of a market, the standard does not delete or
 /// reuse identifiers, it only marks them as deleted or not operational.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MarketStatus {
     Active,
     Deleted,
@@ -27,6 +31,7 @@ pub enum MarketStatus {
 
 /// Details of a market contained in the standard's Excel-based registry.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Market {
     /// the MIC code, or identifier for the market
     pub mic: String,
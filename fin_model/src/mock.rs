@@ -0,0 +1,870 @@
+/*!
+Provides an in-memory mock provider for testing clients of the _request traits_.
+
+The `MockProvider` implements all of the _request traits_, answering each
+request from a `MockData` value. The data may be built programmatically, or
+loaded from a JSON fixture in the form produced by the `serde` feature.
+Failures may be scripted per operation, either for the next call(s), with
+`MockProvider::fail_next`, or for all calls, with `MockProvider::fail_always`.
+The number of calls to each operation is recorded and available from
+`MockProvider::calls`.
+
+A request for a symbol, or category, with no data returns an error of kind
+`RequestErrorKind::BadSymbolError`. This module requires the `mock` feature.
+
+## Example
+
+```rust
+use fin_model::mock::{MockData, MockProvider};
+use fin_model::prelude::*;
+use fin_model::provider::Operation;
+use fin_model::quote::FetchPriceQuote;
+use steel_cent::currency::USD;
+
+let symbol: Symbol = "AAPL".parse().unwrap();
+let mut data = MockData::default();
+data.add_price(symbol.clone(), Money::of_major_minor(USD, 201, 55));
+
+let provider = MockProvider::with_data(data);
+provider.fail_next(Operation::LatestPriceOnly, RequestErrorKind::RequestThrottled);
+
+assert!(provider.latest_price_only(symbol.clone()).is_err());
+assert_eq!(
+    provider.latest_price_only(symbol).unwrap(),
+    Money::of_major_minor(USD, 201, 55)
+);
+assert_eq!(provider.calls(Operation::LatestPriceOnly), 2);
+```
+
+A JSON fixture contains any of the fields of `MockData`, each a map from
+symbol (or category) to values.
+
+```json
+{
+  "peers": { "AAPL": ["MSFT", "GOOG"] },
+  "news": {
+    "AAPL": [{
+      "date": "2019-06-28T14:30:00",
+      "data": {
+        "headline": "Example headline",
+        "source": "Example News",
+        "url": "https://example.com/story",
+        "summary": "An example story.",
+        "image_url": null,
+        "language": "en",
+        "categories": null,
+        "sub_categories": null
+      }
+    }]
+  }
+}
+```
+*/
+
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::*;
+use crate::company::*;
+use crate::corporate::*;
+use crate::news::*;
+use crate::options::*;
+use crate::prelude::*;
+use crate::provider::{Capabilities, Operation, RequestTrait};
+use crate::quote::*;
+use crate::reporting::FinancialPeriod;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The data returned by a `MockProvider`, keyed by symbol unless noted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MockData {
+    /// used by `FetchPriceQuote::real_time`, `FetchPriceQuote::latest_price_only`,
+    /// and `FetchPriceQuoteBatch::real_time`
+    pub quotes: HashMap<Symbol, Quote>,
+    /// used by `FetchPriceQuote::delayed` and `FetchPriceQuoteBatch::delayed`
+    pub delayed_quotes: HashMap<Symbol, DelayedQuote>,
+    /// used by `FetchPriceRangeSeries::intra_day`
+    pub intra_day: HashMap<Symbol, PriceRangeSeries>,
+    /// used by the remaining `FetchPriceRangeSeries` operations
+    pub series: HashMap<Symbol, PriceRangeSeries>,
+    /// used by `FetchNews`
+    pub news: HashMap<Symbol, NewsFeed>,
+    /// used by `FetchCategoryNews`, keyed by category
+    pub category_news: HashMap<String, NewsFeed>,
+    pub peers: HashMap<Symbol, Symbols>,
+    pub target_prices: HashMap<Symbol, Snapshot<PriceTarget>>,
+    pub consensus_ratings: HashMap<Symbol, Vec<Bounded<Ratings>>>,
+    pub consensus_eps: HashMap<Symbol, Vec<EPSConsensus>>,
+    /// used by `FetchEarnings`, most recent first
+    pub earnings: HashMap<Symbol, Vec<EarningsReport>>,
+    pub about: HashMap<Symbol, About>,
+    pub filings: HashMap<Symbol, RegulatoryFilings>,
+    pub income_statements: HashMap<Symbol, Vec<Snapshot<IncomeStatement>>>,
+    pub balance_sheets: HashMap<Symbol, Vec<Snapshot<BalanceSheet>>>,
+    pub cash_flow_statements: HashMap<Symbol, Vec<Snapshot<CashFlowStatement>>>,
+    pub statistics: HashMap<Symbol, Statistics>,
+    /// used by `FetchCorporateActions`, in ex-date order
+    pub corporate_actions: HashMap<Symbol, Vec<CorporateAction>>,
+    /// used by `FetchOptionChain`, one chain for each expiration
+    pub option_chains: HashMap<Symbol, Vec<Snapshot<OptionChain>>>,
+}
+
+/// A provider answering all requests from `MockData`.
+#[derive(Debug)]
+pub struct MockProvider {
+    data: MockData,
+    default_currency: String,
+    script: Mutex<Script>,
+    calls: Mutex<HashMap<Operation, usize>>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+struct Script {
+    next: HashMap<Operation, VecDeque<RequestErrorKind>>,
+    always: HashMap<Operation, RequestErrorKind>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl MockData {
+    /// Load data from a JSON string.
+    pub fn from_json(json: &str) -> RequestResult<Self> {
+        serde_json::from_str(json).map_err(|err| {
+            RequestError::new(RequestErrorKind::ConfigurationError(
+                "could not parse mock data".to_string(),
+            ))
+            .with_source(err)
+        })
+    }
+
+    /// Load data from the JSON file at `path`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> RequestResult<Self> {
+        match fs::read_to_string(path.as_ref()) {
+            Ok(json) => MockData::from_json(&json),
+            Err(err) => Err(
+                RequestError::new(RequestErrorKind::ConfigurationError(format!(
+                    "could not read mock data file {:?}",
+                    path.as_ref()
+                )))
+                .with_source(err),
+            ),
+        }
+    }
+
+    /// Add a simple quote, and delayed quote, for `symbol` with the latest
+    /// `price`, dated now; any existing quotes are replaced.
+    pub fn add_price(&mut self, symbol: Symbol, price: Money) -> &mut Self {
+        let date = Utc::now().naive_utc();
+        let latest = QuotePrice {
+            price,
+            change: None,
+            percentage: None,
+        };
+        self.quotes.insert(
+            symbol.clone(),
+            Snapshot {
+                date,
+                data: QuotePriceFull {
+                    latest: latest.clone(),
+                    range: None,
+                    trade_size: None,
+                    previous_close_date: None,
+                    extended: None,
+                },
+            },
+        );
+        self.delayed_quotes.insert(
+            symbol,
+            Snapshot {
+                date,
+                data: QuotePriceDelayed {
+                    latest,
+                    delayed_by: 15,
+                    high: price,
+                    low: price,
+                    trade_size: None,
+                    volume: None,
+                    previous_close_date: None,
+                },
+            },
+        );
+        self
+    }
+}
+
+impl MockProvider {
+    /// Construct a provider answering requests from `data`.
+    pub fn with_data(data: MockData) -> Self {
+        MockProvider {
+            data,
+            default_currency: "USD".to_string(),
+            script: Mutex::new(Script::default()),
+            calls: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Construct a provider answering requests from the JSON fixture at `path`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> RequestResult<Self> {
+        Ok(MockProvider::with_data(MockData::from_file(path)?))
+    }
+
+    /// Set the default currency code returned by `Provider::get_default_currency`.
+    pub fn set_default_currency(&mut self, code: &str) -> &mut Self {
+        self.default_currency = code.to_string();
+        self
+    }
+
+    /// The data used to answer requests.
+    pub fn data(&self) -> &MockData {
+        &self.data
+    }
+
+    /// The data used to answer requests, for modification.
+    pub fn data_mut(&mut self) -> &mut MockData {
+        &mut self.data
+    }
+
+    /// Fail the next call to `operation` with an error of `kind`; calling this
+    /// more than once queues failures for successive calls.
+    pub fn fail_next(&self, operation: Operation, kind: RequestErrorKind) -> &Self {
+        let mut script = self.script.lock().unwrap();
+        script.next.entry(operation).or_default().push_back(kind);
+        self
+    }
+
+    /// Fail all calls to `operation` with an error of `kind`, until cleared.
+    pub fn fail_always(&self, operation: Operation, kind: RequestErrorKind) -> &Self {
+        let mut script = self.script.lock().unwrap();
+        script.always.insert(operation, kind);
+        self
+    }
+
+    /// Remove all scripted failures.
+    pub fn clear_failures(&self) -> &Self {
+        let mut script = self.script.lock().unwrap();
+        script.next.clear();
+        script.always.clear();
+        self
+    }
+
+    /// The number of calls made to `operation`, including failed calls.
+    pub fn calls(&self, operation: Operation) -> usize {
+        match self.calls.lock().unwrap().get(&operation) {
+            Some(count) => *count,
+            None => 0,
+        }
+    }
+
+    fn respond<T, F>(&self, operation: Operation, f: F) -> RequestResult<T>
+    where
+        F: FnOnce(&MockData) -> Option<T>,
+    {
+        *self.calls.lock().unwrap().entry(operation).or_insert(0) += 1;
+        {
+            let mut script = self.script.lock().unwrap();
+            if let Some(kind) = script.next.get_mut(&operation).and_then(|q| q.pop_front()) {
+                return Err(kind.into());
+            }
+            if let Some(kind) = script.always.get(&operation) {
+                return Err(kind.clone().into());
+            }
+        }
+        match f(&self.data) {
+            Some(value) => Ok(value),
+            None => Err(RequestErrorKind::BadSymbolError(format!(
+                "no mock data for {:?}",
+                operation
+            ))
+            .into()),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Trait Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Provider for MockProvider {
+    fn new() -> RequestResult<Self> {
+        Ok(MockProvider::with_data(MockData::default()))
+    }
+
+    fn attribution(&self) -> String {
+        "Mock data".to_string()
+    }
+
+    fn url(&self) -> String {
+        "https://github.com/johnstonskj/rust-financial".to_string()
+    }
+
+    fn get_default_currency(&self) -> &String {
+        &self.default_currency
+    }
+
+    /// The mock provider supports all operations.
+    fn capabilities(&self) -> Capabilities {
+        let mut capabilities = Capabilities::default();
+        for request_trait in &[
+            RequestTrait::Peers,
+            RequestTrait::AnalystRecommendations,
+            RequestTrait::FetchEarnings,
+            RequestTrait::FetchCompanyInformation,
+            RequestTrait::FetchCompanyFinancials,
+            RequestTrait::FetchCompanyStatistics,
+            RequestTrait::FetchCorporateActions,
+            RequestTrait::FetchNews,
+            RequestTrait::FetchCategoryNews,
+            RequestTrait::FetchOptionChain,
+            RequestTrait::FetchPriceQuote,
+            RequestTrait::FetchPriceQuoteBatch,
+            RequestTrait::FetchPriceRangeSeries,
+        ] {
+            capabilities.add_trait(*request_trait);
+        }
+        capabilities.real_time = true;
+        capabilities
+            .currencies
+            .insert(self.default_currency.clone());
+        capabilities
+    }
+
+    fn finish(&self) {}
+}
+
+impl Peers for MockProvider {
+    fn peers(&self, for_symbol: Symbol) -> RequestResult<Symbols> {
+        self.respond(Operation::Peers, |data| {
+            data.peers.get(&for_symbol).cloned()
+        })
+    }
+}
+
+impl AnalystRecommendations for MockProvider {
+    fn target_price(&self, for_symbol: Symbol) -> RequestResult<Snapshot<PriceTarget>> {
+        self.respond(Operation::TargetPrice, |data| {
+            data.target_prices.get(&for_symbol).cloned()
+        })
+    }
+
+    fn consensus_rating(&self, for_symbol: Symbol) -> RequestResult<Vec<Bounded<Ratings>>> {
+        self.respond(Operation::ConsensusRating, |data| {
+            data.consensus_ratings.get(&for_symbol).cloned()
+        })
+    }
+
+    fn consensus_eps(&self, for_symbol: Symbol) -> RequestResult<Vec<EPSConsensus>> {
+        self.respond(Operation::ConsensusEPS, |data| {
+            data.consensus_eps.get(&for_symbol).cloned()
+        })
+    }
+}
+
+impl FetchEarnings for MockProvider {
+    /// Returns at most `last` reports.
+    fn earnings(&self, for_symbol: Symbol, last: usize) -> RequestResult<Vec<EarningsReport>> {
+        self.respond(Operation::Earnings, |data| {
+            data.earnings
+                .get(&for_symbol)
+                .map(|reports| reports.iter().take(last).cloned().collect())
+        })
+    }
+}
+
+impl FetchCompanyInformation for MockProvider {
+    fn about(&self, for_symbol: Symbol) -> RequestResult<About> {
+        self.respond(Operation::About, |data| {
+            data.about.get(&for_symbol).cloned()
+        })
+    }
+
+    /// Returns the filings on or after `start_date` and of `form_type`, if given.
+    fn filings(
+        &self,
+        for_symbol: Symbol,
+        start_date: Option<Date>,
+        form_type: Option<String>,
+    ) -> RequestResult<RegulatoryFilings> {
+        self.respond(Operation::Filings, |data| {
+            data.filings.get(&for_symbol).map(|filings| {
+                filings
+                    .iter()
+                    .filter(|f| on_or_after(f.date.date(), start_date))
+                    .filter(|f| {
+                        form_type.is_none() || form_type.as_ref() == Some(&f.data.form_type)
+                    })
+                    .cloned()
+                    .collect()
+            })
+        })
+    }
+}
+
+impl FetchCompanyFinancials for MockProvider {
+    /// Returns the first `last` statements; `period` is ignored.
+    fn reported_income(
+        &self,
+        for_symbol: Symbol,
+        last: u8,
+        _period: ReportingPeriod,
+    ) -> RequestResult<Vec<Snapshot<IncomeStatement>>> {
+        self.respond(Operation::ReportedIncome, |data| {
+            data.income_statements
+                .get(&for_symbol)
+                .map(|statements| statements.iter().take(last as usize).cloned().collect())
+        })
+    }
+
+    /// Returns the first `last` statements; `period` is ignored.
+    fn reported_balance_sheet(
+        &self,
+        for_symbol: Symbol,
+        last: u8,
+        _period: ReportingPeriod,
+    ) -> RequestResult<Vec<Snapshot<BalanceSheet>>> {
+        self.respond(Operation::ReportedBalanceSheet, |data| {
+            data.balance_sheets
+                .get(&for_symbol)
+                .map(|statements| statements.iter().take(last as usize).cloned().collect())
+        })
+    }
+
+    /// Returns the first `last` statements; `period` is ignored.
+    fn reported_cash_flow(
+        &self,
+        for_symbol: Symbol,
+        last: u8,
+        _period: ReportingPeriod,
+    ) -> RequestResult<Vec<Snapshot<CashFlowStatement>>> {
+        self.respond(Operation::ReportedCashFlow, |data| {
+            data.cash_flow_statements
+                .get(&for_symbol)
+                .map(|statements| statements.iter().take(last as usize).cloned().collect())
+        })
+    }
+}
+
+impl FetchCompanyStatistics for MockProvider {
+    fn core_statistics(&self, for_symbol: Symbol) -> RequestResult<Statistics> {
+        self.respond(Operation::CoreStatistics, |data| {
+            data.statistics.get(&for_symbol).cloned()
+        })
+    }
+}
+
+impl FetchCorporateActions for MockProvider {
+    /// Returns all dividends; `interval` is ignored.
+    fn dividends(
+        &self,
+        for_symbol: Symbol,
+        _interval: SeriesInterval,
+    ) -> RequestResult<Vec<Dividend>> {
+        self.respond(Operation::Dividends, |data| {
+            data.corporate_actions.get(&for_symbol).map(|actions| {
+                actions
+                    .iter()
+                    .filter_map(|action| match action {
+                        CorporateAction::Dividend(dividend) => Some(dividend.clone()),
+                        _ => None,
+                    })
+                    .collect()
+            })
+        })
+    }
+
+    /// Returns all splits; `interval` is ignored.
+    fn splits(&self, for_symbol: Symbol, _interval: SeriesInterval) -> RequestResult<Vec<Split>> {
+        self.respond(Operation::Splits, |data| {
+            data.corporate_actions.get(&for_symbol).map(|actions| {
+                actions
+                    .iter()
+                    .filter_map(|action| match action {
+                        CorporateAction::Split(split) => Some(split.clone()),
+                        _ => None,
+                    })
+                    .collect()
+            })
+        })
+    }
+
+    /// Returns all corporate actions; `interval` is ignored.
+    fn corporate_actions(
+        &self,
+        for_symbol: Symbol,
+        _interval: SeriesInterval,
+    ) -> RequestResult<Vec<CorporateAction>> {
+        self.respond(Operation::CorporateActions, |data| {
+            data.corporate_actions.get(&for_symbol).cloned()
+        })
+    }
+}
+
+impl FetchNews for MockProvider {
+    fn latest_news(&self, for_symbol: Symbol, max_items: usize) -> RequestResult<NewsFeed> {
+        self.respond(Operation::LatestNews, |data| {
+            data.news
+                .get(&for_symbol)
+                .map(|feed| latest(feed, None, max_items))
+        })
+    }
+
+    fn news_from(
+        &self,
+        for_symbol: Symbol,
+        start_date: Date,
+        max_items: usize,
+    ) -> RequestResult<NewsFeed> {
+        self.respond(Operation::NewsFrom, |data| {
+            data.news
+                .get(&for_symbol)
+                .map(|feed| latest(feed, Some(start_date), max_items))
+        })
+    }
+}
+
+impl FetchCategoryNews for MockProvider {
+    fn latest_news(
+        &self,
+        category: String,
+        sub_category: Option<String>,
+        max_items: usize,
+    ) -> RequestResult<NewsFeed> {
+        self.respond(Operation::LatestCategoryNews, |data| {
+            data.category_news
+                .get(&category)
+                .map(|feed| latest(&in_sub_category(feed, &sub_category), None, max_items))
+        })
+    }
+
+    fn news_from(
+        &self,
+        category: String,
+        sub_category: Option<String>,
+        start_date: Date,
+        max_items: usize,
+    ) -> RequestResult<NewsFeed> {
+        self.respond(Operation::CategoryNewsFrom, |data| {
+            data.category_news.get(&category).map(|feed| {
+                latest(
+                    &in_sub_category(feed, &sub_category),
+                    Some(start_date),
+                    max_items,
+                )
+            })
+        })
+    }
+}
+
+impl FetchOptionChain for MockProvider {
+    /// Returns the expiration of each chain, in date order.
+    fn expirations(&self, for_symbol: Symbol) -> RequestResult<Vec<Date>> {
+        self.respond(Operation::OptionExpirations, |data| {
+            data.option_chains.get(&for_symbol).map(|chains| {
+                let mut expirations: Vec<Date> =
+                    chains.iter().map(|chain| chain.data.expiration).collect();
+                expirations.sort();
+                expirations.dedup();
+                expirations
+            })
+        })
+    }
+
+    fn option_chain(
+        &self,
+        for_symbol: Symbol,
+        expiration: Date,
+    ) -> RequestResult<Snapshot<OptionChain>> {
+        self.respond(Operation::OptionChain, |data| {
+            data.option_chains.get(&for_symbol).and_then(|chains| {
+                chains
+                    .iter()
+                    .find(|chain| chain.data.expiration == expiration)
+                    .cloned()
+            })
+        })
+    }
+}
+
+impl FetchPriceQuote for MockProvider {
+    fn latest_price_only(&self, for_symbol: Symbol) -> RequestResult<Money> {
+        self.respond(Operation::LatestPriceOnly, |data| {
+            data.quotes
+                .get(&for_symbol)
+                .map(|quote| quote.data.latest.price)
+        })
+    }
+
+    fn real_time(&self, for_symbol: Symbol) -> RequestResult<Quote> {
+        self.respond(Operation::RealTime, |data| {
+            data.quotes.get(&for_symbol).cloned()
+        })
+    }
+
+    fn delayed(&self, for_symbol: Symbol) -> RequestResult<DelayedQuote> {
+        self.respond(Operation::Delayed, |data| {
+            data.delayed_quotes.get(&for_symbol).cloned()
+        })
+    }
+}
+
+impl FetchPriceQuoteBatch for MockProvider {
+    /// Returns quotes for the symbols with data, other symbols are omitted.
+    fn real_time(&self, for_symbols: Symbols) -> RequestResult<HashMap<Symbol, Quote>> {
+        self.respond(Operation::RealTimeBatch, |data| {
+            Some(select(&data.quotes, &for_symbols))
+        })
+    }
+
+    /// Returns quotes for the symbols with data, other symbols are omitted.
+    fn delayed(&self, for_symbols: Symbols) -> RequestResult<HashMap<Symbol, DelayedQuote>> {
+        self.respond(Operation::DelayedBatch, |data| {
+            Some(select(&data.delayed_quotes, &for_symbols))
+        })
+    }
+}
+
+impl FetchPriceRangeSeries for MockProvider {
+    /// Returns `None` if there is no intra-day data for `for_symbol`.
+    fn intra_day(
+        &self,
+        for_symbol: Symbol,
+        _interval_minutes: u8,
+    ) -> RequestResult<Option<PriceRangeSeries>> {
+        self.respond(Operation::IntraDay, |data| {
+            Some(data.intra_day.get(&for_symbol).cloned())
+        })
+    }
+
+    /// Returns the complete series, with the requested `interval`.
+    fn last(
+        &self,
+        for_symbol: Symbol,
+        interval: SeriesInterval,
+    ) -> RequestResult<PriceRangeSeries> {
+        self.respond(Operation::Last, |data| {
+            data.series
+                .get(&for_symbol)
+                .map(|series| filter_series(series, interval, |_| true))
+        })
+    }
+
+    fn from(
+        &self,
+        for_symbol: Symbol,
+        start_date: DateTime,
+        interval: SeriesInterval,
+    ) -> RequestResult<PriceRangeSeries> {
+        self.respond(Operation::From, |data| {
+            data.series
+                .get(&for_symbol)
+                .map(|series| filter_series(series, interval, |point| point.date >= start_date))
+        })
+    }
+
+    fn for_period(
+        &self,
+        for_symbol: Symbol,
+        period: FinancialPeriod,
+    ) -> RequestResult<PriceRangeSeries> {
+        self.respond(Operation::ForPeriod, |data| {
+            data.series.get(&for_symbol).map(|series| {
+                filter_series(series, series.interval, |point| {
+                    period.contains(point.date.date())
+                })
+            })
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+/// The most recent `max_items`, on or after `start_date` if given, newest first.
+fn latest(feed: &[Snapshot<NewsItem>], start_date: Option<Date>, max_items: usize) -> NewsFeed {
+    let mut feed: NewsFeed = feed
+        .iter()
+        .filter(|item| on_or_after(item.date.date(), start_date))
+        .cloned()
+        .collect();
+    feed.sort_by_key(|item| Reverse(item.date));
+    feed.truncate(max_items);
+    feed
+}
+
+fn on_or_after(date: Date, start_date: Option<Date>) -> bool {
+    match start_date {
+        Some(start_date) => date >= start_date,
+        None => true,
+    }
+}
+
+fn in_sub_category(feed: &[Snapshot<NewsItem>], sub_category: &Option<String>) -> NewsFeed {
+    feed.iter()
+        .filter(|item| match (sub_category, &item.data.sub_categories) {
+            (None, _) => true,
+            (Some(sub_category), Some(sub_categories)) => sub_categories.contains(sub_category),
+            (Some(_), None) => false,
+        })
+        .cloned()
+        .collect()
+}
+
+fn select<T: Clone>(values: &HashMap<Symbol, T>, symbols: &Symbols) -> HashMap<Symbol, T> {
+    symbols
+        .iter()
+        .filter_map(|symbol| {
+            values
+                .get(symbol)
+                .map(|value| (symbol.clone(), value.clone()))
+        })
+        .collect()
+}
+
+fn filter_series<F>(
+    series: &PriceRangeSeries,
+    interval: SeriesInterval,
+    predicate: F,
+) -> PriceRangeSeries
+where
+    F: Fn(&Snapshot<PriceRange>) -> bool,
+{
+    PriceRangeSeries {
+        interval,
+        intra_interval: series.intra_interval,
+        series: series
+            .series
+            .iter()
+            .filter(|point| predicate(point))
+            .cloned()
+            .collect(),
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::{MockData, MockProvider};
+    use crate::analysis::Peers;
+    use crate::news::FetchNews;
+    use crate::prelude::*;
+    use crate::provider::Operation;
+    use crate::quote::{FetchPriceQuote, FetchPriceQuoteBatch, Quote};
+    use steel_cent::currency::USD;
+
+    fn symbol(s: &str) -> Symbol {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_programmatic_data() {
+        let mut data = MockData::default();
+        data.add_price(symbol("AAPL"), Money::of_major(USD, 200))
+            .add_price(symbol("MSFT"), Money::of_major(USD, 130));
+        let provider = MockProvider::with_data(data);
+
+        assert_eq!(
+            provider.latest_price_only(symbol("MSFT")).unwrap(),
+            Money::of_major(USD, 130)
+        );
+        assert_eq!(
+            FetchPriceQuote::delayed(&provider, symbol("AAPL"))
+                .unwrap()
+                .data
+                .delayed_by,
+            15
+        );
+        let batch = FetchPriceQuoteBatch::real_time(
+            &provider,
+            vec![symbol("AAPL"), symbol("GOOG")].into_iter().collect(),
+        )
+        .unwrap();
+        assert_eq!(batch.len(), 1);
+        assert!(batch.contains_key(&symbol("AAPL")));
+
+        match provider.latest_price_only(symbol("GOOG")) {
+            Err(err) => assert!(matches!(err.kind(), RequestErrorKind::BadSymbolError(_))),
+            Ok(_) => panic!("expected an error"),
+        }
+    }
+
+    #[test]
+    fn test_scripted_failures() {
+        let mut data = MockData::default();
+        data.add_price(symbol("AAPL"), Money::of_major(USD, 200));
+        let provider = MockProvider::with_data(data);
+
+        provider
+            .fail_next(Operation::RealTime, RequestErrorKind::RequestThrottled)
+            .fail_next(Operation::RealTime, RequestErrorKind::CommunicationError);
+        let kind = |r: RequestResult<Quote>| r.err().map(|e| e.kind().clone());
+        assert_eq!(
+            kind(FetchPriceQuote::real_time(&provider, symbol("AAPL"))),
+            Some(RequestErrorKind::RequestThrottled)
+        );
+        assert_eq!(
+            kind(FetchPriceQuote::real_time(&provider, symbol("AAPL"))),
+            Some(RequestErrorKind::CommunicationError)
+        );
+        assert_eq!(
+            kind(FetchPriceQuote::real_time(&provider, symbol("AAPL"))),
+            None
+        );
+
+        provider.fail_always(Operation::Delayed, RequestErrorKind::Unsupported);
+        assert!(FetchPriceQuote::delayed(&provider, symbol("AAPL")).is_err());
+        assert!(FetchPriceQuote::delayed(&provider, symbol("AAPL")).is_err());
+        provider.clear_failures();
+        assert!(FetchPriceQuote::delayed(&provider, symbol("AAPL")).is_ok());
+
+        assert_eq!(provider.calls(Operation::RealTime), 3);
+        assert_eq!(provider.calls(Operation::Delayed), 3);
+        assert_eq!(provider.calls(Operation::Peers), 0);
+    }
+
+    #[test]
+    fn test_fixture() {
+        let data = MockData::from_json(
+            r#"{
+              "peers": { "AAPL": ["MSFT", "GOOG"] },
+              "news": { "AAPL": [
+                { "date": "2019-06-27T09:00:00",
+                  "data": { "headline": "Older", "source": "s", "url": "u", "summary": "",
+                            "image_url": null, "language": null, "categories": null,
+                            "sub_categories": null } },
+                { "date": "2019-06-28T09:00:00",
+                  "data": { "headline": "Newer", "source": "s", "url": "u", "summary": "",
+                            "image_url": null, "language": null, "categories": null,
+                            "sub_categories": null } }
+              ] }
+            }"#,
+        )
+        .unwrap();
+        let provider = MockProvider::with_data(data);
+
+        assert_eq!(provider.peers(symbol("AAPL")).unwrap().len(), 2);
+        let news = provider.latest_news(symbol("AAPL"), 1).unwrap();
+        assert_eq!(news.len(), 1);
+        assert_eq!(news[0].data.headline, "Newer".to_string());
+
+        assert!(MockData::from_json("{ \"peers\": 1 }").is_err());
+    }
+}
@@ -8,6 +8,9 @@ category classification. This is optional as only some providers expose categori
 part of their news feeds.
 */
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::prelude::*;
 
 // ------------------------------------------------------------------------------------------------
@@ -15,6 +18,8 @@ use crate::prelude::*;
 // ------------------------------------------------------------------------------------------------
 
 /// A single news item, or story.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NewsItem {
     /// The headline for the story
     pub headline: String,
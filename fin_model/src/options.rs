@@ -0,0 +1,381 @@
+/*!
+Provides structs and traits that represent listed option contracts, and the
+chain of quotes for all contracts on an underlying security with the same
+expiration.
+
+An `OptionContract` identifies a single contract by its underlying symbol,
+expiration date, strike price, type (call or put), exercise style, and
+multiplier. Contracts listed in the U.S. are also identified by an OCC
+symbol, which `OptionContract` can both parse and produce. The _request
+trait_ `FetchOptionChain` fetches the expiration dates listed for an
+underlying symbol, and the `OptionChain` for any one of them.
+
+## OCC Symbols
+
+The OCC symbol is the root symbol of the underlying, padded with spaces to
+six characters, followed by the expiration date as `YYMMDD`, `C` or `P` for
+call or put, and the strike price multiplied by 1000 as eight digits. OCC
+symbols do not include the style, multiplier, or currency of the contract so
+parsed contracts are assumed to be American style, U.S. dollar, contracts for
+100 shares, the standard for U.S. equity options. The padding may be omitted
+when parsing.
+
+```rust
+use fin_model::options::{ExerciseStyle, OptionContract, OptionType};
+use fin_model::prelude::*;
+use steel_cent::currency::USD;
+
+let contract: OptionContract = "AAPL  191018C00212500".parse().unwrap();
+assert_eq!(contract.underlying.as_ref(), "AAPL");
+assert_eq!(contract.expiration, Date::from_ymd(2019, 10, 18));
+assert_eq!(contract.strike, Money::of_major_minor(USD, 212, 50));
+assert_eq!(contract.option_type, OptionType::Call);
+assert_eq!(contract.style, ExerciseStyle::American);
+assert_eq!(contract.multiplier, 100);
+assert_eq!(contract.occ_symbol(), "AAPL  191018C00212500");
+```
+*/
+
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use steel_cent::currency::USD;
+
+use crate::prelude::*;
+use crate::symbol::SymbolError;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The right granted by an option contract, to buy (call) or sell (put)
+/// the underlying at the strike price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OptionType {
+    Call,
+    Put,
+}
+
+/// When an option contract may be exercised.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ExerciseStyle {
+    /// at any time up to, and including, the expiration date
+    American,
+    /// only on the expiration date
+    European,
+}
+
+/// A single listed option contract.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OptionContract {
+    pub underlying: Symbol,
+    pub expiration: Date,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::money"))]
+    pub strike: Money,
+    pub option_type: OptionType,
+    pub style: ExerciseStyle,
+    /// the number of units of the underlying delivered on exercise of one contract
+    pub multiplier: u32,
+}
+
+/// The market for a single contract, as part of an `OptionChain`. Values the
+/// provider does not report, or for contracts that have not traded, are `None`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OptionQuote {
+    pub contract: OptionContract,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::option_money"))]
+    pub bid: Option<Money>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::option_money"))]
+    pub ask: Option<Money>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::option_money"))]
+    pub last: Option<Money>,
+    pub volume: Option<u64>,
+    pub open_interest: Option<u64>,
+    /// annualized, as a fraction; i.e. 0.25 is 25%
+    pub implied_volatility: Option<f64>,
+}
+
+/// The quotes for all contracts on `underlying` that expire on `expiration`,
+/// ordered by strike, and then calls before puts.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OptionChain {
+    pub underlying: Symbol,
+    pub expiration: Date,
+    pub quotes: Vec<OptionQuote>,
+}
+
+/// Errors that can result from parsing an OCC option symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OptionSymbolError {
+    /// the string is not the length of an OCC symbol, with or without padding
+    InvalidLength(usize),
+    /// the root symbol is not a valid symbol
+    InvalidUnderlying(SymbolError),
+    /// the expiration is not a valid `YYMMDD` date
+    InvalidExpiration(String),
+    /// the option type is not `C` or `P`
+    InvalidOptionType(char),
+    /// the strike is not eight digits, or is not a whole number of cents
+    InvalidStrike(String),
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Traits
+// ------------------------------------------------------------------------------------------------
+
+/// Fetch listed option contracts, and their quotes, for an underlying symbol.
+pub trait FetchOptionChain {
+    /// The expiration dates of all listed contracts on `for_symbol`, in date order.
+    fn expirations(&self, for_symbol: Symbol) -> RequestResult<Vec<Date>>;
+
+    /// The chain of contracts on `for_symbol` expiring on `expiration`; the
+    /// snapshot date is the time of the latest quote in the chain.
+    fn option_chain(
+        &self,
+        for_symbol: Symbol,
+        expiration: Date,
+    ) -> RequestResult<Snapshot<OptionChain>>;
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl OptionContract {
+    /// Parse an OCC symbol; see the module documentation for the assumed
+    /// style, multiplier, and currency of the returned contract.
+    pub fn from_occ_symbol(s: &str) -> Result<Self, OptionSymbolError> {
+        if !s.is_ascii() || s.len() <= OCC_SUFFIX_LENGTH || s.len() > OCC_SYMBOL_LENGTH {
+            return Err(OptionSymbolError::InvalidLength(s.chars().count()));
+        }
+        let (root, suffix) = s.split_at(s.len() - OCC_SUFFIX_LENGTH);
+        let (expiration, suffix) = suffix.split_at(6);
+        let (option_type, strike) = suffix.split_at(1);
+
+        let underlying =
+            Symbol::from_str(root.trim_end()).map_err(OptionSymbolError::InvalidUnderlying)?;
+        let expiration = Date::parse_from_str(expiration, "%y%m%d")
+            .map_err(|_| OptionSymbolError::InvalidExpiration(expiration.to_string()))?;
+        let option_type = match option_type {
+            "C" => OptionType::Call,
+            "P" => OptionType::Put,
+            other => {
+                return Err(OptionSymbolError::InvalidOptionType(
+                    other.chars().next().unwrap(),
+                ))
+            }
+        };
+        let strike = match strike.parse::<i32>() {
+            Ok(thousandths)
+                if strike.bytes().all(|b| b.is_ascii_digit()) && thousandths % 10 == 0 =>
+            {
+                Money::of_minor(USD, thousandths / 10)
+            }
+            _ => return Err(OptionSymbolError::InvalidStrike(strike.to_string())),
+        };
+        Ok(OptionContract {
+            underlying,
+            expiration,
+            strike,
+            option_type,
+            style: ExerciseStyle::American,
+            multiplier: 100,
+        })
+    }
+
+    /// The OCC symbol for this contract, the root symbol padded to six characters.
+    pub fn occ_symbol(&self) -> String {
+        let decimal_places = u32::from(self.strike.currency.decimal_places());
+        let thousandths = if decimal_places <= 3 {
+            i64::from(self.strike.minor_amount()) * 10i64.pow(3 - decimal_places)
+        } else {
+            i64::from(self.strike.minor_amount()) / 10i64.pow(decimal_places - 3)
+        };
+        format!(
+            "{:<6}{}{}{:08}",
+            self.underlying.as_ref(),
+            self.expiration.format("%y%m%d"),
+            match self.option_type {
+                OptionType::Call => 'C',
+                OptionType::Put => 'P',
+            },
+            thousandths
+        )
+    }
+}
+
+impl OptionChain {
+    /// The quotes for all call contracts in the chain.
+    pub fn calls(&self) -> impl Iterator<Item = &OptionQuote> {
+        self.quotes
+            .iter()
+            .filter(|quote| quote.contract.option_type == OptionType::Call)
+    }
+
+    /// The quotes for all put contracts in the chain.
+    pub fn puts(&self) -> impl Iterator<Item = &OptionQuote> {
+        self.quotes
+            .iter()
+            .filter(|quote| quote.contract.option_type == OptionType::Put)
+    }
+
+    /// The quote for the contract of `option_type` at `strike`, if listed.
+    pub fn quote_for(&self, option_type: OptionType, strike: &Money) -> Option<&OptionQuote> {
+        self.quotes.iter().find(|quote| {
+            quote.contract.option_type == option_type && quote.contract.strike == *strike
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Trait Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl FromStr for OptionContract {
+    type Err = OptionSymbolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        OptionContract::from_occ_symbol(s)
+    }
+}
+
+impl Display for OptionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OptionType::Call => write!(f, "call"),
+            OptionType::Put => write!(f, "put"),
+        }
+    }
+}
+
+impl Display for OptionSymbolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OptionSymbolError::InvalidLength(length) => {
+                write!(f, "option symbol has an invalid length {}", length)
+            }
+            OptionSymbolError::InvalidUnderlying(err) => {
+                write!(f, "option symbol has an invalid root symbol: {}", err)
+            }
+            OptionSymbolError::InvalidExpiration(date) => {
+                write!(f, "option symbol has an invalid expiration '{}'", date)
+            }
+            OptionSymbolError::InvalidOptionType(c) => {
+                write!(f, "option symbol has an invalid option type '{}'", c)
+            }
+            OptionSymbolError::InvalidStrike(strike) => {
+                write!(f, "option symbol has an invalid strike '{}'", strike)
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+/// The length of a padded OCC symbol.
+const OCC_SYMBOL_LENGTH: usize = 21;
+
+/// The length of the expiration, option type, and strike.
+const OCC_SUFFIX_LENGTH: usize = 15;
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contract(option_type: OptionType, strike: i32) -> OptionContract {
+        OptionContract {
+            underlying: "AAPL".parse().unwrap(),
+            expiration: Date::from_ymd(2019, 10, 18),
+            strike: Money::of_major(USD, strike),
+            option_type,
+            style: ExerciseStyle::American,
+            multiplier: 100,
+        }
+    }
+
+    fn quote(contract: OptionContract) -> OptionQuote {
+        OptionQuote {
+            contract,
+            bid: None,
+            ask: None,
+            last: None,
+            volume: None,
+            open_interest: None,
+            implied_volatility: None,
+        }
+    }
+
+    #[test]
+    fn test_occ_symbol_round_trip() {
+        let put = contract(OptionType::Put, 210);
+        assert_eq!(put.occ_symbol(), "AAPL  191018P00210000");
+        assert_eq!(OptionContract::from_str(&put.occ_symbol()).unwrap(), put);
+        assert_eq!(
+            OptionContract::from_str("AAPL191018P00210000").unwrap(),
+            put
+        );
+
+        let mut call = contract(OptionType::Call, 0);
+        call.underlying = "F".parse().unwrap();
+        call.strike = Money::of_major_minor(USD, 8, 50);
+        assert_eq!(call.occ_symbol(), "F     191018C00008500");
+        assert_eq!(OptionContract::from_str(&call.occ_symbol()).unwrap(), call);
+    }
+
+    #[test]
+    fn test_invalid_occ_symbols() {
+        assert_eq!(
+            OptionContract::from_str("191018C00210000"),
+            Err(OptionSymbolError::InvalidLength(15))
+        );
+        assert_eq!(
+            OptionContract::from_str("AAPL  191318C00210000"),
+            Err(OptionSymbolError::InvalidExpiration("191318".to_string()))
+        );
+        assert_eq!(
+            OptionContract::from_str("AAPL  191018X00210000"),
+            Err(OptionSymbolError::InvalidOptionType('X'))
+        );
+        assert_eq!(
+            OptionContract::from_str("AAPL  191018C00210005"),
+            Err(OptionSymbolError::InvalidStrike("00210005".to_string()))
+        );
+        assert!(OptionContract::from_str("AAPL  191018C+0210000").is_err());
+    }
+
+    #[test]
+    fn test_option_chain() {
+        let chain = OptionChain {
+            underlying: "AAPL".parse().unwrap(),
+            expiration: Date::from_ymd(2019, 10, 18),
+            quotes: vec![
+                quote(contract(OptionType::Call, 205)),
+                quote(contract(OptionType::Put, 205)),
+                quote(contract(OptionType::Call, 210)),
+            ],
+        };
+        assert_eq!(chain.calls().count(), 2);
+        assert_eq!(chain.puts().count(), 1);
+        assert!(chain
+            .quote_for(OptionType::Put, &Money::of_major(USD, 205))
+            .is_some());
+        assert!(chain
+            .quote_for(OptionType::Put, &Money::of_major(USD, 210))
+            .is_none());
+    }
+}
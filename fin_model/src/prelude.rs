@@ -10,6 +10,9 @@ crate.
 
 use std::time::Duration;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 pub use steel_cent::SmallMoney as Money;
 
 pub use chrono::NaiveDate as Date;
@@ -21,7 +24,8 @@ pub use chrono::NaiveDateTime as DateTime;
 
 /// A snapshot value; `data` with a `date`, usually the last updated
 /// or or calculated date and time.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Snapshot<T> {
     pub date: DateTime,
     pub data: T,
@@ -29,7 +33,8 @@ pub struct Snapshot<T> {
 
 /// A time-bounded value; `data` with a `start_date` and `end_date`
 /// signifying the range within which the data is considered valid.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Bounded<T> {
     pub start_date: DateTime,
     pub end_date: DateTime,
@@ -38,7 +43,8 @@ pub struct Bounded<T> {
 
 /// Represents a `series` of data points, over the time period indicated
 /// by `interval`, with values separated by `intra_interval`.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Series<I, T> {
     /// the interval over which time data is reported
     pub interval: I,
@@ -56,6 +62,6 @@ pub use crate::provider::Provider;
 
 pub use crate::registry::Registry;
 
-pub use crate::request::{RequestError, RequestResult};
+pub use crate::request::{RequestError, RequestErrorKind, RequestResult};
 
 pub use crate::symbol::{Symbol, Symbols};
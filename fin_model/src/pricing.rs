@@ -0,0 +1,742 @@
+/*!
+Provides option pricing models, the Greeks, and an implied volatility solver.
+
+Three models are provided, each selected with a `PricingModel`:
+
+* `BlackScholesMerton`, the closed-form value of a European option on an
+  underlying paying a continuous dividend yield;
+* `Black76`, the closed-form value of a European option on a forward, or
+  futures, contract, where the underlying price is the forward price;
+* `CoxRossRubinstein`, a binomial tree which, unlike the closed-form models,
+  values the early exercise of an American option.
+
+All values are per unit of the underlying, multiply by the contract's
+`multiplier` for the value of one contract. Times are in years and rates,
+yields, and volatilities are annual and expressed as a fraction, so 2.5% is
+`0.025`; rates and yields are continuously compounded. The Greeks are the
+partial derivatives of the value, so vega is the change in value for a change
+in volatility of 1.0 (not 1%), rho for a change in rate of 1.0, and theta is
+the change in value for one year passing, usually negative.
+
+Where inputs are not valid, such as a non-positive price or volatility, the
+functions return `None`. An option with no time to expiry is valued at its
+intrinsic value.
+
+## Example
+
+The following recalculates the Greeks for each quote in a chain from its
+implied volatility, rather than relying on values reported by a provider.
+
+```rust,no_run
+use fin_model::options::FetchOptionChain;
+use fin_model::pricing::{quote_valuation, PricingModel};
+use fin_model::quote::FetchPriceQuote;
+# use fin_model::prelude::*;
+# fn example(provider: &(impl FetchOptionChain + FetchPriceQuote)) -> RequestResult<()> {
+let symbol: Symbol = "AAPL".parse().unwrap();
+let expiration = provider.expirations(symbol.clone())?[0];
+let chain = provider.option_chain(symbol.clone(), expiration)?;
+let underlying = provider.latest_price_only(symbol)?;
+
+let model = PricingModel::BlackScholesMerton;
+for quote in &chain.data.quotes {
+    if let Some(valuation) =
+        quote_valuation(&model, quote, &underlying, chain.date.date(), 0.02, 0.01)
+    {
+        println!(
+            "{} IV {:.3} delta {:.3} gamma {:.4}",
+            quote.contract.occ_symbol(),
+            valuation.volatility,
+            valuation.greeks.delta,
+            valuation.greeks.gamma
+        );
+    }
+}
+# Ok(())
+# }
+```
+*/
+
+use std::f64::consts::PI;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::options::{ExerciseStyle, OptionQuote, OptionType};
+use crate::prelude::*;
+use crate::series::amount;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The model used to value an option.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PricingModel {
+    BlackScholesMerton,
+    Black76,
+    CoxRossRubinstein {
+        /// the number of steps in the tree, at least 2
+        steps: usize,
+        style: ExerciseStyle,
+    },
+}
+
+/// The market inputs to a pricing model.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PricingInputs {
+    /// the spot price of the underlying, or for `Black76` the forward price
+    pub underlying_price: f64,
+    pub strike: f64,
+    /// in years
+    pub time_to_expiry: f64,
+    pub risk_free_rate: f64,
+    /// ignored by `Black76`
+    pub dividend_yield: f64,
+    pub volatility: f64,
+}
+
+/// The sensitivities of an option's value to its inputs.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Greeks {
+    /// to the underlying price
+    pub delta: f64,
+    /// of delta to the underlying price
+    pub gamma: f64,
+    /// to volatility
+    pub vega: f64,
+    /// to the passing of time, per year
+    pub theta: f64,
+    /// to the risk-free rate
+    pub rho: f64,
+}
+
+/// The value of an option, and its Greeks, at a given volatility.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Valuation {
+    pub price: f64,
+    /// the volatility used, either an input or an implied volatility
+    pub volatility: f64,
+    pub greeks: Greeks,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+/// The number of days in a year, used to calculate the time to expiry.
+pub const DAYS_PER_YEAR: f64 = 365.0;
+
+/// The time, in years, from `as_of` to `expiration`; negative if expired.
+pub fn time_to_expiry(as_of: Date, expiration: Date) -> f64 {
+    (expiration - as_of).num_days() as f64 / DAYS_PER_YEAR
+}
+
+/// Value an option using `model`.
+pub fn value(
+    model: &PricingModel,
+    option_type: OptionType,
+    inputs: &PricingInputs,
+) -> Option<Valuation> {
+    match model {
+        PricingModel::BlackScholesMerton => black_scholes_merton(option_type, inputs),
+        PricingModel::Black76 => black_76(option_type, inputs),
+        PricingModel::CoxRossRubinstein { steps, style } => {
+            cox_ross_rubinstein(option_type, *style, inputs, *steps)
+        }
+    }
+}
+
+/// Value a European option on an underlying paying a continuous dividend yield.
+pub fn black_scholes_merton(option_type: OptionType, inputs: &PricingInputs) -> Option<Valuation> {
+    if !is_valid(inputs) {
+        return None;
+    }
+    if inputs.time_to_expiry == 0.0 {
+        return Some(expired(option_type, inputs));
+    }
+    let PricingInputs {
+        underlying_price: s,
+        strike: k,
+        time_to_expiry: t,
+        risk_free_rate: r,
+        dividend_yield: q,
+        volatility: sigma,
+    } = *inputs;
+    let root_t = t.sqrt();
+    let d1 = ((s / k).ln() + (r - q + sigma * sigma / 2.0) * t) / (sigma * root_t);
+    let d2 = d1 - sigma * root_t;
+    let (dividend_discount, discount) = ((-q * t).exp(), (-r * t).exp());
+    let decay = -s * dividend_discount * normal_density(d1) * sigma / (2.0 * root_t);
+    let (price, delta, theta, rho) = match option_type {
+        OptionType::Call => (
+            s * dividend_discount * normal_cdf(d1) - k * discount * normal_cdf(d2),
+            dividend_discount * normal_cdf(d1),
+            decay - r * k * discount * normal_cdf(d2) + q * s * dividend_discount * normal_cdf(d1),
+            k * t * discount * normal_cdf(d2),
+        ),
+        OptionType::Put => (
+            k * discount * normal_cdf(-d2) - s * dividend_discount * normal_cdf(-d1),
+            -dividend_discount * normal_cdf(-d1),
+            decay + r * k * discount * normal_cdf(-d2)
+                - q * s * dividend_discount * normal_cdf(-d1),
+            -k * t * discount * normal_cdf(-d2),
+        ),
+    };
+    Some(Valuation {
+        price,
+        volatility: sigma,
+        greeks: Greeks {
+            delta,
+            gamma: dividend_discount * normal_density(d1) / (s * sigma * root_t),
+            vega: s * dividend_discount * normal_density(d1) * root_t,
+            theta,
+            rho,
+        },
+    })
+}
+
+/// Value a European option on a forward, or futures, contract; the underlying
+/// price in `inputs` is the forward price and the dividend yield is ignored.
+/// Delta and gamma are with respect to the forward price.
+pub fn black_76(option_type: OptionType, inputs: &PricingInputs) -> Option<Valuation> {
+    // Black-76 is Black-Scholes-Merton with a dividend yield equal to the
+    // risk-free rate, except that the forward price does not change with the
+    // rate and so rho is only the effect of discounting.
+    let inputs = PricingInputs {
+        dividend_yield: inputs.risk_free_rate,
+        ..inputs.clone()
+    };
+    black_scholes_merton(option_type, &inputs).map(|mut valuation| {
+        valuation.greeks.rho = -inputs.time_to_expiry * valuation.price;
+        valuation
+    })
+}
+
+/// Value an option using a Cox-Ross-Rubinstein binomial tree of `steps` steps.
+/// Delta, gamma, and theta are taken from the tree, vega and rho by revaluing
+/// with small changes in volatility and rate. Returns `None` if `steps` is
+/// less than 2, or the tree has no valid risk-neutral probability for the
+/// inputs, usually because there are too few steps.
+pub fn cox_ross_rubinstein(
+    option_type: OptionType,
+    style: ExerciseStyle,
+    inputs: &PricingInputs,
+    steps: usize,
+) -> Option<Valuation> {
+    if !is_valid(inputs) || steps < 2 {
+        return None;
+    }
+    if inputs.time_to_expiry == 0.0 {
+        return Some(expired(option_type, inputs));
+    }
+    let tree = binomial_tree(option_type, style, inputs, steps)?;
+    let revalue = |volatility: f64, risk_free_rate: f64| {
+        let inputs = PricingInputs {
+            volatility,
+            risk_free_rate,
+            ..inputs.clone()
+        };
+        binomial_tree(option_type, style, &inputs, steps).map(|tree| tree.price)
+    };
+    let sigma = inputs.volatility;
+    let volatility_bump = VOLATILITY_BUMP.min(sigma / 2.0);
+    let vega = (revalue(sigma + volatility_bump, inputs.risk_free_rate)?
+        - revalue(sigma - volatility_bump, inputs.risk_free_rate)?)
+        / (2.0 * volatility_bump);
+    let rho = (revalue(sigma, inputs.risk_free_rate + RATE_BUMP)?
+        - revalue(sigma, inputs.risk_free_rate - RATE_BUMP)?)
+        / (2.0 * RATE_BUMP);
+    Some(Valuation {
+        price: tree.price,
+        volatility: sigma,
+        greeks: Greeks {
+            delta: tree.delta,
+            gamma: tree.gamma,
+            vega,
+            theta: tree.theta,
+            rho,
+        },
+    })
+}
+
+/// The volatility at which `model` values the option at `price`, the
+/// volatility in `inputs` is ignored. Returns `None` if the inputs are not
+/// valid, or no volatility between 0.0001% and 500% gives `price`; for example
+/// if `price` is below the option's minimum value.
+pub fn implied_volatility(
+    model: &PricingModel,
+    option_type: OptionType,
+    price: f64,
+    inputs: &PricingInputs,
+) -> Option<f64> {
+    if !price.is_finite() || price <= 0.0 || inputs.time_to_expiry <= 0.0 {
+        return None;
+    }
+    let value_at = |volatility: f64| {
+        let inputs = PricingInputs {
+            volatility,
+            ..inputs.clone()
+        };
+        value(model, option_type, &inputs)
+    };
+    let (mut low, mut high) = (MIN_VOLATILITY, MAX_VOLATILITY);
+    // a binomial tree has no valid probabilities at very low volatilities
+    while value_at(low).is_none() {
+        low *= 2.0;
+        if low >= high {
+            return None;
+        }
+    }
+    if price < value_at(low)?.price || price > value_at(high)?.price {
+        return None;
+    }
+    // Newton's method, falling back to bisection whenever a step would leave
+    // the bracket known to contain the solution.
+    let mut volatility = INITIAL_VOLATILITY;
+    for _ in 0..MAX_ITERATIONS {
+        let valuation = value_at(volatility)?;
+        let difference = valuation.price - price;
+        if difference.abs() < PRICE_TOLERANCE {
+            return Some(volatility);
+        }
+        if difference > 0.0 {
+            high = volatility;
+        } else {
+            low = volatility;
+        }
+        let next = volatility - difference / valuation.greeks.vega;
+        volatility = if next.is_finite() && next > low && next < high {
+            next
+        } else {
+            (low + high) / 2.0
+        };
+        if high - low < VOLATILITY_TOLERANCE {
+            return Some(volatility);
+        }
+    }
+    Some(volatility)
+}
+
+/// Value the option in `quote` at its implied volatility, calculated from the
+/// quote's mid price, or last price if there is no bid and ask, and the
+/// underlying price. Returns `None` if the quote has no price, or no implied
+/// volatility can be found.
+pub fn quote_valuation(
+    model: &PricingModel,
+    quote: &OptionQuote,
+    underlying_price: &Money,
+    as_of: Date,
+    risk_free_rate: f64,
+    dividend_yield: f64,
+) -> Option<Valuation> {
+    let price = match (&quote.bid, &quote.ask) {
+        (Some(bid), Some(ask))
+            if bid.minor_amount() > 0 && ask.minor_amount() >= bid.minor_amount() =>
+        {
+            (amount(bid) + amount(ask)) / 2.0
+        }
+        _ => amount(quote.last.as_ref()?),
+    };
+    let contract = &quote.contract;
+    let mut inputs = PricingInputs {
+        underlying_price: amount(underlying_price),
+        strike: amount(&contract.strike),
+        time_to_expiry: time_to_expiry(as_of, contract.expiration),
+        risk_free_rate,
+        dividend_yield,
+        volatility: INITIAL_VOLATILITY,
+    };
+    inputs.volatility = implied_volatility(model, contract.option_type, price, &inputs)?;
+    value(model, contract.option_type, &inputs)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+struct Tree {
+    price: f64,
+    delta: f64,
+    gamma: f64,
+    theta: f64,
+}
+
+const VOLATILITY_BUMP: f64 = 0.001;
+
+const RATE_BUMP: f64 = 0.0001;
+
+const MIN_VOLATILITY: f64 = 0.000_001;
+
+const MAX_VOLATILITY: f64 = 5.0;
+
+const INITIAL_VOLATILITY: f64 = 0.2;
+
+const PRICE_TOLERANCE: f64 = 1.0e-10;
+
+const VOLATILITY_TOLERANCE: f64 = 1.0e-12;
+
+const MAX_ITERATIONS: usize = 100;
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn is_valid(inputs: &PricingInputs) -> bool {
+    let PricingInputs {
+        underlying_price,
+        strike,
+        time_to_expiry,
+        risk_free_rate,
+        dividend_yield,
+        volatility,
+    } = *inputs;
+    underlying_price > 0.0
+        && strike > 0.0
+        && time_to_expiry >= 0.0
+        && volatility > 0.0
+        && [underlying_price, strike, time_to_expiry, volatility]
+            .iter()
+            .all(|v| v.is_finite())
+        && risk_free_rate.is_finite()
+        && dividend_yield.is_finite()
+}
+
+fn payoff(option_type: OptionType, underlying_price: f64, strike: f64) -> f64 {
+    match option_type {
+        OptionType::Call => (underlying_price - strike).max(0.0),
+        OptionType::Put => (strike - underlying_price).max(0.0),
+    }
+}
+
+/// At expiry an option is worth its intrinsic value, delta is one for an in
+/// the money call (minus one for a put) and the other Greeks are zero.
+fn expired(option_type: OptionType, inputs: &PricingInputs) -> Valuation {
+    let price = payoff(option_type, inputs.underlying_price, inputs.strike);
+    Valuation {
+        price,
+        volatility: inputs.volatility,
+        greeks: Greeks {
+            delta: match option_type {
+                _ if price == 0.0 => 0.0,
+                OptionType::Call => 1.0,
+                OptionType::Put => -1.0,
+            },
+            gamma: 0.0,
+            vega: 0.0,
+            theta: 0.0,
+            rho: 0.0,
+        },
+    }
+}
+
+/// Values the tree backwards from expiry, keeping the option values at the
+/// first two steps for delta, gamma, and theta.
+fn binomial_tree(
+    option_type: OptionType,
+    style: ExerciseStyle,
+    inputs: &PricingInputs,
+    steps: usize,
+) -> Option<Tree> {
+    let s = inputs.underlying_price;
+    let dt = inputs.time_to_expiry / steps as f64;
+    let up = (inputs.volatility * dt.sqrt()).exp();
+    let down = 1.0 / up;
+    let probability =
+        (((inputs.risk_free_rate - inputs.dividend_yield) * dt).exp() - down) / (up - down);
+    if !(probability > 0.0 && probability < 1.0) {
+        return None;
+    }
+    let discount = (-inputs.risk_free_rate * dt).exp();
+    let price_at =
+        |step: usize, ups: usize| s * up.powi(ups as i32) * down.powi((step - ups) as i32);
+
+    let mut values: Vec<f64> = (0..=steps)
+        .map(|ups| payoff(option_type, price_at(steps, ups), inputs.strike))
+        .collect();
+    let mut at_step: Vec<Vec<f64>> = vec![Vec::new(); 3];
+    if steps == 2 {
+        at_step[2] = values.clone();
+    }
+    for step in (0..steps).rev() {
+        for ups in 0..=step {
+            let continuation =
+                discount * (probability * values[ups + 1] + (1.0 - probability) * values[ups]);
+            values[ups] = match style {
+                ExerciseStyle::American => {
+                    continuation.max(payoff(option_type, price_at(step, ups), inputs.strike))
+                }
+                ExerciseStyle::European => continuation,
+            };
+        }
+        values.truncate(step + 1);
+        if step <= 2 {
+            at_step[step] = values.clone();
+        }
+    }
+
+    let (v1, v2) = (&at_step[1], &at_step[2]);
+    let (s_up, s_down) = (price_at(1, 1), price_at(1, 0));
+    let (s_up_up, s_down_down) = (price_at(2, 2), price_at(2, 0));
+    let delta_up = (v2[2] - v2[1]) / (s_up_up - s);
+    let delta_down = (v2[1] - v2[0]) / (s - s_down_down);
+    Some(Tree {
+        price: at_step[0][0],
+        delta: (v1[1] - v1[0]) / (s_up - s_down),
+        gamma: (delta_up - delta_down) / ((s_up_up - s_down_down) / 2.0),
+        theta: (v2[1] - at_step[0][0]) / (2.0 * dt),
+    })
+}
+
+fn normal_density(x: f64) -> f64 {
+    (-x * x / 2.0).exp() / (2.0 * PI).sqrt()
+}
+
+/// The cumulative standard normal distribution, using the double precision
+/// approximation of Hart (1968), as given by West (2005).
+fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs();
+    let tail = if z > 37.0 {
+        0.0
+    } else if z < 7.071_067_811_865_47 {
+        let numerator = [
+            0.035_262_496_599_891_1,
+            0.700_383_064_443_688,
+            6.373_962_203_531_65,
+            33.912_866_078_383,
+            112.079_291_497_871,
+            221.213_596_169_931,
+            220.206_867_912_376,
+        ]
+        .iter()
+        .fold(0.0, |b, c| b * z + c);
+        let denominator = [
+            0.088_388_347_648_318_4,
+            1.755_667_163_182_64,
+            16.064_177_579_207,
+            86.780_732_202_946_1,
+            296.564_248_779_674,
+            637.333_633_378_831,
+            793.826_512_519_948,
+            440.413_735_824_752,
+        ]
+        .iter()
+        .fold(0.0, |b, c| b * z + c);
+        (-z * z / 2.0).exp() * numerator / denominator
+    } else {
+        let fraction = [4.0, 3.0, 2.0, 1.0].iter().fold(z + 0.65, |b, c| z + c / b);
+        (-z * z / 2.0).exp() / fraction / (2.0 * PI).sqrt()
+    };
+    if x > 0.0 {
+        1.0 - tail
+    } else {
+        tail
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::OptionContract;
+    use steel_cent::currency::USD;
+
+    fn at_the_money() -> PricingInputs {
+        PricingInputs {
+            underlying_price: 100.0,
+            strike: 100.0,
+            time_to_expiry: 1.0,
+            risk_free_rate: 0.05,
+            dividend_yield: 0.0,
+            volatility: 0.2,
+        }
+    }
+
+    fn assert_near(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() < tolerance,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_normal_cdf() {
+        assert_near(normal_cdf(0.0), 0.5, 1e-15);
+        assert_near(normal_cdf(1.96), 0.975_002_104_851_779_5, 1e-14);
+        assert_near(normal_cdf(-1.0), 0.158_655_253_931_457_07, 1e-14);
+        assert_near(normal_cdf(-3.5), 0.000_232_629_079_035_525, 1e-16);
+        assert_near(normal_cdf(-8.0), 6.220_960_574_271_819e-16, 1e-20);
+        assert_eq!(normal_cdf(40.0), 1.0);
+    }
+
+    #[test]
+    fn test_black_scholes_merton() {
+        let call = black_scholes_merton(OptionType::Call, &at_the_money()).unwrap();
+        assert_near(call.price, 10.450_583_572_185_565, 1e-10);
+        assert_near(call.greeks.delta, 0.636_830_651_175_619_1, 1e-10);
+        assert_near(call.greeks.gamma, 0.018_762_017_345_846_895, 1e-10);
+        assert_near(call.greeks.vega, 37.524_034_691_693_79, 1e-8);
+        assert_near(call.greeks.theta, -6.414_027_546_438_197, 1e-8);
+        assert_near(call.greeks.rho, 53.232_481_545_376_345, 1e-8);
+
+        let inputs = PricingInputs {
+            strike: 95.0,
+            time_to_expiry: 0.5,
+            dividend_yield: 0.02,
+            volatility: 0.25,
+            ..at_the_money()
+        };
+        let put = black_scholes_merton(OptionType::Put, &inputs).unwrap();
+        assert_near(put.price, 4.041_887_951_766_604, 1e-10);
+        assert_near(put.greeks.delta, -0.318_339_527_026_883_73, 1e-10);
+        assert_near(put.greeks.theta, -5.114_251_744_121_222, 1e-8);
+        assert_near(put.greeks.rho, -17.937_920_327_227_488, 1e-8);
+
+        // put-call parity, C - P = S e^-qT - K e^-rT
+        let call = black_scholes_merton(OptionType::Call, &inputs).unwrap();
+        assert_near(
+            call.price - put.price,
+            100.0 * (-0.01f64).exp() - 95.0 * (-0.025f64).exp(),
+            1e-10,
+        );
+    }
+
+    #[test]
+    fn test_black_76() {
+        let call = black_76(OptionType::Call, &at_the_money()).unwrap();
+        assert_near(call.price, 7.577_082_146_427_273, 1e-10);
+        assert_near(call.greeks.rho, -7.577_082_146_427_273, 1e-10);
+        let put = black_76(OptionType::Put, &at_the_money()).unwrap();
+        assert_near(put.price, call.price, 1e-10);
+    }
+
+    #[test]
+    fn test_cox_ross_rubinstein() {
+        let inputs = at_the_money();
+        let european =
+            cox_ross_rubinstein(OptionType::Call, ExerciseStyle::European, &inputs, 500).unwrap();
+        assert_near(european.price, 10.446_585_136_446_54, 1e-8);
+        let analytic = black_scholes_merton(OptionType::Call, &inputs).unwrap();
+        assert_near(european.greeks.delta, analytic.greeks.delta, 1e-3);
+        assert_near(european.greeks.gamma, analytic.greeks.gamma, 1e-3);
+        assert_near(european.greeks.vega, analytic.greeks.vega, 0.1);
+        assert_near(european.greeks.theta, analytic.greeks.theta, 0.05);
+        assert_near(european.greeks.rho, analytic.greeks.rho, 0.1);
+
+        let american =
+            cox_ross_rubinstein(OptionType::Put, ExerciseStyle::American, &inputs, 500).unwrap();
+        assert_near(american.price, 6.088_810_110_703_037, 1e-8);
+        let european =
+            cox_ross_rubinstein(OptionType::Put, ExerciseStyle::European, &inputs, 500).unwrap();
+        assert!(american.price > european.price);
+
+        let two_steps =
+            cox_ross_rubinstein(OptionType::Put, ExerciseStyle::American, &inputs, 2).unwrap();
+        assert!(two_steps.greeks.gamma > 0.0);
+        assert!(
+            cox_ross_rubinstein(OptionType::Put, ExerciseStyle::American, &inputs, 1).is_none()
+        );
+    }
+
+    #[test]
+    fn test_expired_and_invalid() {
+        let inputs = PricingInputs {
+            underlying_price: 110.0,
+            time_to_expiry: 0.0,
+            ..at_the_money()
+        };
+        let call = black_scholes_merton(OptionType::Call, &inputs).unwrap();
+        assert_eq!(call.price, 10.0);
+        assert_eq!(call.greeks.delta, 1.0);
+        let put = black_scholes_merton(OptionType::Put, &inputs).unwrap();
+        assert_eq!(put.price, 0.0);
+        assert_eq!(put.greeks.delta, 0.0);
+
+        let inputs = PricingInputs {
+            volatility: 0.0,
+            ..at_the_money()
+        };
+        assert!(black_scholes_merton(OptionType::Call, &inputs).is_none());
+    }
+
+    #[test]
+    fn test_implied_volatility() {
+        let inputs = PricingInputs {
+            volatility: 0.35,
+            ..at_the_money()
+        };
+        for model in &[
+            PricingModel::BlackScholesMerton,
+            PricingModel::Black76,
+            PricingModel::CoxRossRubinstein {
+                steps: 200,
+                style: ExerciseStyle::American,
+            },
+        ] {
+            for option_type in &[OptionType::Call, OptionType::Put] {
+                let price = value(model, *option_type, &inputs).unwrap().price;
+                let volatility =
+                    implied_volatility(model, *option_type, price, &at_the_money()).unwrap();
+                assert_near(volatility, 0.35, 1e-6);
+            }
+        }
+        // below the discounted intrinsic value
+        let inputs = PricingInputs {
+            strike: 50.0,
+            ..at_the_money()
+        };
+        assert!(implied_volatility(
+            &PricingModel::BlackScholesMerton,
+            OptionType::Call,
+            45.0,
+            &inputs
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_quote_valuation() {
+        let expiration = Date::from_ymd(2020, 1, 1);
+        let as_of = expiration - chrono::Duration::days(365);
+        let inputs = at_the_money();
+        let price = black_scholes_merton(OptionType::Call, &inputs)
+            .unwrap()
+            .price;
+        let quote = OptionQuote {
+            contract: OptionContract {
+                underlying: "AAPL".parse().unwrap(),
+                expiration,
+                strike: Money::of_major(USD, 100),
+                option_type: OptionType::Call,
+                style: ExerciseStyle::European,
+                multiplier: 100,
+            },
+            bid: Some(Money::of_minor(USD, (price * 100.0).round() as i32 - 5)),
+            ask: Some(Money::of_minor(USD, (price * 100.0).round() as i32 + 5)),
+            last: None,
+            volume: None,
+            open_interest: None,
+            implied_volatility: None,
+        };
+        let valuation = quote_valuation(
+            &PricingModel::BlackScholesMerton,
+            &quote,
+            &Money::of_major(USD, 100),
+            as_of,
+            0.05,
+            0.0,
+        )
+        .unwrap();
+        assert_near(valuation.volatility, 0.2, 1e-3);
+        assert_near(valuation.greeks.delta, 0.6368, 1e-3);
+    }
+}
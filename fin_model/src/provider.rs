@@ -5,10 +5,145 @@ Provides the trait required by service provider implementations.
 Before implementing any _request traits_ a service provider needs to
 implement the `Provider` trait. This is the mechanism by which a client
 instantiates a specific service provider.
+
+## Capabilities
+
+A provider also describes the operations it implements, and the markets and
+currencies it covers, with the `Capabilities` type returned by
+`Provider::capabilities`. This allows a client to select a provider up front
+rather than calling an operation and receiving a `RequestErrorKind::Unsupported`
+error.
+
+```rust
+use fin_model::provider::{Capabilities, Operation, RequestTrait};
+
+let mut capabilities = Capabilities::default();
+capabilities.add_trait(RequestTrait::FetchPriceQuote);
+capabilities.markets = Some(["XNAS", "XNYS"].iter().map(|s| s.to_string()).collect());
+
+assert!(capabilities.supports(Operation::LatestPriceOnly));
+assert!(!capabilities.supports(Operation::LatestNews));
+assert!(capabilities.covers_market("XNAS"));
+assert!(!capabilities.covers_market("XLON"));
+```
 */
 
+use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::request::RequestResult;
 
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// Identifies one of the _request traits_ a provider may implement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RequestTrait {
+    Peers,
+    AnalystRecommendations,
+    FetchEarnings,
+    FetchCompanyInformation,
+    FetchCompanyFinancials,
+    FetchCompanyStatistics,
+    FetchCorporateActions,
+    FetchNews,
+    FetchCategoryNews,
+    FetchOptionChain,
+    FetchPriceQuote,
+    FetchPriceQuoteBatch,
+    FetchPriceRangeSeries,
+}
+
+/// Identifies a single operation, a method, of one of the _request traits_.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Operation {
+    /// `Peers::peers`
+    Peers,
+    /// `AnalystRecommendations::target_price`
+    TargetPrice,
+    /// `AnalystRecommendations::consensus_rating`
+    ConsensusRating,
+    /// `AnalystRecommendations::consensus_eps`
+    ConsensusEPS,
+    /// `FetchEarnings::earnings`
+    Earnings,
+    /// `FetchCompanyInformation::about`
+    About,
+    /// `FetchCompanyInformation::filings`
+    Filings,
+    /// `FetchCompanyFinancials::reported_income`
+    ReportedIncome,
+    /// `FetchCompanyFinancials::reported_balance_sheet`
+    ReportedBalanceSheet,
+    /// `FetchCompanyFinancials::reported_cash_flow`
+    ReportedCashFlow,
+    /// `FetchCompanyStatistics::core_statistics`
+    CoreStatistics,
+    /// `FetchCorporateActions::dividends`
+    Dividends,
+    /// `FetchCorporateActions::splits`
+    Splits,
+    /// `FetchCorporateActions::corporate_actions`
+    CorporateActions,
+    /// `FetchNews::latest_news`
+    LatestNews,
+    /// `FetchNews::news_from`
+    NewsFrom,
+    /// `FetchCategoryNews::latest_news`
+    LatestCategoryNews,
+    /// `FetchCategoryNews::news_from`
+    CategoryNewsFrom,
+    /// `FetchOptionChain::expirations`
+    OptionExpirations,
+    /// `FetchOptionChain::option_chain`
+    OptionChain,
+    /// `FetchPriceQuote::latest_price_only`
+    LatestPriceOnly,
+    /// `FetchPriceQuote::real_time`
+    RealTime,
+    /// `FetchPriceQuote::delayed`
+    Delayed,
+    /// `FetchPriceQuoteBatch::real_time`
+    RealTimeBatch,
+    /// `FetchPriceQuoteBatch::delayed`
+    DelayedBatch,
+    /// `FetchPriceRangeSeries::intra_day`
+    IntraDay,
+    /// `FetchPriceRangeSeries::last`
+    Last,
+    /// `FetchPriceRangeSeries::from`
+    From,
+    /// `FetchPriceRangeSeries::for_period`
+    ForPeriod,
+}
+
+/// Describes the operations a provider implements, and the data it covers.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Capabilities {
+    /// the operations implemented by the provider, any operation not in
+    /// this set will return `RequestErrorKind::Unsupported`
+    pub operations: HashSet<Operation>,
+    /// whether the provider returns real-time prices
+    pub real_time: bool,
+    /// if the provider returns delayed prices, the delay in minutes
+    pub delayed_by: Option<u16>,
+    /// the MIC codes of markets covered by the provider, or `None` if
+    /// the provider does not restrict, or does not declare, its markets
+    pub markets: Option<HashSet<String>>,
+    /// the ISO currency codes the provider returns values in, an empty set
+    /// if the provider does not restrict, or does not declare, its currencies
+    pub currencies: HashSet<String>,
+    /// the maximum number of symbols accepted in a single call, by operation;
+    /// an operation not in this map accepts any number of symbols
+    pub max_batch_sizes: HashMap<Operation, usize>,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Public Traits
 // ------------------------------------------------------------------------------------------------
@@ -32,6 +167,198 @@ where
     /// The default currency code, using ISO three-character code.
     fn get_default_currency(&self) -> &String;
 
+    /// The operations implemented by this provider and the data it covers.
+    /// The default declares no operations and no restrictions on coverage.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::default()
+    }
+
     /// Optional, if the provider needs to close or complete any resources when done.
     fn finish(&self);
 }
+
+// ------------------------------------------------------------------------------------------------
+// Trait Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl RequestTrait {
+    /// All the operations defined by this request trait.
+    pub fn operations(&self) -> Vec<Operation> {
+        match self {
+            RequestTrait::Peers => vec![Operation::Peers],
+            RequestTrait::AnalystRecommendations => vec![
+                Operation::TargetPrice,
+                Operation::ConsensusRating,
+                Operation::ConsensusEPS,
+            ],
+            RequestTrait::FetchEarnings => vec![Operation::Earnings],
+            RequestTrait::FetchCompanyInformation => vec![Operation::About, Operation::Filings],
+            RequestTrait::FetchCompanyFinancials => vec![
+                Operation::ReportedIncome,
+                Operation::ReportedBalanceSheet,
+                Operation::ReportedCashFlow,
+            ],
+            RequestTrait::FetchCompanyStatistics => vec![Operation::CoreStatistics],
+            RequestTrait::FetchCorporateActions => vec![
+                Operation::Dividends,
+                Operation::Splits,
+                Operation::CorporateActions,
+            ],
+            RequestTrait::FetchNews => vec![Operation::LatestNews, Operation::NewsFrom],
+            RequestTrait::FetchCategoryNews => {
+                vec![Operation::LatestCategoryNews, Operation::CategoryNewsFrom]
+            }
+            RequestTrait::FetchOptionChain => {
+                vec![Operation::OptionExpirations, Operation::OptionChain]
+            }
+            RequestTrait::FetchPriceQuote => vec![
+                Operation::LatestPriceOnly,
+                Operation::RealTime,
+                Operation::Delayed,
+            ],
+            RequestTrait::FetchPriceQuoteBatch => {
+                vec![Operation::RealTimeBatch, Operation::DelayedBatch]
+            }
+            RequestTrait::FetchPriceRangeSeries => vec![
+                Operation::IntraDay,
+                Operation::Last,
+                Operation::From,
+                Operation::ForPeriod,
+            ],
+        }
+    }
+}
+
+impl Operation {
+    /// The request trait that defines this operation.
+    pub fn request_trait(&self) -> RequestTrait {
+        match self {
+            Operation::Peers => RequestTrait::Peers,
+            Operation::TargetPrice | Operation::ConsensusRating | Operation::ConsensusEPS => {
+                RequestTrait::AnalystRecommendations
+            }
+            Operation::Earnings => RequestTrait::FetchEarnings,
+            Operation::About | Operation::Filings => RequestTrait::FetchCompanyInformation,
+            Operation::ReportedIncome
+            | Operation::ReportedBalanceSheet
+            | Operation::ReportedCashFlow => RequestTrait::FetchCompanyFinancials,
+            Operation::CoreStatistics => RequestTrait::FetchCompanyStatistics,
+            Operation::Dividends | Operation::Splits | Operation::CorporateActions => {
+                RequestTrait::FetchCorporateActions
+            }
+            Operation::LatestNews | Operation::NewsFrom => RequestTrait::FetchNews,
+            Operation::LatestCategoryNews | Operation::CategoryNewsFrom => {
+                RequestTrait::FetchCategoryNews
+            }
+            Operation::OptionExpirations | Operation::OptionChain => RequestTrait::FetchOptionChain,
+            Operation::LatestPriceOnly | Operation::RealTime | Operation::Delayed => {
+                RequestTrait::FetchPriceQuote
+            }
+            Operation::RealTimeBatch | Operation::DelayedBatch => {
+                RequestTrait::FetchPriceQuoteBatch
+            }
+            Operation::IntraDay | Operation::Last | Operation::From | Operation::ForPeriod => {
+                RequestTrait::FetchPriceRangeSeries
+            }
+        }
+    }
+}
+
+impl Capabilities {
+    /// Add all the operations of `request_trait` to the supported operations.
+    pub fn add_trait(&mut self, request_trait: RequestTrait) {
+        self.operations.extend(request_trait.operations());
+    }
+
+    /// Returns `true` if the provider implements `operation`.
+    pub fn supports(&self, operation: Operation) -> bool {
+        self.operations.contains(&operation)
+    }
+
+    /// Returns `true` if the provider implements any operation of `request_trait`.
+    pub fn supports_any(&self, request_trait: RequestTrait) -> bool {
+        request_trait
+            .operations()
+            .iter()
+            .any(|op| self.operations.contains(op))
+    }
+
+    /// Returns `true` if the provider implements every operation of `request_trait`.
+    pub fn supports_all(&self, request_trait: RequestTrait) -> bool {
+        request_trait
+            .operations()
+            .iter()
+            .all(|op| self.operations.contains(op))
+    }
+
+    /// Returns `true` if the provider covers the market identified by `mic`.
+    pub fn covers_market(&self, mic: &str) -> bool {
+        match &self.markets {
+            Some(markets) => markets.contains(mic),
+            None => true,
+        }
+    }
+
+    /// Returns `true` if the provider returns values in the currency `code`.
+    pub fn covers_currency(&self, code: &str) -> bool {
+        self.currencies.is_empty() || self.currencies.contains(code)
+    }
+
+    /// The maximum number of symbols `operation` accepts in a single call, if limited.
+    pub fn max_batch_size(&self, operation: Operation) -> Option<usize> {
+        self.max_batch_sizes.get(&operation).cloned()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_capabilities() {
+        let capabilities = Capabilities::default();
+        assert!(!capabilities.supports(Operation::LatestPriceOnly));
+        assert!(!capabilities.supports_any(RequestTrait::FetchPriceQuote));
+        assert!(capabilities.covers_market("XNAS"));
+        assert!(capabilities.covers_currency("USD"));
+        assert_eq!(capabilities.max_batch_size(Operation::RealTimeBatch), None);
+    }
+
+    #[test]
+    fn test_operations() {
+        let mut capabilities = Capabilities::default();
+        capabilities.add_trait(RequestTrait::FetchPriceQuote);
+        capabilities.operations.insert(Operation::LatestNews);
+        assert!(capabilities.supports(Operation::LatestPriceOnly));
+        assert!(capabilities.supports_all(RequestTrait::FetchPriceQuote));
+        assert!(capabilities.supports_any(RequestTrait::FetchNews));
+        assert!(!capabilities.supports_all(RequestTrait::FetchNews));
+        for request_trait in &[RequestTrait::FetchPriceQuote, RequestTrait::FetchNews] {
+            for operation in request_trait.operations() {
+                assert_eq!(operation.request_trait(), *request_trait);
+            }
+        }
+    }
+
+    #[test]
+    fn test_markets_and_currencies() {
+        let capabilities = Capabilities {
+            markets: Some(["XNAS", "XNYS"].iter().map(|s| s.to_string()).collect()),
+            currencies: ["USD"].iter().map(|s| s.to_string()).collect(),
+            max_batch_sizes: [(Operation::RealTimeBatch, 100)].iter().cloned().collect(),
+            ..Default::default()
+        };
+        assert!(capabilities.covers_market("XNAS"));
+        assert!(!capabilities.covers_market("XLON"));
+        assert!(capabilities.covers_currency("USD"));
+        assert!(!capabilities.covers_currency("GBP"));
+        assert_eq!(
+            capabilities.max_batch_size(Operation::RealTimeBatch),
+            Some(100)
+        );
+    }
+}
@@ -17,6 +17,9 @@ The traits include the following capabilities:
 */
 use std::collections::HashMap;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::prelude::*;
 use crate::reporting::FinancialPeriod;
 
@@ -31,38 +34,52 @@ use crate::reporting::FinancialPeriod;
 /// is still open, in which case the value of `close`
 /// is the price at closing of the _previous trading day_, otherwise
 /// it is the price at closing of the same trading day.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PriceRange {
     /// price at market opening
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::money"))]
     pub open: Money,
     /// price at market closing
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::money"))]
     pub close: Money,
     /// highest price within the market window
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::money"))]
     pub high: Money,
     /// lowest price within the market window
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::money"))]
     pub low: Money,
     /// the (optional) volume of trading within the market window
     pub volume: Option<u64>,
 }
 
 /// A returned, real-time or delayed, price quote.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct QuotePrice {
     /// the current price
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::money"))]
     pub price: Money,
     /// the (optional) change in currency, since the last close
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::option_money"))]
     pub change: Option<Money>,
     /// the (optional) change, in percentage, since the last close
     pub percentage: Option<f64>,
 }
 
 /// A returned, delayed full quote quote.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct QuotePriceDelayed {
     /// the latest price
     pub latest: QuotePrice,
     /// number of minutes of delay (average)
     pub delayed_by: u16,
     /// highest price within the market window
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::money"))]
     pub high: Money,
     /// lowest price within the market window
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::money"))]
     pub low: Money,
     /// the (optional) number of trades at this latest price
     pub trade_size: Option<u64>,
@@ -77,6 +94,8 @@ pub type DelayedQuote = Snapshot<QuotePriceDelayed>;
 
 /// A complete price quote, includes price, range, and potentially
 /// extended hours trading data.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct QuotePriceFull {
     /// the latest price
     pub latest: QuotePrice,
@@ -94,7 +113,8 @@ pub struct QuotePriceFull {
 pub type Quote = Snapshot<QuotePriceFull>;
 
 /// Common intervals for quote series data.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SeriesInterval {
     Day,
     FiveDays,
@@ -125,21 +145,21 @@ pub trait FetchPriceQuote {
     /// the best available from the service provider.
     fn latest_price_only(&self, for_symbol: Symbol) -> RequestResult<Money>;
 
-    /// Return a real-time price, or `RequestError::Unsupported` if the service
+    /// Return a real-time price, or `RequestErrorKind::Unsupported` if the service
     /// provider cannot honor real-time requests.
     fn real_time(&self, for_symbol: Symbol) -> RequestResult<Quote>;
 
-    /// Return a delayed price, or `RequestError::Unsupported` if the service
+    /// Return a delayed price, or `RequestErrorKind::Unsupported` if the service
     /// provider does not provide delayed prices.
     fn delayed(&self, for_symbol: Symbol) -> RequestResult<DelayedQuote>;
 }
 
 pub trait FetchPriceQuoteBatch {
-    /// Return a real-time price, or `RequestError::Unsupported` if the service
+    /// Return a real-time price, or `RequestErrorKind::Unsupported` if the service
     /// provider cannot honor real-time requests.
     fn real_time(&self, for_symbols: Symbols) -> RequestResult<HashMap<Symbol, Quote>>;
 
-    /// Return a delayed price, or `RequestError::Unsupported` if the service
+    /// Return a delayed price, or `RequestErrorKind::Unsupported` if the service
     /// provider does not provide delayed prices.
     fn delayed(&self, for_symbols: Symbols) -> RequestResult<HashMap<Symbol, DelayedQuote>>;
 }
@@ -150,7 +170,7 @@ pub trait FetchPriceQuoteBatch {
 /// Not all providers may have an option for intra-day requests.
 pub trait FetchPriceRangeSeries {
     /// Return a series of intra-day prices for the current trading day, or
-    /// `RequestError::Unsupported` if the service provider does not provide
+    /// `RequestErrorKind::Unsupported` if the service provider does not provide
     /// intra-day data.
     fn intra_day(
         &self,
@@ -0,0 +1,517 @@
+/*!
+Provides financial ratios, and valuation multiples, calculated from reported
+company financials.
+
+The ratios are calculated from an `IncomeStatement` and a `BalanceSheet` for
+the same period; income statement values are totals for the period, so ratios
+such as return on equity calculated from a quarterly statement are quarterly
+values. Use annual statements, or trailing totals, for annual ratios. The
+valuation multiples also use the market capitalization in
+`company::Statistics`, and so should always be calculated from annual, or
+trailing twelve month, values.
+
+Statement values are converted to units, using the statement's reporting
+scale, before any calculation. Where a ratio's denominator is zero, or either
+value was not reported, the ratio is `None`, rather than infinite or `NaN`.
+Ratios combining income statement and balance sheet values are also `None`
+where the two statements are reported in different currencies. Negative
+denominators, such as negative shareholder equity, are not treated specially
+and so the resulting ratio may be negative.
+
+## Example
+
+```rust,no_run
+use fin_model::company::{FetchCompanyFinancials, FetchCompanyStatistics, ReportingPeriod};
+use fin_model::ratios::{financial_ratios, valuation_multiples};
+# use fin_model::prelude::*;
+# fn example(provider: &(impl FetchCompanyFinancials + FetchCompanyStatistics)) -> RequestResult<()> {
+let symbol: Symbol = "AAPL".parse().unwrap();
+
+let income = provider.reported_income(symbol.clone(), 1, ReportingPeriod::Annual)?;
+let balance = provider.reported_balance_sheet(symbol.clone(), 1, ReportingPeriod::Annual)?;
+let cash_flow = provider.reported_cash_flow(symbol.clone(), 1, ReportingPeriod::Annual)?;
+let statistics = provider.core_statistics(symbol)?;
+
+let ratios = financial_ratios(&income[0], &balance[0]);
+println!("ROE {:?}, current ratio {:?}", ratios.return_on_equity, ratios.current_ratio);
+
+let multiples = valuation_multiples(&statistics, &income[0], &balance[0], cash_flow.first());
+println!("P/B {:?}, EV/EBITDA {:?}", multiples.price_to_book, multiples.ev_to_ebitda);
+# Ok(())
+# }
+```
+*/
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::company::{BalanceSheet, CashFlowStatement, IncomeStatement, Statistics};
+use crate::prelude::*;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// Profitability, liquidity, leverage, and efficiency ratios for a single period.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FinancialRatios {
+    /// gross profit / total revenue
+    pub gross_margin: Option<f64>,
+    /// operating income / total revenue
+    pub operating_margin: Option<f64>,
+    /// net income / total revenue
+    pub net_margin: Option<f64>,
+    /// net income / total shareholder equity
+    pub return_on_equity: Option<f64>,
+    /// net income / total assets
+    pub return_on_assets: Option<f64>,
+    /// operating income after tax / (shareholder equity + debt - cash)
+    pub return_on_invested_capital: Option<f64>,
+    /// current assets / current liabilities
+    pub current_ratio: Option<f64>,
+    /// (cash + short-term investments + receivables) / current liabilities
+    pub quick_ratio: Option<f64>,
+    /// (long-term debt + current portion of long-term debt) / shareholder equity
+    pub debt_to_equity: Option<f64>,
+    /// earnings before interest and tax / interest expense
+    pub interest_coverage: Option<f64>,
+    /// total revenue / total assets
+    pub asset_turnover: Option<f64>,
+    /// cost of revenue / inventory
+    pub inventory_turnover: Option<f64>,
+}
+
+/// Valuation multiples combining market capitalization with reported financials.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ValuationMultiples {
+    /// market capitalization + debt + minority interest + preferred stock -
+    /// cash and short-term investments, `None` without a market capitalization
+    pub enterprise_value: Option<i64>,
+    /// market capitalization / net income
+    pub price_to_earnings: Option<f64>,
+    /// market capitalization / total shareholder equity
+    pub price_to_book: Option<f64>,
+    /// market capitalization / total revenue
+    pub price_to_sales: Option<f64>,
+    /// enterprise value / total revenue
+    pub ev_to_sales: Option<f64>,
+    /// enterprise value / (earnings before interest and tax + depreciation),
+    /// `None` without a cash flow statement to provide depreciation
+    pub ev_to_ebitda: Option<f64>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+/// Calculate the financial ratios for the period covered by `income` and `balance`.
+pub fn financial_ratios(
+    income: &Snapshot<IncomeStatement>,
+    balance: &Snapshot<BalanceSheet>,
+) -> FinancialRatios {
+    let income = &income.data;
+    let balance = &balance.data;
+    let i = |value: Option<i64>| income.info.in_units(value);
+    let b = |value: Option<i64>| balance.info.in_units(value);
+    // ratios of income to balances require both in the same currency.
+    let both = |value: Option<i64>| {
+        if income.info.currency == balance.info.currency {
+            b(value)
+        } else {
+            None
+        }
+    };
+    FinancialRatios {
+        gross_margin: ratio(i(income.gross_profit), i(income.total_revenue)),
+        operating_margin: ratio(i(income.operating_income), i(income.total_revenue)),
+        net_margin: ratio(i(income.net_income), i(income.total_revenue)),
+        return_on_equity: ratio(i(income.net_income), both(balance.total_shareholder_equity)),
+        return_on_assets: ratio(i(income.net_income), both(balance.total_assets)),
+        return_on_invested_capital: return_on_invested_capital(income, balance),
+        current_ratio: ratio(
+            b(balance.total_current_assets),
+            b(balance.total_current_liabilities),
+        ),
+        quick_ratio: ratio(
+            sum(&[
+                b(balance.current_cash),
+                b(balance.short_term_investments),
+                b(balance.net_receivables),
+            ]),
+            b(balance.total_current_liabilities),
+        ),
+        debt_to_equity: ratio(total_debt(balance), b(balance.total_shareholder_equity)),
+        // interest expense is reported as both a positive and negative value.
+        interest_coverage: ratio(
+            i(income.earnings_before_interest_and_tax),
+            i(income.interest_expense).map(i64::abs),
+        ),
+        asset_turnover: ratio(i(income.total_revenue), both(balance.total_assets)),
+        inventory_turnover: ratio(i(income.cost_of_revenue), both(balance.inventory)),
+    }
+}
+
+/// Calculate valuation multiples from the market capitalization in `statistics`
+/// and the annual, or trailing twelve month, `income`, `balance`, and
+/// (optional) `cash_flow` statements. Multiples are `None` where the market
+/// capitalization is not known, or is in a different currency to the statement;
+/// EV/EBITDA is also `None` if `cash_flow` is in a different currency to `income`.
+///
+/// Debt, minority interest, preferred stock, cash, and short-term investments
+/// not reported on the balance sheet are taken to be zero when calculating the
+/// enterprise value.
+pub fn valuation_multiples(
+    statistics: &Statistics,
+    income: &Snapshot<IncomeStatement>,
+    balance: &Snapshot<BalanceSheet>,
+    cash_flow: Option<&Snapshot<CashFlowStatement>>,
+) -> ValuationMultiples {
+    let income = &income.data;
+    let balance = &balance.data;
+    let i = |value: Option<i64>| income.info.in_units(value);
+    let b = |value: Option<i64>| balance.info.in_units(value).unwrap_or_default();
+    let market_cap = |currency: &String| {
+        if *currency == statistics.currency {
+            statistics.market_cap
+        } else {
+            None
+        }
+    };
+    let enterprise_value = market_cap(&balance.info.currency).map(|market_cap| {
+        market_cap
+            + total_debt(balance).unwrap_or_default()
+            + b(balance.minority_interest)
+            + b(balance.preferred_stock)
+            - b(balance.current_cash)
+            - b(balance.short_term_investments)
+    });
+    let enterprise_value_for = |currency: &String| {
+        if *currency == balance.info.currency {
+            enterprise_value
+        } else {
+            None
+        }
+    };
+    // depreciation is added to EBIT only if both are in the same currency.
+    let ebitda = match cash_flow {
+        Some(cash_flow) if cash_flow.data.info.currency == income.info.currency => match (
+            i(income.earnings_before_interest_and_tax),
+            cash_flow.data.info.in_units(cash_flow.data.depreciation),
+        ) {
+            (Some(ebit), Some(depreciation)) => Some(ebit + depreciation),
+            _ => None,
+        },
+        _ => None,
+    };
+    ValuationMultiples {
+        enterprise_value,
+        price_to_earnings: ratio(market_cap(&income.info.currency), i(income.net_income)),
+        price_to_book: ratio(
+            market_cap(&balance.info.currency),
+            balance.info.in_units(balance.total_shareholder_equity),
+        ),
+        price_to_sales: ratio(market_cap(&income.info.currency), i(income.total_revenue)),
+        ev_to_sales: ratio(
+            enterprise_value_for(&income.info.currency),
+            i(income.total_revenue),
+        ),
+        ev_to_ebitda: ratio(enterprise_value_for(&income.info.currency), ebitda),
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn ratio(numerator: Option<i64>, denominator: Option<i64>) -> Option<f64> {
+    match (numerator, denominator) {
+        (Some(_), Some(0)) => None,
+        (Some(numerator), Some(denominator)) => Some(numerator as f64 / denominator as f64),
+        _ => None,
+    }
+}
+
+/// The sum of the reported values; `None` if none were reported.
+fn sum(values: &[Option<i64>]) -> Option<i64> {
+    values
+        .iter()
+        .fold(None, |total, value| match (total, value) {
+            (Some(total), Some(value)) => Some(total + value),
+            (None, Some(value)) => Some(*value),
+            (total, None) => total,
+        })
+}
+
+fn total_debt(balance: &BalanceSheet) -> Option<i64> {
+    sum(&[
+        balance.info.in_units(balance.long_term_debt),
+        balance.info.in_units(balance.current_long_term_debt),
+    ])
+}
+
+/// The effective tax rate is used to calculate operating income after tax;
+/// where there is no tax, or no pre-tax earnings, the rate is taken as zero.
+fn return_on_invested_capital(income: &IncomeStatement, balance: &BalanceSheet) -> Option<f64> {
+    if income.info.currency != balance.info.currency {
+        return None;
+    }
+    let i = |value: Option<i64>| income.info.in_units(value);
+    let earnings_before_tax = i(income.earnings_before_tax);
+    let tax_rate = match ratio(i(income.income_tax_expense), earnings_before_tax) {
+        Some(rate) if earnings_before_tax.unwrap_or_default() > 0 => rate.clamp(0.0, 1.0),
+        _ => 0.0,
+    };
+    let cash = balance
+        .info
+        .in_units(balance.current_cash)
+        .unwrap_or_default();
+    let invested_capital = balance
+        .info
+        .in_units(balance.total_shareholder_equity)
+        .map(|equity| equity + total_debt(balance).unwrap_or_default() - cash);
+    ratio(i(income.operating_income), invested_capital).map(|r| r * (1.0 - tax_rate))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::company::{ReportingScale, StatementInfo};
+    use crate::reporting::FiscalPeriod;
+    use steel_cent::currency::USD;
+
+    fn snapshot<T>(data: T) -> Snapshot<T> {
+        Snapshot {
+            date: Date::from_ymd(2019, 9, 28).and_hms(0, 0, 0),
+            data,
+        }
+    }
+
+    fn info() -> StatementInfo {
+        StatementInfo {
+            currency: "USD".to_string(),
+            scale: ReportingScale::Units,
+            fiscal_period: FiscalPeriod::year_for_date(
+                Date::from_ymd(2019, 9, 28),
+                Date::from_ymd(2018, 10, 1),
+            ),
+            filing_date: None,
+            restated: false,
+        }
+    }
+
+    fn income() -> IncomeStatement {
+        IncomeStatement {
+            info: info(),
+            total_revenue: Some(1_000),
+            cost_of_revenue: Some(600),
+            gross_profit: Some(400),
+            research_and_development: Some(50),
+            sales_and_administration: Some(100),
+            non_recurring: Some(0),
+            other_operating_expense: Some(0),
+            operating_income: Some(250),
+            additional_income_or_expense: Some(0),
+            earnings_before_interest_and_tax: Some(250),
+            interest_expense: Some(-25),
+            earnings_before_tax: Some(225),
+            income_tax_expense: Some(45),
+            minority_interest: Some(0),
+            equity_earnings: Some(0),
+            net_income: Some(180),
+            net_income_to_shareholders: Some(180),
+        }
+    }
+
+    fn balance() -> BalanceSheet {
+        BalanceSheet {
+            info: info(),
+            current_cash: Some(100),
+            short_term_investments: Some(50),
+            net_receivables: Some(150),
+            inventory: Some(120),
+            other_current_assets: Some(80),
+            total_current_assets: Some(500),
+            long_term_investments: Some(0),
+            property_plant_equipment: Some(1_000),
+            goodwill: Some(0),
+            intangible_assets: Some(0),
+            accumulated_amortization: Some(0),
+            other_assets: Some(500),
+            deferred_asset_charges: Some(0),
+            total_assets: Some(2_000),
+            accounts_payable: Some(150),
+            current_long_term_debt: Some(50),
+            other_current_liabilities: Some(50),
+            total_current_liabilities: Some(250),
+            long_term_debt: Some(350),
+            other_liabilities: Some(200),
+            deferred_liability_charges: Some(0),
+            minority_interest: Some(0),
+            total_liabilities: Some(800),
+            redeemable_preferred_stock: Some(0),
+            preferred_stock: Some(0),
+            common_stock: Some(700),
+            retained_earnings: Some(500),
+            treasury_stock: Some(0),
+            capital_surplus: Some(0),
+            other_shareholder_equity: Some(0),
+            total_shareholder_equity: Some(1_200),
+            net_tangible_assets: Some(1_200),
+        }
+    }
+
+    fn assert_near(actual: Option<f64>, expected: f64) {
+        let actual = actual.unwrap();
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} is not {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_financial_ratios() {
+        let ratios = financial_ratios(&snapshot(income()), &snapshot(balance()));
+        assert_near(ratios.gross_margin, 0.4);
+        assert_near(ratios.operating_margin, 0.25);
+        assert_near(ratios.net_margin, 0.18);
+        assert_near(ratios.return_on_equity, 0.15);
+        assert_near(ratios.return_on_assets, 0.09);
+        // 250 * (1 - 0.2) / (1200 + 400 - 100)
+        assert_near(ratios.return_on_invested_capital, 200.0 / 1_500.0);
+        assert_near(ratios.current_ratio, 2.0);
+        assert_near(ratios.quick_ratio, 1.2);
+        assert_near(ratios.debt_to_equity, 400.0 / 1_200.0);
+        assert_near(ratios.interest_coverage, 10.0);
+        assert_near(ratios.asset_turnover, 0.5);
+        assert_near(ratios.inventory_turnover, 5.0);
+    }
+
+    #[test]
+    fn test_zero_denominators() {
+        let mut income = income();
+        income.total_revenue = Some(0);
+        income.interest_expense = None;
+        let mut balance = balance();
+        balance.inventory = Some(0);
+        balance.total_current_liabilities = None;
+        let ratios = financial_ratios(&snapshot(income), &snapshot(balance));
+        assert_eq!(ratios.gross_margin, None);
+        assert_eq!(ratios.net_margin, None);
+        assert_eq!(ratios.current_ratio, None);
+        assert_eq!(ratios.quick_ratio, None);
+        assert_eq!(ratios.interest_coverage, None);
+        assert_eq!(ratios.inventory_turnover, None);
+        assert!(ratios.return_on_equity.is_some());
+    }
+
+    #[test]
+    fn test_scale_and_currency() {
+        let mut balance = balance();
+        balance.info.scale = ReportingScale::Thousands;
+        balance.total_shareholder_equity = Some(1);
+        balance.total_assets = Some(2);
+        let ratios = financial_ratios(&snapshot(income()), &snapshot(balance.clone()));
+        assert_near(ratios.return_on_equity, 0.18);
+        assert_near(ratios.return_on_assets, 0.09);
+        assert_near(ratios.current_ratio, 2.0);
+
+        balance.info.currency = "EUR".to_string();
+        let ratios = financial_ratios(&snapshot(income()), &snapshot(balance));
+        assert_eq!(ratios.return_on_equity, None);
+        assert_eq!(ratios.return_on_invested_capital, None);
+        assert_near(ratios.current_ratio, 2.0);
+        assert_near(ratios.gross_margin, 0.4);
+    }
+
+    #[test]
+    fn test_valuation_multiples() {
+        let mut statistics = Statistics {
+            currency: "USD".to_string(),
+            shares_outstanding: Some(100),
+            market_cap: Some(3_600),
+            share_float: Some(100),
+            pe_ratio: Some(20.0),
+            beta: None,
+            t52w_high: Some(Money::of_major(USD, 40)),
+            t52w_low: Some(Money::of_major(USD, 30)),
+            t52w_change: None,
+            t10d_average_volume: None,
+            t30d_average_volume: None,
+            t50d_moving_average: Some(Money::of_major(USD, 36)),
+            t200d_moving_average: Some(Money::of_major(USD, 35)),
+            t12m_eps: Some(Money::of_major_minor(USD, 1, 80)),
+            t12m_dividend_rate: None,
+            dividend_yield: None,
+            last_ex_dividend_date: None,
+            next_ex_dividend_date: None,
+            next_earnings_date: Some(Date::from_ymd(2019, 10, 30)),
+            t5d_change_percentage: None,
+            t30d_change_percentage: None,
+            t1m_change_percentage: None,
+            t3m_change_percentage: None,
+            t6m_change_percentage: None,
+            t1y_change_percentage: None,
+            t2y_change_percentage: None,
+            t5y_change_percentage: None,
+            ytd_change_percentage: None,
+            max_change_percentage: None,
+        };
+        let cash_flow = snapshot(CashFlowStatement {
+            info: info(),
+            net_income: Some(180),
+            depreciation: Some(100),
+            changes_in_receivables: Some(0),
+            changes_in_inventories: Some(0),
+            other_operating_activities: Some(0),
+            operating_cash_flow: Some(280),
+            capital_expenditures: Some(-80),
+            investments: Some(0),
+            other_investing_activities: Some(0),
+            investing_cash_flow: Some(-80),
+            dividends_paid: Some(-50),
+            net_borrowings: Some(0),
+            other_financing_activities: Some(0),
+            financing_cash_flow: Some(-50),
+            exchange_rate_effect: Some(0),
+            net_change_in_cash: Some(150),
+        });
+        let (income, balance) = (snapshot(income()), snapshot(balance()));
+
+        let multiples = valuation_multiples(&statistics, &income, &balance, Some(&cash_flow));
+        // 3600 + 400 - 100 - 50
+        assert_eq!(multiples.enterprise_value, Some(3_850));
+        assert_near(multiples.price_to_earnings, 20.0);
+        assert_near(multiples.price_to_book, 3.0);
+        assert_near(multiples.price_to_sales, 3.6);
+        assert_near(multiples.ev_to_sales, 3.85);
+        assert_near(multiples.ev_to_ebitda, 11.0);
+
+        let multiples = valuation_multiples(&statistics, &income, &balance, None);
+        assert_eq!(multiples.ev_to_ebitda, None);
+
+        let mut other_currency = cash_flow.clone();
+        other_currency.data.info.currency = "EUR".to_string();
+        let multiples = valuation_multiples(&statistics, &income, &balance, Some(&other_currency));
+        assert_eq!(multiples.ev_to_ebitda, None);
+        assert_near(multiples.ev_to_sales, 3.85);
+
+        statistics.currency = "EUR".to_string();
+        let multiples = valuation_multiples(&statistics, &income, &balance, None);
+        assert_eq!(multiples.enterprise_value, None);
+        assert_eq!(multiples.price_to_earnings, None);
+
+        statistics.currency = "USD".to_string();
+        statistics.market_cap = None;
+        let multiples = valuation_multiples(&statistics, &income, &balance, None);
+        assert_eq!(multiples.enterprise_value, None);
+        assert_eq!(multiples.price_to_sales, None);
+    }
+}
@@ -2,7 +2,8 @@
 Provides common types for reporting data.
 
 Primarily this module provides the `FinancialPeriod` type that can represent
-periods such as "Q2 2019", "H1 2018", or "2020". This period may be used as a
+periods such as "Q2 2019", "H1 2018", "2020", the month "2019-06", the ISO
+week "2019-W23", or the trailing twelve months "TTM 2019-06". This period may be used as a
 calendar period where the assumption is that the periods are relative to
 January 1st, or as a fiscal period where the start of a fiscal year may not
 align with calendar years. For this latter case the `FiscalPeriod` struct
@@ -10,14 +11,34 @@ also contains a date for the fiscal year start.
 
 The financial period type implements both `fmt::Display` and `str::FromStr`
 and so supports the ability to read and write the period as a string in a
-common manner.
+common manner. The parser also accepts common provider notations such as
+"FY2019", "Q3 FY20", and "TTM Q2 2019".
+
+Both types also support date arithmetic; the dates a period covers, whether
+a date falls within a period, and moving to the next or previous period.
+These operations return `None` for periods that are not valid.
+
+## Example
+
+```rust
+use fin_model::prelude::Date;
+use fin_model::reporting::{FinancialPeriod, FiscalPeriod};
+
+let period = FiscalPeriod::for_date(Date::from_ymd(2019, 8, 15), Date::from_ymd(2019, 4, 1));
+assert_eq!(period.period, FinancialPeriod::Quarter { quarter: 2, year: 2020 });
+assert_eq!(period.start_date(), Some(Date::from_ymd(2019, 7, 1)));
+assert_eq!(period.end_date(), Some(Date::from_ymd(2019, 9, 30)));
+```
 */
 
 use std::fmt;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use chrono::{Datelike, Duration, Weekday};
 use regex::Regex;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::prelude::*;
 
@@ -25,13 +46,15 @@ use crate::prelude::*;
 // Public Types
 // ------------------------------------------------------------------------------------------------
 
-/// Represents common financial periods, whole years, half years, and quarter
-/// years.
+/// Represents common financial periods, whole years, half years, quarter
+/// years, months, and weeks, as well as the trailing twelve months (TTM)
+/// ending with a given month.
 ///
 /// By default the assumption is that periods represent calendar time;
 /// for example, the values "2019", "Q1 2019", and "H1 2019" are all assumed
-/// to start on January 1st.
-#[derive(Debug, PartialEq)]
+/// to start on January 1st. Weeks are ISO 8601 weeks, starting on a Monday,
+/// and the year of a week is the ISO week-numbering year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum FinancialPeriod {
     Quarter {
         /// the quarter within the year (values: 1..4)
@@ -49,13 +72,37 @@ pub enum FinancialPeriod {
         /// the year itself (values: 1900..9999)
         year: u16,
     },
+    Month {
+        /// the month within the year (values: 1..12)
+        month: u8,
+        /// the year itself (values: 1900..9999)
+        year: u16,
+    },
+    Week {
+        /// the ISO week within the year (values: 1..53)
+        week: u8,
+        /// the ISO week-numbering year itself (values: 1900..9999)
+        year: u16,
+    },
+    TrailingTwelveMonths {
+        /// the last month of the trailing period (values: 1..12)
+        month: u8,
+        /// the year of the last month (values: 1900..9999)
+        year: u16,
+    },
 }
 
 /// Represents a fiscal periods, with a reference start date, allowing it
 /// to represent years that do not align with the calendar start date.
 ///
-/// For example, "Q1 2019" with a start date of April 1st ends on June 30th.
-#[derive(Debug, PartialEq)]
+/// A fiscal year is named for the calendar year in which it ends, so with a
+/// start date of April 1st the fiscal year "2019" runs from April 1st 2018
+/// to March 31st 2019, and "Q1 2019" ends on June 30th 2018. Only the month
+/// and day of `fiscal_year_start_date` are significant. Months, weeks, and
+/// trailing periods are always calendar periods and are not affected by the
+/// fiscal year start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FiscalPeriod {
     /// the period within the fiscal year
     pub period: FinancialPeriod,
@@ -63,13 +110,21 @@ pub struct FiscalPeriod {
     pub fiscal_year_start_date: Date,
 }
 
+/// An iterator over consecutive financial periods, returned from
+/// `FinancialPeriod::iter_to`.
+#[derive(Debug)]
+pub struct Periods {
+    next: Option<FinancialPeriod>,
+    last_date: Option<Date>,
+}
+
 // ------------------------------------------------------------------------------------------------
 // Trait Implementations
 // ------------------------------------------------------------------------------------------------
 
 impl FinancialPeriod {
-    /// Validate the period to ensure that quarter, half, and year
-    /// values are within correct ranges.
+    /// Validate the period to ensure that quarter, half, month, week, and
+    /// year values are within correct ranges.
     pub fn is_valid(&self) -> bool {
         match self {
             FinancialPeriod::Quarter { quarter, year } => {
@@ -79,6 +134,327 @@ impl FinancialPeriod {
                 *half >= 1 && *half <= 2 && is_valid_year(*year)
             }
             FinancialPeriod::Year { year } => is_valid_year(*year),
+            FinancialPeriod::Month { month, year }
+            | FinancialPeriod::TrailingTwelveMonths { month, year } => {
+                *month >= 1 && *month <= 12 && is_valid_year(*year)
+            }
+            FinancialPeriod::Week { week, year } => {
+                is_valid_year(*year) && *week >= 1 && *week <= weeks_in_year(*year)
+            }
+        }
+    }
+
+    /// The calendar quarter that contains `date`.
+    pub fn quarter_containing(date: Date) -> Self {
+        FinancialPeriod::Quarter {
+            quarter: ((date.month() - 1) / 3 + 1) as u8,
+            year: date.year() as u16,
+        }
+    }
+
+    /// The calendar half year that contains `date`.
+    pub fn half_containing(date: Date) -> Self {
+        FinancialPeriod::Half {
+            half: ((date.month() - 1) / 6 + 1) as u8,
+            year: date.year() as u16,
+        }
+    }
+
+    /// The calendar year that contains `date`.
+    pub fn year_containing(date: Date) -> Self {
+        FinancialPeriod::Year {
+            year: date.year() as u16,
+        }
+    }
+
+    /// The calendar month that contains `date`.
+    pub fn month_containing(date: Date) -> Self {
+        FinancialPeriod::Month {
+            month: date.month() as u8,
+            year: date.year() as u16,
+        }
+    }
+
+    /// The ISO week that contains `date`.
+    pub fn week_containing(date: Date) -> Self {
+        let week = date.iso_week();
+        FinancialPeriod::Week {
+            week: week.week() as u8,
+            year: week.year() as u16,
+        }
+    }
+
+    /// The twelve months ending with the last month of `period`; for example
+    /// the trailing period for "Q2 2019" runs from July 2018 to June 2019.
+    /// Returns `None` if `period` is not valid.
+    pub fn trailing_twelve_months(period: &FinancialPeriod) -> Option<Self> {
+        let end = period.end_date()?;
+        Some(FinancialPeriod::TrailingTwelveMonths {
+            month: end.month() as u8,
+            year: end.year() as u16,
+        })
+    }
+
+    /// The year this period falls within, for trailing periods this is the
+    /// year of the last month.
+    pub fn year(&self) -> u16 {
+        match self {
+            FinancialPeriod::Quarter { year, .. }
+            | FinancialPeriod::Half { year, .. }
+            | FinancialPeriod::Year { year }
+            | FinancialPeriod::Month { year, .. }
+            | FinancialPeriod::Week { year, .. }
+            | FinancialPeriod::TrailingTwelveMonths { year, .. } => *year,
+        }
+    }
+
+    /// The first calendar date within this period, or `None` if the period
+    /// is not valid.
+    pub fn start_date(&self) -> Option<Date> {
+        period_dates(self, 1, 1).map(|(start, _)| start)
+    }
+
+    /// The last calendar date within this period, or `None` if the period
+    /// is not valid.
+    pub fn end_date(&self) -> Option<Date> {
+        period_dates(self, 1, 1).map(|(_, end)| end)
+    }
+
+    /// Returns `true` if `date` falls within this period.
+    pub fn contains(&self, date: Date) -> bool {
+        match period_dates(self, 1, 1) {
+            Some((start, end)) => date >= start && date <= end,
+            None => false,
+        }
+    }
+
+    /// The period immediately following this one, of the same kind. For
+    /// trailing periods this is the period ending one month later. Returns
+    /// `None` if this period, or the following one, is not valid.
+    pub fn next(&self) -> Option<Self> {
+        if !self.is_valid() {
+            return None;
+        }
+        let next = match *self {
+            FinancialPeriod::Quarter { quarter, year } if quarter >= 4 => {
+                FinancialPeriod::Quarter {
+                    quarter: 1,
+                    year: year.checked_add(1)?,
+                }
+            }
+            FinancialPeriod::Quarter { quarter, year } => FinancialPeriod::Quarter {
+                quarter: quarter + 1,
+                year,
+            },
+            FinancialPeriod::Half { half, year } if half >= 2 => FinancialPeriod::Half {
+                half: 1,
+                year: year.checked_add(1)?,
+            },
+            FinancialPeriod::Half { half, year } => FinancialPeriod::Half {
+                half: half + 1,
+                year,
+            },
+            FinancialPeriod::Year { year } => FinancialPeriod::Year {
+                year: year.checked_add(1)?,
+            },
+            FinancialPeriod::Month { month, year } if month >= 12 => FinancialPeriod::Month {
+                month: 1,
+                year: year.checked_add(1)?,
+            },
+            FinancialPeriod::Month { month, year } => FinancialPeriod::Month {
+                month: month + 1,
+                year,
+            },
+            FinancialPeriod::Week { week, year } if week >= weeks_in_year(year) => {
+                FinancialPeriod::Week {
+                    week: 1,
+                    year: year.checked_add(1)?,
+                }
+            }
+            FinancialPeriod::Week { week, year } => FinancialPeriod::Week {
+                week: week + 1,
+                year,
+            },
+            FinancialPeriod::TrailingTwelveMonths { month, year } if month >= 12 => {
+                FinancialPeriod::TrailingTwelveMonths {
+                    month: 1,
+                    year: year.checked_add(1)?,
+                }
+            }
+            FinancialPeriod::TrailingTwelveMonths { month, year } => {
+                FinancialPeriod::TrailingTwelveMonths {
+                    month: month + 1,
+                    year,
+                }
+            }
+        };
+        Some(next).filter(FinancialPeriod::is_valid)
+    }
+
+    /// The period immediately preceding this one, of the same kind. For
+    /// trailing periods this is the period ending one month earlier. Returns
+    /// `None` if this period, or the preceding one, is not valid.
+    pub fn previous(&self) -> Option<Self> {
+        if !self.is_valid() {
+            return None;
+        }
+        let previous = match *self {
+            FinancialPeriod::Quarter { quarter, year } if quarter <= 1 => {
+                FinancialPeriod::Quarter {
+                    quarter: 4,
+                    year: year.checked_sub(1)?,
+                }
+            }
+            FinancialPeriod::Quarter { quarter, year } => FinancialPeriod::Quarter {
+                quarter: quarter - 1,
+                year,
+            },
+            FinancialPeriod::Half { half, year } if half <= 1 => FinancialPeriod::Half {
+                half: 2,
+                year: year.checked_sub(1)?,
+            },
+            FinancialPeriod::Half { half, year } => FinancialPeriod::Half {
+                half: half - 1,
+                year,
+            },
+            FinancialPeriod::Year { year } => FinancialPeriod::Year {
+                year: year.checked_sub(1)?,
+            },
+            FinancialPeriod::Month { month, year } if month <= 1 => FinancialPeriod::Month {
+                month: 12,
+                year: year.checked_sub(1)?,
+            },
+            FinancialPeriod::Month { month, year } => FinancialPeriod::Month {
+                month: month - 1,
+                year,
+            },
+            FinancialPeriod::Week { week, year } if week <= 1 => {
+                let year = year.checked_sub(1)?;
+                FinancialPeriod::Week {
+                    week: weeks_in_year(year),
+                    year,
+                }
+            }
+            FinancialPeriod::Week { week, year } => FinancialPeriod::Week {
+                week: week - 1,
+                year,
+            },
+            FinancialPeriod::TrailingTwelveMonths { month, year } if month <= 1 => {
+                FinancialPeriod::TrailingTwelveMonths {
+                    month: 12,
+                    year: year.checked_sub(1)?,
+                }
+            }
+            FinancialPeriod::TrailingTwelveMonths { month, year } => {
+                FinancialPeriod::TrailingTwelveMonths {
+                    month: month - 1,
+                    year,
+                }
+            }
+        };
+        Some(previous).filter(FinancialPeriod::is_valid)
+    }
+
+    /// Iterate over consecutive periods, of the same kind as this one, up to and
+    /// including the period that contains the end date of `last`. For example,
+    /// iterating from "Q1 2019" to "2019" returns the four quarters of 2019.
+    /// The iterator is empty if either period is not valid.
+    pub fn iter_to(&self, last: &FinancialPeriod) -> Periods {
+        Periods {
+            next: Some(*self),
+            last_date: last.end_date(),
+        }
+    }
+}
+
+impl FiscalPeriod {
+    /// The fiscal quarter that contains `date`, for a fiscal year that starts on
+    /// the month and day of `fiscal_year_start`.
+    pub fn for_date(date: Date, fiscal_year_start: Date) -> Self {
+        let year = fiscal_year_containing(date, fiscal_year_start);
+        let (month, day) = (fiscal_year_start.month(), fiscal_year_start.day());
+        let period = (1..=4)
+            .rev()
+            .map(|quarter| FinancialPeriod::Quarter { quarter, year })
+            .find(|period| {
+                period_dates(period, month, day)
+                    .filter(|(start, _)| *start <= date)
+                    .is_some()
+            })
+            .unwrap_or(FinancialPeriod::Quarter { quarter: 1, year });
+        FiscalPeriod {
+            period,
+            fiscal_year_start_date: fiscal_year_start,
+        }
+    }
+
+    /// The fiscal year that contains `date`, for a fiscal year that starts on
+    /// the month and day of `fiscal_year_start`.
+    pub fn year_for_date(date: Date, fiscal_year_start: Date) -> Self {
+        FiscalPeriod {
+            period: FinancialPeriod::Year {
+                year: fiscal_year_containing(date, fiscal_year_start),
+            },
+            fiscal_year_start_date: fiscal_year_start,
+        }
+    }
+
+    /// The first calendar date within this fiscal period, or `None` if the
+    /// period is not valid.
+    pub fn start_date(&self) -> Option<Date> {
+        self.dates().map(|(start, _)| start)
+    }
+
+    /// The last calendar date within this fiscal period, or `None` if the
+    /// period is not valid.
+    pub fn end_date(&self) -> Option<Date> {
+        self.dates().map(|(_, end)| end)
+    }
+
+    /// Returns `true` if `date` falls within this fiscal period.
+    pub fn contains(&self, date: Date) -> bool {
+        match self.dates() {
+            Some((start, end)) => date >= start && date <= end,
+            None => false,
+        }
+    }
+
+    /// The fiscal period immediately following this one, of the same kind.
+    pub fn next(&self) -> Option<Self> {
+        Some(FiscalPeriod {
+            period: self.period.next()?,
+            fiscal_year_start_date: self.fiscal_year_start_date,
+        })
+    }
+
+    /// The fiscal period immediately preceding this one, of the same kind.
+    pub fn previous(&self) -> Option<Self> {
+        Some(FiscalPeriod {
+            period: self.period.previous()?,
+            fiscal_year_start_date: self.fiscal_year_start_date,
+        })
+    }
+
+    fn dates(&self) -> Option<(Date, Date)> {
+        period_dates(
+            &self.period,
+            self.fiscal_year_start_date.month(),
+            self.fiscal_year_start_date.day(),
+        )
+    }
+}
+
+impl Iterator for Periods {
+    type Item = FinancialPeriod;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let period = self.next.take()?;
+        match (period.start_date(), self.last_date) {
+            (Some(start), Some(last_date)) if start <= last_date => {
+                self.next = period.next();
+                Some(period)
+            }
+            _ => None,
         }
     }
 }
@@ -89,6 +465,11 @@ impl Display for FinancialPeriod {
             FinancialPeriod::Quarter { quarter, year } => write!(f, "Q{} {}", quarter, year),
             FinancialPeriod::Half { half, year } => write!(f, "H{} {}", half, year),
             FinancialPeriod::Year { year } => write!(f, "{}", year),
+            FinancialPeriod::Month { month, year } => write!(f, "{}-{:02}", year, month),
+            FinancialPeriod::Week { week, year } => write!(f, "{}-W{:02}", year, week),
+            FinancialPeriod::TrailingTwelveMonths { month, year } => {
+                write!(f, "TTM {}-{:02}", year, month)
+            }
         }
     }
 }
@@ -102,41 +483,86 @@ pub enum ParseError {
     InvalidPeriodString,
     /// the string parsed correctly but failed validation
     InvalidPeriodValue,
+    /// a two digit year was used without the fiscal year prefix, "FY"
+    AmbiguousYear,
+    /// a trailing period, "TTM", was used without the period it ends with
+    MissingTrailingEnd,
+    /// a trailing period, "TTM", was used to end another trailing period
+    NestedTrailingPeriod,
 }
 
 impl FromStr for FinancialPeriod {
     type Err = ParseError;
 
+    /// Parse a financial period, the following notations are supported (case
+    /// is not significant):
+    ///
+    /// * years; `2019`, `FY2019`, `FY 2019`, `FY19`
+    /// * half years; `H1 2019`, `H1-2019`, `H1 FY2019`, `H1 FY19`
+    /// * quarters; `Q3 2019`, `Q3-2019`, `Q3 FY2019`, `Q3 FY19`, `3Q19`, `3Q 2019`
+    /// * months; `2019-06`
+    /// * ISO weeks; `2019-W23`
+    /// * trailing twelve months; `TTM`, followed by any of the above, for
+    ///   example `TTM Q2 2019` or `TTM 2019-06`
+    ///
+    /// Two digit years are only accepted with the fiscal year prefix and
+    /// are assumed to be in the 21st century.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static! {
-            static ref RE: Regex = Regex::new(r"^(([QH])(\d) )?(\d{4})$").unwrap();
+            static ref RE_PERIOD: Regex =
+                Regex::new(r"^(?:([QH])([0-9])[ \-]?)?(FY ?)?([0-9]{4}|[0-9]{2})$").unwrap();
+            static ref RE_SHORT: Regex = Regex::new(r"^([0-9])Q ?([0-9]{4}|[0-9]{2})$").unwrap();
+            static ref RE_MONTH: Regex = Regex::new(r"^([0-9]{4})-([0-9]{2})$").unwrap();
+            static ref RE_WEEK: Regex = Regex::new(r"^([0-9]{4})-W([0-9]{2})$").unwrap();
+            static ref RE_TTM: Regex = Regex::new(r"^TTM(?:[ \-](.*))?$").unwrap();
         }
 
+        let s = s.trim().to_ascii_uppercase();
         if s.is_empty() {
             return Err(ParseError::EmptyString);
         }
 
-        let period = match RE.captures(s) {
-            None => Err(ParseError::InvalidPeriodString),
-            Some(captures) => {
-                if let Some(period) = captures.get(2) {
-                    if period.as_str() == "Q" {
-                        Ok(FinancialPeriod::Quarter {
-                            quarter: u8::from_str(&captures[3]).unwrap(),
-                            year: u16::from_str(&captures[4]).unwrap(),
-                        })
-                    } else {
-                        Ok(FinancialPeriod::Half {
-                            half: u8::from_str(&captures[3]).unwrap(),
-                            year: u16::from_str(&captures[4]).unwrap(),
-                        })
+        let period = if let Some(captures) = RE_TTM.captures(&s) {
+            match captures.get(1) {
+                None => Err(ParseError::MissingTrailingEnd),
+                Some(end) => match FinancialPeriod::from_str(end.as_str())? {
+                    FinancialPeriod::TrailingTwelveMonths { .. } => {
+                        Err(ParseError::NestedTrailingPeriod)
                     }
-                } else {
-                    Ok(FinancialPeriod::Year {
-                        year: u16::from_str(&captures[4]).unwrap(),
-                    })
-                }
+                    end => FinancialPeriod::trailing_twelve_months(&end)
+                        .ok_or(ParseError::InvalidPeriodValue),
+                },
             }
+        } else if let Some(captures) = RE_PERIOD.captures(&s) {
+            let year = parse_year(&captures[4], captures.get(3).is_some())?;
+            match captures.get(1).map(|m| m.as_str()) {
+                Some("Q") => Ok(FinancialPeriod::Quarter {
+                    quarter: u8::from_str(&captures[2]).unwrap(),
+                    year,
+                }),
+                Some(_) => Ok(FinancialPeriod::Half {
+                    half: u8::from_str(&captures[2]).unwrap(),
+                    year,
+                }),
+                None => Ok(FinancialPeriod::Year { year }),
+            }
+        } else if let Some(captures) = RE_SHORT.captures(&s) {
+            Ok(FinancialPeriod::Quarter {
+                quarter: u8::from_str(&captures[1]).unwrap(),
+                year: parse_year(&captures[2], true)?,
+            })
+        } else if let Some(captures) = RE_MONTH.captures(&s) {
+            Ok(FinancialPeriod::Month {
+                month: u8::from_str(&captures[2]).unwrap(),
+                year: u16::from_str(&captures[1]).unwrap(),
+            })
+        } else if let Some(captures) = RE_WEEK.captures(&s) {
+            Ok(FinancialPeriod::Week {
+                week: u8::from_str(&captures[2]).unwrap(),
+                year: u16::from_str(&captures[1]).unwrap(),
+            })
+        } else {
+            Err(ParseError::InvalidPeriodString)
         };
         match period {
             Err(e) => Err(e),
@@ -151,6 +577,51 @@ impl FromStr for FinancialPeriod {
     }
 }
 
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::EmptyString => write!(f, "period string is empty"),
+            ParseError::InvalidPeriodString => write!(f, "period string is not recognized"),
+            ParseError::InvalidPeriodValue => write!(f, "period value is out of range"),
+            ParseError::AmbiguousYear => {
+                write!(f, "two digit years require the fiscal year prefix 'FY'")
+            }
+            ParseError::MissingTrailingEnd => {
+                write!(f, "trailing period 'TTM' requires the period it ends with")
+            }
+            ParseError::NestedTrailingPeriod => {
+                write!(
+                    f,
+                    "trailing period 'TTM' cannot end with another trailing period"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for FinancialPeriod {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for FinancialPeriod {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(crate::serialize::FromStrVisitor {
+            expecting: "a financial period string such as 'Q2 2019'",
+            marker: std::marker::PhantomData,
+        })
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 // Private Functions
 // ------------------------------------------------------------------------------------------------
@@ -159,13 +630,103 @@ fn is_valid_year(year: u16) -> bool {
     year >= 1900 && year <= 9999
 }
 
+fn parse_year(year: &str, fiscal: bool) -> Result<u16, ParseError> {
+    let value = u16::from_str(year).unwrap();
+    if year.len() == 2 {
+        if fiscal {
+            Ok(2000 + value)
+        } else {
+            Err(ParseError::AmbiguousYear)
+        }
+    } else {
+        Ok(value)
+    }
+}
+
+/// The number of ISO weeks in the ISO week-numbering `year`, either 52 or 53.
+fn weeks_in_year(year: u16) -> u8 {
+    match Date::from_isoywd_opt(i32::from(year), 53, Weekday::Mon) {
+        Some(_) => 53,
+        None => 52,
+    }
+}
+
+/// The first and last dates of a period, or `None` if the period is not
+/// valid. Months, weeks, and trailing periods are always calendar periods
+/// and so ignore the fiscal year `month` and `day`.
+fn period_dates(period: &FinancialPeriod, month: u32, day: u32) -> Option<(Date, Date)> {
+    if !period.is_valid() {
+        return None;
+    }
+    match *period {
+        FinancialPeriod::Quarter { quarter, year } => {
+            fiscal_dates(year, month, day, 3 * (u32::from(quarter) - 1), 3)
+        }
+        FinancialPeriod::Half { half, year } => {
+            fiscal_dates(year, month, day, 6 * (u32::from(half) - 1), 6)
+        }
+        FinancialPeriod::Year { year } => fiscal_dates(year, month, day, 0, 12),
+        FinancialPeriod::Month { month, year } => fiscal_dates(year, 1, 1, u32::from(month) - 1, 1),
+        FinancialPeriod::Week { week, year } => {
+            let start = Date::from_isoywd_opt(i32::from(year), u32::from(week), Weekday::Mon)?;
+            Some((start, start.checked_add_signed(Duration::days(6))?))
+        }
+        FinancialPeriod::TrailingTwelveMonths { month, year } => {
+            fiscal_dates(year.checked_sub(1)?, 1, 1, u32::from(month), 12)
+        }
+    }
+}
+
+/// The first and last dates of the `length` months that begin `offset` months
+/// into the fiscal year `year`, which starts on `month` and `day`.
+fn fiscal_dates(year: u16, month: u32, day: u32, offset: u32, length: u32) -> Option<(Date, Date)> {
+    let start = fiscal_month_start(year, month, day, offset)?;
+    let end = fiscal_month_start(year, month, day, offset + length)?.pred_opt()?;
+    Some((start, end))
+}
+
+/// The date `months` after the start of the fiscal year `year`. Fiscal years
+/// are named for the calendar year they end in, so unless the fiscal year
+/// starts on January 1st it begins in the previous calendar year.
+fn fiscal_month_start(year: u16, month: u32, day: u32, months: u32) -> Option<Date> {
+    let months = (month - 1) + months;
+    let first_year = i32::from(year) - fiscal_year_offset(month, day);
+    date_clamped(first_year + (months / 12) as i32, months % 12 + 1, day)
+}
+
+fn fiscal_year_offset(month: u32, day: u32) -> i32 {
+    if month == 1 && day == 1 {
+        0
+    } else {
+        1
+    }
+}
+
+fn fiscal_year_containing(date: Date, fiscal_year_start: Date) -> u16 {
+    let (month, day) = (fiscal_year_start.month(), fiscal_year_start.day());
+    let first_year = match date_clamped(date.year(), month, day) {
+        Some(start) if date < start => date.year() - 1,
+        _ => date.year(),
+    };
+    (first_year + fiscal_year_offset(month, day)) as u16
+}
+
+/// Construct a date, moving the day back to the end of the month if it does
+/// not exist in the given month (for example, February 29th).
+fn date_clamped(year: i32, month: u32, day: u32) -> Option<Date> {
+    (1..=day)
+        .rev()
+        .find_map(|day| Date::from_ymd_opt(year, month, day))
+}
+
 // ------------------------------------------------------------------------------------------------
 // Unit Tests
 // ------------------------------------------------------------------------------------------------
 
 #[cfg(test)]
 mod tests {
-    use super::{is_valid_year, FinancialPeriod, ParseError};
+    use super::{is_valid_year, FinancialPeriod, FiscalPeriod, ParseError};
+    use crate::prelude::Date;
     use std::str::FromStr;
 
     #[test]
@@ -279,4 +840,304 @@ mod tests {
             ParseError::InvalidPeriodValue
         );
     }
+
+    #[test]
+    fn test_from_string_notations() {
+        let q3 = FinancialPeriod::Quarter {
+            quarter: 3,
+            year: 2020,
+        };
+        assert_eq!(FinancialPeriod::from_str("Q3 FY20").unwrap(), q3);
+        assert_eq!(FinancialPeriod::from_str("q3-2020").unwrap(), q3);
+        assert_eq!(FinancialPeriod::from_str("3Q20").unwrap(), q3);
+        assert_eq!(
+            FinancialPeriod::from_str("FY2019").unwrap(),
+            FinancialPeriod::Year { year: 2019 }
+        );
+        assert_eq!(
+            FinancialPeriod::from_str("FY 19").unwrap(),
+            FinancialPeriod::Year { year: 2019 }
+        );
+        assert_eq!(
+            FinancialPeriod::from_str("2019-06").unwrap(),
+            FinancialPeriod::Month {
+                month: 6,
+                year: 2019
+            }
+        );
+        assert_eq!(
+            FinancialPeriod::from_str("2019-W05").unwrap(),
+            FinancialPeriod::Week {
+                week: 5,
+                year: 2019
+            }
+        );
+        assert_eq!(
+            FinancialPeriod::from_str("TTM Q2 2019").unwrap(),
+            FinancialPeriod::TrailingTwelveMonths {
+                month: 6,
+                year: 2019
+            }
+        );
+        assert_eq!(
+            FinancialPeriod::from_str("TTM 2019").unwrap(),
+            FinancialPeriod::TrailingTwelveMonths {
+                month: 12,
+                year: 2019
+            }
+        );
+
+        assert_eq!(
+            FinancialPeriod::from_str("19").err().unwrap(),
+            ParseError::AmbiguousYear
+        );
+        assert_eq!(
+            FinancialPeriod::from_str("TTM").err().unwrap(),
+            ParseError::MissingTrailingEnd
+        );
+        assert_eq!(
+            FinancialPeriod::from_str("TTM TTM 2019").err().unwrap(),
+            ParseError::NestedTrailingPeriod
+        );
+        assert_eq!(
+            FinancialPeriod::from_str("2019-13").err().unwrap(),
+            ParseError::InvalidPeriodValue
+        );
+        assert_eq!(
+            FinancialPeriod::from_str("2019-W53").err().unwrap(),
+            ParseError::InvalidPeriodValue
+        );
+        assert!(FinancialPeriod::from_str("2020-W53").is_ok());
+
+        for s in &["2019-06", "2019-W05", "TTM 2019-06", "Q3 2019"] {
+            assert_eq!(FinancialPeriod::from_str(s).unwrap().to_string(), *s);
+        }
+    }
+
+    #[test]
+    fn test_month_week_ttm_dates() {
+        let feb = FinancialPeriod::month_containing(Date::from_ymd(2020, 2, 10));
+        assert_eq!(feb.start_date(), Some(Date::from_ymd(2020, 2, 1)));
+        assert_eq!(feb.end_date(), Some(Date::from_ymd(2020, 2, 29)));
+        assert_eq!(
+            FinancialPeriod::Month {
+                month: 12,
+                year: 2019
+            }
+            .next(),
+            Some(FinancialPeriod::Month {
+                month: 1,
+                year: 2020
+            })
+        );
+
+        let week = FinancialPeriod::week_containing(Date::from_ymd(2019, 12, 30));
+        assert_eq!(
+            week,
+            FinancialPeriod::Week {
+                week: 1,
+                year: 2020
+            }
+        );
+        assert_eq!(week.start_date(), Some(Date::from_ymd(2019, 12, 30)));
+        assert_eq!(week.end_date(), Some(Date::from_ymd(2020, 1, 5)));
+        assert_eq!(
+            week.previous(),
+            Some(FinancialPeriod::Week {
+                week: 52,
+                year: 2019
+            })
+        );
+        assert_eq!(
+            FinancialPeriod::Week {
+                week: 53,
+                year: 2020
+            }
+            .next(),
+            Some(FinancialPeriod::Week {
+                week: 1,
+                year: 2021
+            })
+        );
+
+        let ttm = FinancialPeriod::TrailingTwelveMonths {
+            month: 6,
+            year: 2019,
+        };
+        assert_eq!(ttm.start_date(), Some(Date::from_ymd(2018, 7, 1)));
+        assert_eq!(ttm.end_date(), Some(Date::from_ymd(2019, 6, 30)));
+        assert_eq!(
+            ttm.next().unwrap().end_date(),
+            Some(Date::from_ymd(2019, 7, 31))
+        );
+
+        let fiscal = FiscalPeriod {
+            period: ttm,
+            fiscal_year_start_date: Date::from_ymd(2019, 4, 1),
+        };
+        assert_eq!(fiscal.end_date(), Some(Date::from_ymd(2019, 6, 30)));
+    }
+
+    #[test]
+    fn test_period_dates() {
+        let q2 = FinancialPeriod::Quarter {
+            quarter: 2,
+            year: 2019,
+        };
+        assert_eq!(q2.start_date(), Some(Date::from_ymd(2019, 4, 1)));
+        assert_eq!(q2.end_date(), Some(Date::from_ymd(2019, 6, 30)));
+        assert!(q2.contains(Date::from_ymd(2019, 6, 30)));
+        assert!(!q2.contains(Date::from_ymd(2019, 7, 1)));
+
+        let h2 = FinancialPeriod::Half {
+            half: 2,
+            year: 2019,
+        };
+        assert_eq!(h2.start_date(), Some(Date::from_ymd(2019, 7, 1)));
+        assert_eq!(h2.end_date(), Some(Date::from_ymd(2019, 12, 31)));
+
+        let y = FinancialPeriod::Year { year: 2020 };
+        assert_eq!(y.start_date(), Some(Date::from_ymd(2020, 1, 1)));
+        assert_eq!(y.end_date(), Some(Date::from_ymd(2020, 12, 31)));
+
+        assert_eq!(
+            FinancialPeriod::quarter_containing(Date::from_ymd(2019, 12, 31)),
+            FinancialPeriod::Quarter {
+                quarter: 4,
+                year: 2019
+            }
+        );
+        assert_eq!(
+            FinancialPeriod::half_containing(Date::from_ymd(2019, 6, 30)),
+            FinancialPeriod::Half {
+                half: 1,
+                year: 2019
+            }
+        );
+    }
+
+    #[test]
+    fn test_period_next_previous() {
+        let q4 = FinancialPeriod::Quarter {
+            quarter: 4,
+            year: 2019,
+        };
+        assert_eq!(
+            q4.next(),
+            Some(FinancialPeriod::Quarter {
+                quarter: 1,
+                year: 2020
+            })
+        );
+        assert_eq!(q4.next().unwrap().previous(), Some(q4));
+        assert_eq!(
+            FinancialPeriod::Half {
+                half: 1,
+                year: 2019
+            }
+            .previous(),
+            Some(FinancialPeriod::Half {
+                half: 2,
+                year: 2018
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_period_dates() {
+        let q5 = FinancialPeriod::Quarter {
+            quarter: 5,
+            year: 2019,
+        };
+        assert_eq!(q5.start_date(), None);
+        assert_eq!(q5.end_date(), None);
+        assert_eq!(q5.next(), None);
+        assert!(!q5.contains(Date::from_ymd(2019, 12, 31)));
+
+        let month = FinancialPeriod::Month {
+            month: 13,
+            year: 2019,
+        };
+        assert_eq!(month.end_date(), None);
+        assert_eq!(month.previous(), None);
+
+        let week = FinancialPeriod::Week {
+            week: 54,
+            year: 2020,
+        };
+        assert_eq!(week.start_date(), None);
+        assert_eq!(week.next(), None);
+
+        assert_eq!(FinancialPeriod::Year { year: 9999 }.next(), None);
+        assert_eq!(FinancialPeriod::Year { year: u16::MAX }.next(), None);
+        assert_eq!(FinancialPeriod::Year { year: 0 }.previous(), None);
+        assert_eq!(FinancialPeriod::trailing_twelve_months(&month), None);
+        assert_eq!(
+            FinancialPeriod::from_str("TTM Q5 2019").err().unwrap(),
+            ParseError::InvalidPeriodValue
+        );
+        assert_eq!(q5.iter_to(&q5).count(), 0);
+    }
+
+    #[test]
+    fn test_period_iteration() {
+        let periods: Vec<String> = FinancialPeriod::from_str("Q3 2018")
+            .unwrap()
+            .iter_to(&FinancialPeriod::from_str("H1 2019").unwrap())
+            .map(|p| p.to_string())
+            .collect();
+        assert_eq!(periods, vec!["Q3 2018", "Q4 2018", "Q1 2019", "Q2 2019"]);
+
+        let periods: Vec<FinancialPeriod> = FinancialPeriod::from_str("2019")
+            .unwrap()
+            .iter_to(&FinancialPeriod::from_str("2018").unwrap())
+            .collect();
+        assert!(periods.is_empty());
+    }
+
+    #[test]
+    fn test_fiscal_period() {
+        let april = Date::from_ymd(2019, 4, 1);
+        let q1 = FiscalPeriod {
+            period: FinancialPeriod::Quarter {
+                quarter: 1,
+                year: 2019,
+            },
+            fiscal_year_start_date: april,
+        };
+        assert_eq!(q1.start_date(), Some(Date::from_ymd(2018, 4, 1)));
+        assert_eq!(q1.end_date(), Some(Date::from_ymd(2018, 6, 30)));
+
+        let q4 = FiscalPeriod::for_date(Date::from_ymd(2020, 3, 31), april);
+        assert_eq!(
+            q4.period,
+            FinancialPeriod::Quarter {
+                quarter: 4,
+                year: 2020
+            }
+        );
+        assert_eq!(q4.start_date(), Some(Date::from_ymd(2020, 1, 1)));
+        assert_eq!(
+            q4.next().unwrap().start_date(),
+            Some(Date::from_ymd(2020, 4, 1))
+        );
+        assert!(q4.contains(Date::from_ymd(2020, 2, 29)));
+
+        let year = FiscalPeriod::year_for_date(Date::from_ymd(2019, 3, 31), april);
+        assert_eq!(year.period, FinancialPeriod::Year { year: 2019 });
+        assert_eq!(year.end_date(), Some(Date::from_ymd(2019, 3, 31)));
+
+        let calendar =
+            FiscalPeriod::year_for_date(Date::from_ymd(2019, 6, 1), Date::from_ymd(2019, 1, 1));
+        assert_eq!(calendar.period, FinancialPeriod::Year { year: 2019 });
+        assert_eq!(calendar.start_date(), Some(Date::from_ymd(2019, 1, 1)));
+    }
+
+    #[test]
+    fn test_fiscal_period_clamped() {
+        let leap = Date::from_ymd(2016, 2, 29);
+        let year = FiscalPeriod::year_for_date(Date::from_ymd(2019, 3, 1), leap);
+        assert_eq!(year.start_date(), Some(Date::from_ymd(2019, 2, 28)));
+        assert_eq!(year.end_date(), Some(Date::from_ymd(2020, 2, 28)));
+    }
 }
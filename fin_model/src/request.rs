@@ -1,22 +1,43 @@
 /*!
-Provides the common `Result` type and error enum used by _request traits_.
+Provides the common `Result` type and error types used by _request traits_.
+
+The `RequestError` type describes the kind of failure, using
+`RequestErrorKind`, as well as any context the `Provider` was able to
+determine; the underlying error, the HTTP status, the provider and endpoint
+called, and any hint as to when a throttled request may be retried.
+`RequestError` implements `std::error::Error` and so may be used with `?`
+and with common error reporting crates.
 
 ## Example
 
 ```rust
+use fin_model::prelude::*;
+
 pub trait Peers {
     fn peers(&self, for_symbol: Symbol) -> RequestResult<Snapshot<Vec<Symbol>>>;
 }
+
+let error = RequestError::from_u16(429)
+    .unwrap()
+    .with_provider("IEX Cloud")
+    .with_endpoint("/stock/aapl/peers");
+assert_eq!(error.kind(), &RequestErrorKind::RequestThrottled);
+assert_eq!(error.status(), Some(429));
 ```
 */
 
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::time::Duration;
+
 // ------------------------------------------------------------------------------------------------
 // Public Types
 // ------------------------------------------------------------------------------------------------
 
-/// The common error responses returned from _request traits_.
-#[derive(Debug)]
-pub enum RequestError {
+/// The kinds of error responses returned from _request traits_.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequestErrorKind {
     /// Usually a response from `Provider::new` this indicates that some
     /// error occurred due to missing or invalid configuration data.
     ConfigurationError(String),
@@ -49,6 +70,10 @@ pub enum RequestError {
     /// specifically for your identity.
     RequestThrottled,
 
+    /// The request would exceed a usage quota, or credit budget, either one
+    /// enforced by the service provider or one configured for the `Provider`.
+    QuotaExceeded,
+
     /// This can be used to indicate that a given operation is unsupported
     /// by the `Provider`. This may mean that 1) it is not supported by the
     /// service provider itself, 2) it is not _yet_ implemented by the
@@ -58,6 +83,18 @@ pub enum RequestError {
     Unsupported,
 }
 
+/// The common error returned from _request traits_, the `kind` of error
+/// and any additional context known to the `Provider`.
+#[derive(Debug)]
+pub struct RequestError {
+    kind: RequestErrorKind,
+    source: Option<Box<dyn Error + Send + Sync + 'static>>,
+    status: Option<u16>,
+    provider: Option<String>,
+    endpoint: Option<String>,
+    retry_after: Option<Duration>,
+}
+
 /// The common `Result` returned from _request traits_; the success type is
 /// unspecified but the error is always `RequestError`.
 pub type RequestResult<T> = Result<T, RequestError>;
@@ -67,22 +104,216 @@ pub type RequestResult<T> = Result<T, RequestError>;
 // ------------------------------------------------------------------------------------------------
 
 impl RequestError {
-    /// Return a `RequestError` from an HTTP status code as a `u16` value.
+    /// Construct a new error of the given kind, with no additional context.
+    pub fn new(kind: RequestErrorKind) -> Self {
+        RequestError {
+            kind,
+            source: None,
+            status: None,
+            provider: None,
+            endpoint: None,
+            retry_after: None,
+        }
+    }
+
+    /// Return a `RequestError` from an HTTP status code as a `u16` value,
+    /// the returned error records the status code.
     #[allow(ellipsis_inclusive_range_patterns)]
     pub fn from_u16(code: u16) -> Option<Self> {
-        match code {
-            100...299 => None,
+        let kind = match code {
+            100...299 => return None,
+
+            401 | 407 => RequestErrorKind::AuthenticationError,
+            403 | 451 => RequestErrorKind::AuthorizationError,
+            400 | 404...406 | 411...417 | 426...428 | 431 => RequestErrorKind::BadRequestError,
+            402 => RequestErrorKind::QuotaExceeded,
+            429 => RequestErrorKind::RequestThrottled,
+
+            501 => RequestErrorKind::Unsupported,
+            505 | 506 => RequestErrorKind::BadRequestError,
+            511 => RequestErrorKind::AuthorizationError,
 
-            401 | 407 => Some(RequestError::AuthenticationError),
-            403 | 451 => Some(RequestError::AuthorizationError),
-            400 | 404...406 | 411...417 | 426...428 | 431 => Some(RequestError::BadRequestError),
-            429 => Some(RequestError::RequestThrottled),
+            _ => RequestErrorKind::CommunicationError,
+        };
+        Some(RequestError::new(kind).with_status(code))
+    }
+
+    /// Set the underlying error that caused this one.
+    pub fn with_source<E>(self, source: E) -> Self
+    where
+        E: Into<Box<dyn Error + Send + Sync + 'static>>,
+    {
+        RequestError {
+            source: Some(source.into()),
+            ..self
+        }
+    }
 
-            501 => Some(RequestError::Unsupported),
-            505 | 506 => Some(RequestError::BadRequestError),
-            511 => Some(RequestError::AuthorizationError),
+    /// Set the HTTP status code returned by the service provider.
+    pub fn with_status(self, status: u16) -> Self {
+        RequestError {
+            status: Some(status),
+            ..self
+        }
+    }
+
+    /// Set the name of the provider that returned this error.
+    pub fn with_provider(self, provider: &str) -> Self {
+        RequestError {
+            provider: Some(provider.to_string()),
+            ..self
+        }
+    }
+
+    /// Set the endpoint called when this error occurred; note that this
+    /// value may be displayed, so it should not contain any credentials.
+    pub fn with_endpoint(self, endpoint: &str) -> Self {
+        RequestError {
+            endpoint: Some(endpoint.to_string()),
+            ..self
+        }
+    }
+
+    /// Set the duration the service provider suggests waiting before
+    /// retrying the request.
+    pub fn with_retry_after(self, retry_after: Duration) -> Self {
+        RequestError {
+            retry_after: Some(retry_after),
+            ..self
+        }
+    }
+
+    /// The kind of error that occurred.
+    pub fn kind(&self) -> &RequestErrorKind {
+        &self.kind
+    }
+
+    /// The HTTP status code returned by the service provider, if known.
+    pub fn status(&self) -> Option<u16> {
+        self.status
+    }
+
+    /// The name of the provider that returned this error, if known.
+    pub fn provider(&self) -> Option<&str> {
+        self.provider.as_deref()
+    }
+
+    /// The endpoint called when this error occurred, if known.
+    pub fn endpoint(&self) -> Option<&str> {
+        self.endpoint.as_deref()
+    }
+
+    /// The duration the service provider suggests waiting before retrying
+    /// the request, if known.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after
+    }
+}
+
+impl From<RequestErrorKind> for RequestError {
+    fn from(kind: RequestErrorKind) -> Self {
+        RequestError::new(kind)
+    }
+}
+
+impl Display for RequestErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RequestErrorKind::ConfigurationError(reason) => {
+                write!(f, "configuration error: {}", reason)
+            }
+            RequestErrorKind::CommunicationError => write!(f, "communication error"),
+            RequestErrorKind::AuthenticationError => write!(f, "authentication failed"),
+            RequestErrorKind::AuthorizationError => write!(f, "operation not authorized"),
+            RequestErrorKind::BadSymbolError(reason) => write!(f, "bad symbol: {}", reason),
+            RequestErrorKind::BadRequestError => write!(f, "bad request"),
+            RequestErrorKind::BadResponseError => write!(f, "bad response"),
+            RequestErrorKind::RequestThrottled => write!(f, "request throttled"),
+            RequestErrorKind::QuotaExceeded => write!(f, "usage quota exceeded"),
+            RequestErrorKind::Unsupported => write!(f, "operation unsupported"),
+        }
+    }
+}
+
+impl Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(provider) = &self.provider {
+            write!(f, "{}: ", provider)?;
+        }
+        write!(f, "{}", self.kind)?;
+        if let Some(status) = self.status {
+            write!(f, " (HTTP status {})", status)?;
+        }
+        if let Some(endpoint) = &self.endpoint {
+            write!(f, ", calling {}", endpoint)?;
+        }
+        if let Some(retry_after) = self.retry_after {
+            write!(f, ", retry after {}s", retry_after.as_secs())?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for RequestError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.source {
+            Some(source) => Some(source.as_ref()),
+            None => None,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::{RequestError, RequestErrorKind};
+    use std::error::Error;
+    use std::time::Duration;
+
+    #[test]
+    fn test_from_u16() {
+        assert!(RequestError::from_u16(200).is_none());
+
+        let error = RequestError::from_u16(404).unwrap();
+        assert_eq!(error.kind(), &RequestErrorKind::BadRequestError);
+        assert_eq!(error.status(), Some(404));
+
+        let error = RequestError::from_u16(402).unwrap();
+        assert_eq!(error.kind(), &RequestErrorKind::QuotaExceeded);
+
+        let error = RequestError::from_u16(503).unwrap();
+        assert_eq!(error.kind(), &RequestErrorKind::CommunicationError);
+    }
+
+    #[test]
+    fn test_context() {
+        let cause = std::io::Error::new(std::io::ErrorKind::TimedOut, "timed out");
+        let error = RequestError::from_u16(429)
+            .unwrap()
+            .with_provider("IEX Cloud")
+            .with_endpoint("/stock/aapl/quote")
+            .with_retry_after(Duration::from_secs(30))
+            .with_source(cause);
+        assert_eq!(error.provider(), Some("IEX Cloud"));
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(30)));
+        assert_eq!(error.source().unwrap().to_string(), "timed out".to_string());
+        assert_eq!(
+            error.to_string(),
+            "IEX Cloud: request throttled (HTTP status 429), calling /stock/aapl/quote, retry after 30s"
+                .to_string()
+        );
+    }
 
-            _ => Some(RequestError::CommunicationError),
+    #[test]
+    fn test_question_mark() {
+        fn parse(s: &str) -> Result<u16, Box<dyn Error>> {
+            Err(RequestError::new(RequestErrorKind::BadSymbolError(
+                s.to_string(),
+            )))?
         }
+        assert_eq!(parse("1X").err().unwrap().to_string(), "bad symbol: 1X");
     }
 }
@@ -0,0 +1,427 @@
+/*!
+Provides return and risk analytics calculated over a `PriceRangeSeries`.
+
+Returns are calculated from the closing prices in a price range series, and
+the risk measures are calculated from a `ReturnSeries`. To calculate any of
+these over a specific window slice the price range series first, for example
+with `Series::between`.
+
+Annualized values require the number of periods in a year for the interval
+between prices, for a daily series this is usually `TRADING_DAYS_PER_YEAR`;
+rates, such as the risk-free rate, are annual rates expressed as a fraction,
+so 2.5% is `0.025`.
+
+The `beta` and trailing changes reported by a provider in `company::Statistics`
+are calculated over the provider's own windows; the functions here allow the
+same measures to be calculated, and checked, over any window.
+
+Where there are not enough values to calculate a measure, such as a volatility
+from fewer than two returns, the functions return `None`.
+
+## Example
+
+```rust,no_run
+use fin_model::quote::{FetchPriceRangeSeries, SeriesInterval};
+use fin_model::returns::{beta, max_drawdown, sharpe_ratio, simple_returns, TRADING_DAYS_PER_YEAR};
+# use fin_model::prelude::*;
+# fn example(provider: &impl FetchPriceRangeSeries) -> RequestResult<()> {
+
+let prices = provider.last("AAPL".parse().unwrap(), SeriesInterval::OneYear)?;
+let market = provider.last("SPY".parse().unwrap(), SeriesInterval::OneYear)?;
+let returns = simple_returns(&prices);
+
+println!(
+    "Sharpe {:?}, beta {:?}",
+    sharpe_ratio(&returns, 0.025, TRADING_DAYS_PER_YEAR),
+    beta(&returns, &simple_returns(&market))
+);
+if let Some(drawdown) = max_drawdown(&prices) {
+    println!(
+        "max drawdown {:.1}% from {} to {}",
+        drawdown.drawdown * 100.0,
+        drawdown.peak,
+        drawdown.trough
+    );
+}
+# Ok(())
+# }
+```
+*/
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::quote::{PriceRangeSeries, SeriesInterval};
+use crate::series::amount;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// A series of returns, each dated with the price range at the end of the
+/// period the return is for.
+pub type ReturnSeries = Series<SeriesInterval, Snapshot<f64>>;
+
+/// The largest fall in price from a peak to a subsequent trough.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Drawdown {
+    /// the fall from peak to trough, as a fraction of the peak price
+    pub drawdown: f64,
+    /// the date of the peak price
+    pub peak: DateTime,
+    /// the date of the trough price
+    pub trough: DateTime,
+    /// the (optional) date the price first returned to the peak price, `None`
+    /// if it has not recovered within the series
+    pub recovery: Option<DateTime>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+/// The usual number of trading days in a year, used to annualize daily values.
+pub const TRADING_DAYS_PER_YEAR: f64 = 252.0;
+
+/// The number of weeks in a year, used to annualize weekly values.
+pub const WEEKS_PER_YEAR: f64 = 52.0;
+
+/// The number of months in a year, used to annualize monthly values.
+pub const MONTHS_PER_YEAR: f64 = 12.0;
+
+/// The simple return between each closing price and the one before it,
+/// `close[i] / close[i - 1] - 1`.
+pub fn simple_returns(series: &PriceRangeSeries) -> ReturnSeries {
+    period_returns(series, |previous, close| close / previous - 1.0)
+}
+
+/// The log, or continuously compounded, return between each closing price
+/// and the one before it, `ln(close[i] / close[i - 1])`.
+pub fn log_returns(series: &PriceRangeSeries) -> ReturnSeries {
+    period_returns(series, |previous, close| (close / previous).ln())
+}
+
+/// The cumulative simple return from the first closing price to each closing
+/// price, the first value is therefore zero. The returns are empty if the
+/// first closing price is zero.
+pub fn cumulative_returns(series: &PriceRangeSeries) -> ReturnSeries {
+    let first = series
+        .series
+        .first()
+        .map(|first| amount(&first.data.close))
+        .filter(|first| *first != 0.0);
+    Series {
+        interval: series.interval,
+        intra_interval: series.intra_interval,
+        series: match first {
+            Some(first) => series
+                .series
+                .iter()
+                .map(|snapshot| Snapshot {
+                    date: snapshot.date,
+                    data: amount(&snapshot.data.close) / first - 1.0,
+                })
+                .collect(),
+            None => Vec::new(),
+        },
+    }
+}
+
+/// The annualized volatility; the sample standard deviation of `returns`
+/// scaled by the square root of `periods_per_year`.
+pub fn annualized_volatility(returns: &ReturnSeries, periods_per_year: f64) -> Option<f64> {
+    let values = values(returns);
+    standard_deviation(&values).map(|deviation| deviation * periods_per_year.sqrt())
+}
+
+/// The annualized Sharpe ratio; the mean return in excess of the risk-free
+/// rate, divided by the sample standard deviation of returns.
+pub fn sharpe_ratio(
+    returns: &ReturnSeries,
+    risk_free_rate: f64,
+    periods_per_year: f64,
+) -> Option<f64> {
+    let excess: Vec<f64> = values(returns)
+        .iter()
+        .map(|value| value - risk_free_rate / periods_per_year)
+        .collect();
+    match standard_deviation(&excess) {
+        Some(deviation) if deviation > 0.0 => {
+            Some(mean(&excess) / deviation * periods_per_year.sqrt())
+        }
+        _ => None,
+    }
+}
+
+/// The annualized Sortino ratio; the mean return in excess of the target
+/// rate, divided by the downside deviation, the root mean square of returns
+/// below the target.
+pub fn sortino_ratio(
+    returns: &ReturnSeries,
+    target_rate: f64,
+    periods_per_year: f64,
+) -> Option<f64> {
+    let excess: Vec<f64> = values(returns)
+        .iter()
+        .map(|value| value - target_rate / periods_per_year)
+        .collect();
+    if excess.is_empty() {
+        return None;
+    }
+    let downside = excess
+        .iter()
+        .map(|value| value.min(0.0).powi(2))
+        .sum::<f64>()
+        / excess.len() as f64;
+    if downside > 0.0 {
+        Some(mean(&excess) / downside.sqrt() * periods_per_year.sqrt())
+    } else {
+        None
+    }
+}
+
+/// The maximum drawdown of closing prices, `None` if the price never falls
+/// below a previous peak.
+pub fn max_drawdown(series: &PriceRangeSeries) -> Option<Drawdown> {
+    let mut peak: Option<(DateTime, f64)> = None;
+    let mut deepest: Option<(Drawdown, f64)> = None;
+    for snapshot in &series.series {
+        let close = amount(&snapshot.data.close);
+        match peak {
+            Some((peak_date, peak_close)) if close < peak_close => {
+                let drawdown = (peak_close - close) / peak_close;
+                let deeper = match &deepest {
+                    Some((current, _)) => drawdown > current.drawdown,
+                    None => true,
+                };
+                if deeper {
+                    deepest = Some((
+                        Drawdown {
+                            drawdown,
+                            peak: peak_date,
+                            trough: snapshot.date,
+                            recovery: None,
+                        },
+                        peak_close,
+                    ));
+                }
+            }
+            _ => {
+                if let Some((current, peak_close)) = &mut deepest {
+                    if current.recovery.is_none() && close >= *peak_close {
+                        current.recovery = Some(snapshot.date);
+                    }
+                }
+                peak = Some((snapshot.date, close));
+            }
+        }
+    }
+    deepest.map(|(drawdown, _)| drawdown)
+}
+
+/// The beta of `returns` against `benchmark`; the covariance of the returns
+/// divided by the variance of the benchmark returns. Only returns for dates
+/// present in both series are used.
+pub fn beta(returns: &ReturnSeries, benchmark: &ReturnSeries) -> Option<f64> {
+    let (values, benchmark) = aligned(returns, benchmark);
+    match (covariance(&values, &benchmark), variance(&benchmark)) {
+        (Some(covariance), Some(variance)) if variance > 0.0 => Some(covariance / variance),
+        _ => None,
+    }
+}
+
+/// The Pearson correlation of `returns` with `benchmark`. Only returns for
+/// dates present in both series are used.
+pub fn correlation(returns: &ReturnSeries, benchmark: &ReturnSeries) -> Option<f64> {
+    let (values, benchmark) = aligned(returns, benchmark);
+    match (
+        covariance(&values, &benchmark),
+        standard_deviation(&values),
+        standard_deviation(&benchmark),
+    ) {
+        (Some(covariance), Some(left), Some(right)) if left > 0.0 && right > 0.0 => {
+            Some(covariance / (left * right))
+        }
+        _ => None,
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn period_returns<F>(series: &PriceRangeSeries, calculate: F) -> ReturnSeries
+where
+    F: Fn(f64, f64) -> f64,
+{
+    Series {
+        interval: series.interval,
+        intra_interval: series.intra_interval,
+        series: series
+            .series
+            .windows(2)
+            .map(|pair| Snapshot {
+                date: pair[1].date,
+                data: calculate(amount(&pair[0].data.close), amount(&pair[1].data.close)),
+            })
+            .collect(),
+    }
+}
+
+fn values(returns: &ReturnSeries) -> Vec<f64> {
+    returns.values().cloned().collect()
+}
+
+fn aligned(returns: &ReturnSeries, benchmark: &ReturnSeries) -> (Vec<f64>, Vec<f64>) {
+    returns.align(benchmark).values().cloned().unzip()
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn covariance(left: &[f64], right: &[f64]) -> Option<f64> {
+    if left.len() < 2 || left.len() != right.len() {
+        return None;
+    }
+    let (left_mean, right_mean) = (mean(left), mean(right));
+    Some(
+        left.iter()
+            .zip(right)
+            .map(|(l, r)| (l - left_mean) * (r - right_mean))
+            .sum::<f64>()
+            / (left.len() - 1) as f64,
+    )
+}
+
+fn variance(values: &[f64]) -> Option<f64> {
+    covariance(values, values)
+}
+
+fn standard_deviation(values: &[f64]) -> Option<f64> {
+    variance(values).map(f64::sqrt)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quote::PriceRange;
+    use steel_cent::currency::USD;
+
+    fn closing(closes: &[f64]) -> PriceRangeSeries {
+        let start = Date::from_ymd(2019, 1, 1).and_hms(0, 0, 0);
+        Series {
+            interval: SeriesInterval::OneYear,
+            intra_interval: None,
+            series: closes
+                .iter()
+                .enumerate()
+                .map(|(i, close)| {
+                    let close = Money::of_minor(USD, (close * 100.0).round() as i32);
+                    Snapshot {
+                        date: start + chrono::Duration::days(i as i64),
+                        data: PriceRange {
+                            open: close,
+                            close,
+                            high: close,
+                            low: close,
+                            volume: None,
+                        },
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    fn assert_near(actual: Option<f64>, expected: f64) {
+        let actual = actual.unwrap();
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    const CLOSES: &[f64] = &[100.0, 110.0, 99.0, 108.9, 107.0, 112.0];
+
+    #[test]
+    fn test_returns() {
+        let series = closing(CLOSES);
+        let returns = simple_returns(&series);
+        assert_eq!(returns.len(), 5);
+        assert_eq!(returns.series[0].date, series.series[1].date);
+        assert_near(Some(returns.series[0].data), 0.1);
+        assert_near(Some(returns.series[1].data), -0.1);
+
+        let logs = log_returns(&series);
+        assert_near(Some(logs.series[1].data), 0.9f64.ln());
+        // log returns sum to the log of the total return.
+        assert_near(Some(logs.values().sum()), 1.12f64.ln());
+
+        let cumulative = cumulative_returns(&series);
+        assert_eq!(cumulative.len(), 6);
+        assert_near(Some(cumulative.series[0].data), 0.0);
+        assert_near(Some(cumulative.series[5].data), 0.12);
+        assert!(cumulative_returns(&closing(&[0.0, 1.0, 2.0])).is_empty());
+    }
+
+    #[test]
+    fn test_risk_ratios() {
+        // expected values calculated independently from the same returns.
+        let returns = simple_returns(&closing(CLOSES));
+        assert_near(
+            annualized_volatility(&returns, TRADING_DAYS_PER_YEAR),
+            1.355046313839525,
+        );
+        assert_near(
+            sharpe_ratio(&returns, 0.0252, TRADING_DAYS_PER_YEAR),
+            4.789947972737225,
+        );
+        assert_near(
+            sortino_ratio(&returns, 0.0, TRADING_DAYS_PER_YEAR),
+            9.041513016527373,
+        );
+        let single = simple_returns(&closing(&[100.0, 101.0]));
+        assert!(annualized_volatility(&single, TRADING_DAYS_PER_YEAR).is_none());
+    }
+
+    #[test]
+    fn test_max_drawdown() {
+        let series = closing(&[100.0, 120.0, 90.0, 110.0, 96.0, 125.0, 100.0]);
+        let drawdown = max_drawdown(&series).unwrap();
+        assert_near(Some(drawdown.drawdown), 0.25);
+        assert_eq!(drawdown.peak, series.series[1].date);
+        assert_eq!(drawdown.trough, series.series[2].date);
+        assert_eq!(drawdown.recovery, Some(series.series[5].date));
+
+        assert!(max_drawdown(&closing(&[100.0, 101.0, 102.0])).is_none());
+    }
+
+    #[test]
+    fn test_beta_and_correlation() {
+        let benchmark = closing(&[100.0, 102.0, 100.98, 103.0, 101.0]);
+        let benchmark_returns = simple_returns(&benchmark);
+        // an asset returning exactly twice the benchmark each period.
+        let mut close = 10000.0;
+        let mut closes = vec![close];
+        for value in benchmark_returns.values() {
+            close *= 1.0 + 2.0 * value;
+            closes.push(close);
+        }
+        let returns = simple_returns(&closing(&closes));
+        assert!((beta(&returns, &benchmark_returns).unwrap() - 2.0).abs() < 1e-3);
+        assert!((correlation(&returns, &benchmark_returns).unwrap() - 1.0).abs() < 1e-4);
+
+        // only common dates are used.
+        let shorter = returns.between(returns.series[1].date.date(), returns.series[3].date.date());
+        assert_eq!(shorter.align(&benchmark_returns).len(), 3);
+        assert!((beta(&shorter, &benchmark_returns).unwrap() - 2.0).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,284 @@
+/*!
+Serialization helpers for types not owned by this crate.
+
+The `Money` type re-exported in the prelude does not implement the serde
+traits, so this module provides functions usable with the field attribute
+`#[serde(with = "...")]`. Money values are written in a stable string form,
+the ISO currency code followed by the decimal amount, for example
+`"USD 12.34"` or `"JPY 1200"`.
+*/
+
+use std::fmt;
+
+use steel_cent::currency::with_code;
+
+use crate::prelude::*;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+/// Format a `Money` value in the stable string form, `"{code} {amount}"`.
+pub fn money_to_string(money: &Money) -> String {
+    let places = u32::from(money.currency.decimal_places());
+    let minor = i64::from(money.minor_amount());
+    let sign = if minor < 0 { "-" } else { "" };
+    let minor = minor.abs();
+    if places == 0 {
+        format!("{} {}{}", money.currency.code(), sign, minor)
+    } else {
+        let scale = 10_i64.pow(places);
+        format!(
+            "{} {}{}.{:0width$}",
+            money.currency.code(),
+            sign,
+            minor / scale,
+            minor % scale,
+            width = places as usize
+        )
+    }
+}
+
+/// Parse a `Money` value from the stable string form, `"{code} {amount}"`.
+pub fn money_from_string(s: &str) -> Result<Money, String> {
+    let mut parts = s.split_whitespace();
+    let (code, amount) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(code), Some(amount), None) => (code, amount),
+        _ => return Err(format!("invalid money value: '{}'", s)),
+    };
+    let currency = match with_code(code) {
+        Some(currency) => currency,
+        None => return Err(format!("invalid currency code: '{}'", code)),
+    };
+    let places = currency.decimal_places() as usize;
+    let (negative, amount) = match amount.strip_prefix('-') {
+        Some(amount) => (true, amount),
+        None => (false, amount),
+    };
+    let (major, minor) = match amount.find('.') {
+        Some(idx) => (&amount[..idx], &amount[idx + 1..]),
+        None => (amount, ""),
+    };
+    if major.is_empty()
+        || minor.len() > places
+        || !major
+            .chars()
+            .chain(minor.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return Err(format!("invalid money amount: '{}'", amount));
+    }
+    let digits = format!("{}{:0<width$}", major, minor, width = places);
+    match digits.parse::<i32>() {
+        Ok(value) => Ok(Money::of_minor(
+            currency,
+            if negative { -value } else { value },
+        )),
+        Err(_) => Err(format!("money amount out of range: '{}'", amount)),
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Modules
+// ------------------------------------------------------------------------------------------------
+
+/// Use with `#[serde(with = "crate::serialize::money")]` on `Money` fields.
+pub mod money {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    use crate::prelude::Money;
+
+    pub fn serialize<S>(money: &Money, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&super::money_to_string(money))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Money, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        super::money_from_string(&s).map_err(de::Error::custom)
+    }
+}
+
+/// Use with `#[serde(with = "crate::serialize::option_money")]` on `Option<Money>` fields.
+pub mod option_money {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    use crate::prelude::Money;
+
+    pub fn serialize<S>(money: &Option<Money>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match money {
+            Some(money) => serializer.serialize_some(&super::money_to_string(money)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Money>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(s) => super::money_from_string(&s)
+                .map(Some)
+                .map_err(de::Error::custom),
+            None => Ok(None),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+/// Visitor used by types that serialize to, and from, their `Display`/`FromStr` forms.
+pub(crate) struct FromStrVisitor<T> {
+    pub(crate) expecting: &'static str,
+    pub(crate) marker: std::marker::PhantomData<T>,
+}
+
+impl<'de, T> serde::de::Visitor<'de> for FromStrVisitor<T>
+where
+    T: std::str::FromStr,
+    T::Err: fmt::Debug,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<T, E>
+    where
+        E: serde::de::Error,
+    {
+        value
+            .parse::<T>()
+            .map_err(|e| E::custom(format!("{:?} parsing '{}'", e, value)))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::{money_from_string, money_to_string};
+    use crate::analysis::{RatingType, Ratings};
+    use crate::prelude::*;
+    use crate::quote::{PriceRange, PriceRangeSeries, QuotePrice, SeriesInterval};
+    use crate::reporting::{FinancialPeriod, FiscalPeriod};
+    use serde::de::DeserializeOwned;
+    use serde::Serialize;
+    use std::collections::HashMap;
+    use steel_cent::currency::with_code;
+
+    fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> String {
+        let json = serde_json::to_string(value).unwrap();
+        let back: T = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&back).unwrap(), json);
+        json
+    }
+
+    fn usd(major: i32, minor: i32) -> Money {
+        Money::of_major_minor(with_code("USD").unwrap(), major, minor)
+    }
+
+    #[test]
+    fn test_money_string_form() {
+        assert_eq!(money_to_string(&usd(12, 34)), "USD 12.34".to_string());
+        assert_eq!(money_to_string(&usd(0, 5)), "USD 0.05".to_string());
+        assert_eq!(money_to_string(&usd(-3, -50)), "USD -3.50".to_string());
+
+        assert_eq!(money_from_string("USD 12.34").unwrap(), usd(12, 34));
+        assert_eq!(money_from_string("USD 12.3").unwrap(), usd(12, 30));
+        assert_eq!(money_from_string("USD 12").unwrap(), usd(12, 0));
+        assert_eq!(money_from_string("USD -3.50").unwrap(), usd(-3, -50));
+
+        assert!(money_from_string("12.34").is_err());
+        assert!(money_from_string("XX 12.34").is_err());
+        assert!(money_from_string("USD 12.345").is_err());
+        assert!(money_from_string("USD 1e3").is_err());
+    }
+
+    #[test]
+    fn test_financial_period_round_trip() {
+        let period = FinancialPeriod::Quarter {
+            quarter: 2,
+            year: 2019,
+        };
+        assert_eq!(round_trip(&period), "\"Q2 2019\"".to_string());
+        assert_eq!(
+            round_trip(&FinancialPeriod::Year { year: 2019 }),
+            "\"2019\"".to_string()
+        );
+        assert!(serde_json::from_str::<FinancialPeriod>("\"Q5 2019\"").is_err());
+
+        let fiscal = FiscalPeriod {
+            period: FinancialPeriod::Half {
+                half: 1,
+                year: 2019,
+            },
+            fiscal_year_start_date: Date::from_ymd(2019, 4, 1),
+        };
+        let json = round_trip(&fiscal);
+        assert_eq!(serde_json::from_str::<FiscalPeriod>(&json).unwrap(), fiscal);
+    }
+
+    #[test]
+    fn test_series_round_trip() {
+        let series: PriceRangeSeries = Series {
+            interval: SeriesInterval::FiveDays,
+            intra_interval: None,
+            series: vec![Snapshot {
+                date: Date::from_ymd(2019, 7, 1).and_hms(0, 0, 0),
+                data: PriceRange {
+                    open: usd(10, 0),
+                    close: usd(11, 25),
+                    high: usd(11, 50),
+                    low: usd(9, 99),
+                    volume: Some(1_000),
+                },
+            }],
+        };
+        let json = round_trip(&series);
+        assert!(json.contains("\"open\":\"USD 10.00\""));
+    }
+
+    #[test]
+    fn test_optional_money_round_trip() {
+        round_trip(&QuotePrice {
+            price: usd(1, 0),
+            change: Some(usd(0, 10)),
+            percentage: Some(0.1),
+        });
+        let json = round_trip(&QuotePrice {
+            price: usd(1, 0),
+            change: None,
+            percentage: None,
+        });
+        assert!(json.contains("\"change\":null"));
+    }
+
+    #[test]
+    fn test_ratings_round_trip() {
+        let mut ratings = HashMap::new();
+        ratings.insert(RatingType::Buy, 10);
+        let json = round_trip(&Bounded {
+            start_date: Date::from_ymd(2019, 1, 1).and_hms(0, 0, 0),
+            end_date: Date::from_ymd(2019, 3, 31).and_hms(0, 0, 0),
+            data: Ratings {
+                ratings,
+                scale_mark: Some(1.5),
+            },
+        });
+        assert!(json.contains("\"Buy\":10"));
+    }
+}
@@ -0,0 +1,440 @@
+/*!
+Provides operations on `Series` values, and in particular on `PriceRangeSeries`.
+
+The `Series` type in the prelude is a simple container, this module adds the
+common operations needed to work with series data once it has been fetched:
+
+* slicing a series of snapshots by date range,
+* iterating over the dates, values, closing prices, or volumes in a series,
+* aligning two series on their common timestamps, and
+* resampling a price range series to a coarser `Resolution`, for example
+  combining one minute intra-day bars into five minute or hourly bars.
+
+All operations assume, as documented on `Series`, that data points are in
+increasing time order.
+
+## Example
+
+```rust
+use fin_model::prelude::*;
+use fin_model::quote::{PriceRange, PriceRangeSeries, SeriesInterval};
+use fin_model::series::Resolution;
+use steel_cent::currency::USD;
+
+let bar = |minute: u32, close: i32| Snapshot {
+    date: Date::from_ymd(2019, 8, 15).and_hms(9, 30 + minute, 0),
+    data: PriceRange {
+        open: Money::of_major(USD, close - 1),
+        close: Money::of_major(USD, close),
+        high: Money::of_major(USD, close + 1),
+        low: Money::of_major(USD, close - 2),
+        volume: Some(100),
+    },
+};
+let series: PriceRangeSeries = Series {
+    interval: SeriesInterval::Day,
+    intra_interval: Some(Resolution::Minute.duration().unwrap()),
+    series: (0..10).map(|minute| bar(minute, 200 + minute as i32)).collect(),
+};
+
+let bars = series.resample(Resolution::FiveMinutes);
+assert_eq!(bars.len(), 2);
+assert_eq!(bars.series[0].data.open, Money::of_major(USD, 199));
+assert_eq!(bars.series[0].data.close, Money::of_major(USD, 204));
+assert_eq!(bars.series[0].data.volume, Some(500));
+```
+*/
+
+use std::time::Duration;
+
+use chrono::{Datelike, Timelike};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::quote::{PriceRange, PriceRangeSeries};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+/// The resolution, or bar size, that a price range series may be resampled to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Resolution {
+    Minute,
+    FiveMinutes,
+    FifteenMinutes,
+    ThirtyMinutes,
+    Hour,
+    Day,
+    /// weeks start on Monday
+    Week,
+    Month,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Resolution {
+    /// The fixed duration of a bar at this resolution, `None` for `Month` as
+    /// months vary in length.
+    pub fn duration(&self) -> Option<Duration> {
+        let minutes = match self {
+            Resolution::Minute => 1,
+            Resolution::FiveMinutes => 5,
+            Resolution::FifteenMinutes => 15,
+            Resolution::ThirtyMinutes => 30,
+            Resolution::Hour => 60,
+            Resolution::Day => 24 * 60,
+            Resolution::Week => 7 * 24 * 60,
+            Resolution::Month => return None,
+        };
+        Some(Duration::from_secs(minutes * 60))
+    }
+
+    /// The start of the bar at this resolution that contains `date`.
+    pub fn bar_start(&self, date: DateTime) -> DateTime {
+        let day = date.date();
+        match self {
+            Resolution::Minute => truncate_minutes(date, 1),
+            Resolution::FiveMinutes => truncate_minutes(date, 5),
+            Resolution::FifteenMinutes => truncate_minutes(date, 15),
+            Resolution::ThirtyMinutes => truncate_minutes(date, 30),
+            Resolution::Hour => day.and_hms_opt(date.hour(), 0, 0).unwrap(),
+            Resolution::Day => start_of_day(day),
+            Resolution::Week => {
+                let offset = i64::from(day.weekday().num_days_from_monday());
+                start_of_day(day - chrono::Duration::days(offset))
+            }
+            Resolution::Month => start_of_day(day.with_day(1).unwrap()),
+        }
+    }
+}
+
+impl<I, T> Series<I, T> {
+    /// The number of data points in the series.
+    pub fn len(&self) -> usize {
+        self.series.len()
+    }
+
+    /// Returns `true` if the series has no data points.
+    pub fn is_empty(&self) -> bool {
+        self.series.is_empty()
+    }
+
+    /// Iterate over the data points in the series.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.series.iter()
+    }
+}
+
+impl<I, T> Series<I, Snapshot<T>> {
+    /// The first and last dates in the series, if it is not empty.
+    pub fn date_range(&self) -> Option<(DateTime, DateTime)> {
+        match (self.series.first(), self.series.last()) {
+            (Some(first), Some(last)) => Some((first.date, last.date)),
+            _ => None,
+        }
+    }
+
+    /// Iterate over the dates of the data points in the series.
+    pub fn dates<'a>(&'a self) -> impl Iterator<Item = DateTime> + 'a {
+        self.series.iter().map(|snapshot| snapshot.date)
+    }
+
+    /// Iterate over the values of the data points in the series.
+    pub fn values<'a>(&'a self) -> impl Iterator<Item = &'a T> + 'a {
+        self.series.iter().map(|snapshot| &snapshot.data)
+    }
+
+    /// The data points with a date from `start`, inclusive, to `end`, exclusive.
+    pub fn slice(&self, start: DateTime, end: DateTime) -> &[Snapshot<T>] {
+        let from = self
+            .series
+            .partition_point(|snapshot| snapshot.date < start);
+        let to = self.series.partition_point(|snapshot| snapshot.date < end);
+        if from < to {
+            &self.series[from..to]
+        } else {
+            &[]
+        }
+    }
+
+    /// A new series containing only the data points with a date from
+    /// `start_date` to `end_date`, both inclusive.
+    pub fn between(&self, start_date: Date, end_date: Date) -> Self
+    where
+        I: Clone,
+        T: Clone,
+    {
+        let start = start_of_day(start_date);
+        let slice = match end_date.succ_opt() {
+            Some(after) => self.slice(start, start_of_day(after)),
+            None => {
+                let from = self
+                    .series
+                    .partition_point(|snapshot| snapshot.date < start);
+                &self.series[from..]
+            }
+        };
+        Series {
+            interval: self.interval.clone(),
+            intra_interval: self.intra_interval,
+            series: slice.to_vec(),
+        }
+    }
+
+    /// Align this series with `other`, returning a series containing a pair of
+    /// values for each date present in both; dates present in only one of the
+    /// series are dropped.
+    pub fn align<J, U>(&self, other: &Series<J, Snapshot<U>>) -> Series<I, Snapshot<(T, U)>>
+    where
+        I: Clone,
+        T: Clone,
+        U: Clone,
+    {
+        let mut aligned = Vec::new();
+        let mut left = self.series.iter().peekable();
+        let mut right = other.series.iter().peekable();
+        while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+            if l.date < r.date {
+                let _ = left.next();
+            } else if r.date < l.date {
+                let _ = right.next();
+            } else {
+                aligned.push(Snapshot {
+                    date: l.date,
+                    data: (l.data.clone(), r.data.clone()),
+                });
+                let _ = left.next();
+                let _ = right.next();
+            }
+        }
+        Series {
+            interval: self.interval.clone(),
+            intra_interval: self.intra_interval,
+            series: aligned,
+        }
+    }
+}
+
+impl PriceRangeSeries {
+    /// Iterate over the opening prices in the series.
+    pub fn opens<'a>(&'a self) -> impl Iterator<Item = Money> + 'a {
+        self.values().map(|range| range.open)
+    }
+
+    /// Iterate over the closing prices in the series.
+    pub fn closes<'a>(&'a self) -> impl Iterator<Item = Money> + 'a {
+        self.values().map(|range| range.close)
+    }
+
+    /// Iterate over the trading volumes in the series.
+    pub fn volumes<'a>(&'a self) -> impl Iterator<Item = Option<u64>> + 'a {
+        self.values().map(|range| range.volume)
+    }
+
+    /// Resample the series into bars at `resolution`. Each new bar is dated
+    /// at the start of its period and takes the open of the first bar in the
+    /// period, the close of the last, the highest high, the lowest low, and
+    /// the total volume; volume is `None` only if it is `None` for every bar
+    /// in the period.
+    ///
+    /// Resampling to a resolution finer than the series itself leaves each
+    /// bar unchanged other than its date.
+    pub fn resample(&self, resolution: Resolution) -> PriceRangeSeries {
+        let mut bars: Vec<Snapshot<PriceRange>> = Vec::new();
+        for snapshot in &self.series {
+            let start = resolution.bar_start(snapshot.date);
+            let range = &snapshot.data;
+            match bars.last_mut() {
+                Some(bar) if bar.date == start => {
+                    let current = &mut bar.data;
+                    current.close = range.close;
+                    if range.high > current.high {
+                        current.high = range.high;
+                    }
+                    if range.low < current.low {
+                        current.low = range.low;
+                    }
+                    current.volume = match (current.volume, range.volume) {
+                        (Some(total), Some(volume)) => Some(total + volume),
+                        (total, None) => total,
+                        (None, volume) => volume,
+                    };
+                }
+                _ => bars.push(Snapshot {
+                    date: start,
+                    data: range.clone(),
+                }),
+            }
+        }
+        Series {
+            interval: self.interval,
+            intra_interval: resolution.duration(),
+            series: bars,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+/// The value of `money` in the major currency unit, for calculations.
+pub(crate) fn amount(money: &Money) -> f64 {
+    f64::from(money.minor_amount()) / 10f64.powi(i32::from(money.currency.decimal_places()))
+}
+
+fn start_of_day(day: Date) -> DateTime {
+    day.and_hms_opt(0, 0, 0).unwrap()
+}
+
+fn truncate_minutes(date: DateTime, minutes: u32) -> DateTime {
+    date.date()
+        .and_hms_opt(date.hour(), date.minute() - (date.minute() % minutes), 0)
+        .unwrap()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quote::SeriesInterval;
+    use steel_cent::currency::USD;
+
+    fn usd(major: i32) -> Money {
+        Money::of_major(USD, major)
+    }
+
+    fn bar(date: DateTime, open: i32, high: i32, low: i32, close: i32) -> Snapshot<PriceRange> {
+        Snapshot {
+            date,
+            data: PriceRange {
+                open: usd(open),
+                close: usd(close),
+                high: usd(high),
+                low: usd(low),
+                volume: Some(10),
+            },
+        }
+    }
+
+    fn daily(days: &[(u32, i32)]) -> PriceRangeSeries {
+        Series {
+            interval: SeriesInterval::OneMonth,
+            intra_interval: Resolution::Day.duration(),
+            series: days
+                .iter()
+                .map(|(day, close)| {
+                    bar(
+                        Date::from_ymd(2019, 8, *day).and_hms(0, 0, 0),
+                        *close,
+                        *close,
+                        *close,
+                        *close,
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_bar_start() {
+        let date = Date::from_ymd(2019, 8, 15).and_hms(10, 47, 31);
+        assert_eq!(
+            Resolution::FiveMinutes.bar_start(date),
+            Date::from_ymd(2019, 8, 15).and_hms(10, 45, 0)
+        );
+        assert_eq!(
+            Resolution::Hour.bar_start(date),
+            Date::from_ymd(2019, 8, 15).and_hms(10, 0, 0)
+        );
+        assert_eq!(
+            Resolution::Week.bar_start(date),
+            Date::from_ymd(2019, 8, 12).and_hms(0, 0, 0)
+        );
+        assert_eq!(
+            Resolution::Month.bar_start(date),
+            Date::from_ymd(2019, 8, 1).and_hms(0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_slice_and_iterate() {
+        let series = daily(&[(1, 10), (2, 11), (5, 12), (6, 13), (7, 14)]);
+        let between = series.between(Date::from_ymd(2019, 8, 2), Date::from_ymd(2019, 8, 6));
+        assert_eq!(
+            between.closes().collect::<Vec<Money>>(),
+            vec![usd(11), usd(12), usd(13)]
+        );
+        assert_eq!(
+            series
+                .slice(
+                    Date::from_ymd(2019, 8, 3).and_hms(0, 0, 0),
+                    Date::from_ymd(2019, 8, 5).and_hms(0, 0, 0)
+                )
+                .len(),
+            0
+        );
+        assert_eq!(series.volumes().flatten().sum::<u64>(), 50);
+        assert!(series
+            .between(Date::from_ymd(2019, 9, 1), Date::from_ymd(2019, 9, 30))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_align() {
+        let left = daily(&[(1, 10), (2, 11), (5, 12), (6, 13)]);
+        let right = daily(&[(2, 21), (3, 22), (6, 23), (7, 24)]);
+        let aligned = left.align(&right);
+        let days: Vec<u32> = aligned.dates().map(|date| date.day()).collect();
+        assert_eq!(days, vec![2, 6]);
+        assert_eq!(aligned.series[1].data.0.close, usd(13));
+        assert_eq!(aligned.series[1].data.1.close, usd(23));
+    }
+
+    #[test]
+    fn test_resample() {
+        let start = Date::from_ymd(2019, 8, 15).and_hms(9, 30, 0);
+        let minute = chrono::Duration::minutes(1);
+        let series: PriceRangeSeries = Series {
+            interval: SeriesInterval::Day,
+            intra_interval: Resolution::Minute.duration(),
+            series: vec![
+                bar(start, 10, 12, 9, 11),
+                bar(start + minute, 11, 15, 11, 14),
+                bar(start + minute * 2, 14, 14, 8, 9),
+                bar(start + minute * 5, 9, 10, 7, 8),
+            ],
+        };
+
+        let bars = series.resample(Resolution::FiveMinutes);
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars.intra_interval, Some(Duration::from_secs(300)));
+        let first = &bars.series[0];
+        assert_eq!(first.date, start);
+        assert_eq!(first.data.open, usd(10));
+        assert_eq!(first.data.high, usd(15));
+        assert_eq!(first.data.low, usd(8));
+        assert_eq!(first.data.close, usd(9));
+        assert_eq!(first.data.volume, Some(30));
+        assert_eq!(bars.series[1].data.close, usd(8));
+
+        let hourly = bars.resample(Resolution::Hour);
+        assert_eq!(hourly.len(), 1);
+        assert_eq!(hourly.series[0].date, start.date().and_hms(9, 0, 0));
+        assert_eq!(hourly.series[0].data.close, usd(8));
+        assert_eq!(hourly.series[0].data.volume, Some(40));
+
+        let monthly = daily(&[(1, 10), (30, 11)]).resample(Resolution::Month);
+        assert_eq!(monthly.len(), 1);
+        assert_eq!(monthly.intra_interval, None);
+    }
+}
@@ -1,78 +1,569 @@
 /*!
 Provides types and functions for market symbols.
 
-In general the `Symbol` type is loosely defined, it is stored as a `String`
-but there is little standardization across markets and geographies
-concerning length or character set. To this end the function `is_valid`
-simply takes the commonly known limits and returns true/false, although
-this should probably be _maybe true_/false.
+In general there is little standardization of symbols across markets and
+geographies concerning length or character set. To this end the `Symbol`
+type only enforces a common, permissive, syntax when it is parsed; it must
+be non-empty, no longer than `MAX_SYMBOL_LENGTH`, start with a letter or
+digit and contain only letters, digits, and the separators commonly used
+for share classes and suffixes (`.`, `-`, `/`, `^`, `=`). As a special
+case index symbols such as `^GSPC` may start with a `^`. Symbols are
+always stored in upper case.
+
+A `QualifiedSymbol` pairs a symbol with the
+[ISO 10383](https://www.iso20022.org/10383/iso-10383-market-identifier-codes)
+Market Identifier Code (MIC) of the market it is traded on, and is written
+as `"XNAS:AAPL"`. The MIC can be checked against any
+[`Registry`](../registry/trait.Registry.html) of markets, and market-specific
+rules can be applied using a `SymbolRules` set.
 
 The macro [`assert_is_valid`](../macro.assert_is_valid.html) can be used by
 providers as it will do nothing if a symbol is valid but return a
 `request::RequestResult` if it is not.
+
+## Example
+
+```rust
+use fin_model::symbol::{QualifiedSymbol, Symbol, SymbolRules};
+
+let symbol: Symbol = "brk.b".parse().unwrap();
+assert_eq!(symbol.to_string(), "BRK.B");
+
+let qualified: QualifiedSymbol = "XNAS:AAPL".parse().unwrap();
+assert_eq!(qualified.market, "XNAS");
+assert!(SymbolRules::default().validate(&qualified).is_ok());
+```
 */
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use regex::Regex;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::market::Market;
+use crate::registry::Registry;
 
 // ------------------------------------------------------------------------------------------------
 // Public Types
 // ------------------------------------------------------------------------------------------------
 
-/// Type for a market ticker symbol. Consumers of symbols should use
-/// `is_valid` to ensure the value they are provided is at least
-/// syntactically correct.
-pub type Symbol = String;
+/// Type for a market ticker symbol. A `Symbol` can only be created by
+/// parsing a string, and so is always at least syntactically correct.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct Symbol(String);
 
 /// Where a set of symbols are used together.
 pub type Symbols = HashSet<Symbol>;
 
-/// Maximum length assuming a root length of 6 and a 2 character suffix.
-pub const MAX_SYMBOL_LENGTH: usize = 8;
+/// Maximum length, this allows for long suffixes such as those used for
+/// indices and currency pairs as well as share classes.
+pub const MAX_SYMBOL_LENGTH: usize = 12;
 
-/// is the symbol valid; currently only string lengths are checked.
-#[inline(always)]
-pub fn is_valid(symbol: Symbol) -> bool {
-    !symbol.is_empty() && symbol.len() <= MAX_SYMBOL_LENGTH
-}
+/// The separator between market and symbol in a `QualifiedSymbol` string.
+pub const MARKET_SEPARATOR: char = ':';
 
-/// Type for a qualified ticker symbol using the same format
-/// for the market and symbol itself.
+/// Type for a qualified ticker symbol, the market is identified by its
+/// ISO 10383 Market Identifier Code (MIC).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "String", into = "String")
+)]
 pub struct QualifiedSymbol {
-    /// the symbol for the market that qualifies `symbol`
-    pub market: Symbol,
+    /// the MIC for the market that qualifies `symbol`
+    pub market: String,
     /// the target security symbol
     pub symbol: Symbol,
 }
 
+/// Errors that can result from parsing or validating a symbol, the `Display`
+/// implementation provides a human readable reason.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SymbolError {
+    /// the string is empty
+    EmptyString,
+    /// the string is longer than `MAX_SYMBOL_LENGTH`
+    TooLong(usize),
+    /// the string contains a character not permitted in a symbol
+    InvalidCharacter(char),
+    /// a qualified symbol string was not in the form `MIC:SYMBOL`
+    MissingMarket,
+    /// the market identifier is not a syntactically valid MIC
+    InvalidMarket(String),
+    /// the market identifier is not present in the market registry
+    UnknownMarket(String),
+    /// the symbol failed a market-specific rule; market and rule description
+    MarketRuleFailed(String, String),
+}
+
+/// A rule used to validate symbols for a specific market.
+pub trait SymbolRule {
+    /// A short description of the rule, used when reporting a failure.
+    fn description(&self) -> String;
+
+    /// Return `true` if `symbol` is acceptable to this rule.
+    fn accepts(&self, symbol: &Symbol) -> bool;
+}
+
+/// A `SymbolRule` that uses a regular expression to match acceptable
+/// symbols.
+#[derive(Debug)]
+pub struct PatternRule {
+    pattern: Regex,
+    description: String,
+}
+
+/// A set of `SymbolRule`s keyed by market identifier (MIC). Markets with
+/// no registered rule accept any syntactically valid symbol.
+pub struct SymbolRules {
+    rules: HashMap<String, Box<dyn SymbolRule + Send + Sync>>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+/// is the symbol valid; see `validate` for the reason a symbol is not.
+#[inline(always)]
+pub fn is_valid(symbol: &str) -> bool {
+    validate(symbol).is_ok()
+}
+
+/// Validate the common symbol syntax, returning the reason for any failure.
+/// Case is not significant.
+pub fn validate(symbol: &str) -> Result<(), SymbolError> {
+    if symbol.is_empty() {
+        return Err(SymbolError::EmptyString);
+    }
+    let length = symbol.chars().count();
+    if length > MAX_SYMBOL_LENGTH {
+        return Err(SymbolError::TooLong(length));
+    }
+    let unprefixed = match symbol.strip_prefix(INDEX_PREFIX) {
+        Some(rest) if !rest.is_empty() => rest,
+        _ => symbol,
+    };
+    for (i, c) in unprefixed.chars().enumerate() {
+        let valid = c.is_ascii_alphanumeric() || (i > 0 && SEPARATORS.contains(c));
+        if !valid {
+            return Err(SymbolError::InvalidCharacter(c));
+        }
+    }
+    Ok(())
+}
+
+/// is `market` a syntactically valid MIC; four upper case letters or digits.
+pub fn is_valid_market(market: &str) -> bool {
+    market.len() == 4
+        && market
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
 // ------------------------------------------------------------------------------------------------
 // Macros
 // ------------------------------------------------------------------------------------------------
 
 /// Short-cut to test whether a `Symbol` is valid, and if not to return
-/// a `RequestResult` containing the error `RequestError::BadSymbolError`.
+/// a `RequestResult` containing an error of kind `RequestErrorKind::BadSymbolError`
+/// with the reason the symbol was rejected.
 ///
-/// This macro *requires* that `is_valid` and `request::RequestError` are
-/// in scope where the assertion is made.
-/// ## Example
+/// The single argument form checks the common symbol syntax, the second
+/// form takes a `SymbolRules` and a market identifier and also checks any
+/// market-specific rule.
 ///
-/// The following example shoes the use of the macro, and specifically the
-/// necessary imports.
+/// ## Example
 ///
 /// ```rust
-/// use fin_model::request::{RequestError, RequestResult};
-/// use fin_model::symbol::is_valid;
+/// # #[macro_use] extern crate fin_model;
+/// use fin_model::prelude::*;
+/// use fin_model::symbol::SymbolRules;
 ///
-/// fn latest_price_only(&self, for_symbol: Symbol) -> RequestResult<f32> {
-///     assert_is_valid!(for_symbol);
+/// fn latest_price_only(for_symbol: Symbol) -> RequestResult<f32> {
+///     assert_is_valid!(for_symbol, SymbolRules::default(), "XNAS");
 ///     Ok(0.0)
 /// }
+/// # fn main() {
+/// assert!(latest_price_only("AAPL".parse().unwrap()).is_ok());
+/// assert!(latest_price_only("GOOGLE".parse().unwrap()).is_err());
+/// # }
 /// ```
 #[macro_export]
 macro_rules! assert_is_valid {
     ($symbol:expr) => {
-        match is_valid($symbol.to_string()) {
-            true => (),
-            false => return Err(RequestError::BadSymbolError($symbol)),
-        };
+        if let Err(err) = $crate::symbol::validate($symbol.as_ref()) {
+            return Err($crate::request::RequestError::new(
+                $crate::request::RequestErrorKind::BadSymbolError(format!("{}, {}", $symbol, err)),
+            ));
+        }
+    };
+    ($symbol:expr, $rules:expr, $market:expr) => {
+        $crate::assert_is_valid!($symbol);
+        if let Err(err) = $rules.validate_for($market, &$symbol) {
+            return Err($crate::request::RequestError::new(
+                $crate::request::RequestErrorKind::BadSymbolError(format!("{}, {}", $symbol, err)),
+            ));
+        }
     };
 }
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+const SEPARATORS: &str = ".-/^=";
+
+/// Prefix commonly used to distinguish index symbols, as in `^GSPC`.
+const INDEX_PREFIX: char = '^';
+
+impl Symbol {
+    /// Return the symbol as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Symbol {
+    type Err = SymbolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        validate(s)?;
+        Ok(Symbol(s.to_ascii_uppercase()))
+    }
+}
+
+impl TryFrom<String> for Symbol {
+    type Error = SymbolError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Symbol::from_str(&s)
+    }
+}
+
+impl From<Symbol> for String {
+    fn from(symbol: Symbol) -> Self {
+        symbol.0
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl QualifiedSymbol {
+    /// Create a new qualified symbol, validating the market identifier syntax.
+    pub fn new(market: &str, symbol: Symbol) -> Result<Self, SymbolError> {
+        if is_valid_market(market) {
+            Ok(QualifiedSymbol {
+                market: market.to_string(),
+                symbol,
+            })
+        } else {
+            Err(SymbolError::InvalidMarket(market.to_string()))
+        }
+    }
+
+    /// Parse a qualified symbol string, and ensure that the market is present
+    /// in the provided market `registry`.
+    pub fn parse_in<R>(s: &str, registry: &R) -> Result<Self, SymbolError>
+    where
+        R: Registry<String, Market>,
+    {
+        let qualified = QualifiedSymbol::from_str(s)?;
+        qualified.check_market(registry)?;
+        Ok(qualified)
+    }
+
+    /// Ensure that the market is present in the provided market `registry`.
+    pub fn check_market<R>(&self, registry: &R) -> Result<(), SymbolError>
+    where
+        R: Registry<String, Market>,
+    {
+        match registry.get(self.market.clone()) {
+            Some(_) => Ok(()),
+            None => Err(SymbolError::UnknownMarket(self.market.clone())),
+        }
+    }
+}
+
+impl Display for QualifiedSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}{}", self.market, MARKET_SEPARATOR, self.symbol)
+    }
+}
+
+impl FromStr for QualifiedSymbol {
+    type Err = SymbolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(SymbolError::EmptyString);
+        }
+        match s.find(MARKET_SEPARATOR) {
+            None => Err(SymbolError::MissingMarket),
+            Some(idx) => QualifiedSymbol::new(
+                &s[..idx].to_ascii_uppercase(),
+                Symbol::from_str(&s[idx + 1..])?,
+            ),
+        }
+    }
+}
+
+impl TryFrom<String> for QualifiedSymbol {
+    type Error = SymbolError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        QualifiedSymbol::from_str(&s)
+    }
+}
+
+impl From<QualifiedSymbol> for String {
+    fn from(symbol: QualifiedSymbol) -> Self {
+        symbol.to_string()
+    }
+}
+
+impl Display for SymbolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SymbolError::EmptyString => write!(f, "symbol is empty"),
+            SymbolError::TooLong(length) => write!(
+                f,
+                "symbol length {} is greater than the maximum {}",
+                length, MAX_SYMBOL_LENGTH
+            ),
+            SymbolError::InvalidCharacter(c) => {
+                write!(f, "symbol contains the invalid character '{}'", c)
+            }
+            SymbolError::MissingMarket => {
+                write!(f, "qualified symbol must be in the form 'MIC:SYMBOL'")
+            }
+            SymbolError::InvalidMarket(market) => {
+                write!(f, "'{}' is not a valid market identifier", market)
+            }
+            SymbolError::UnknownMarket(market) => {
+                write!(f, "'{}' is not a registered market identifier", market)
+            }
+            SymbolError::MarketRuleFailed(market, rule) => {
+                write!(f, "symbol does not match the {} rule: {}", market, rule)
+            }
+        }
+    }
+}
+
+impl PatternRule {
+    /// Create a new rule from a regular expression; the expression is
+    /// anchored so that it must match the entire symbol.
+    pub fn new(pattern: &str, description: &str) -> Result<Self, regex::Error> {
+        Ok(PatternRule {
+            pattern: Regex::new(&format!("^(?:{})$", pattern))?,
+            description: description.to_string(),
+        })
+    }
+}
+
+impl SymbolRule for PatternRule {
+    fn description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn accepts(&self, symbol: &Symbol) -> bool {
+        self.pattern.is_match(symbol.as_str())
+    }
+}
+
+impl SymbolRules {
+    /// Create a rule set with no market rules.
+    pub fn new() -> Self {
+        SymbolRules {
+            rules: HashMap::new(),
+        }
+    }
+
+    /// Add, or replace, the rule for the market `mic`.
+    pub fn add_rule(&mut self, mic: &str, rule: Box<dyn SymbolRule + Send + Sync>) {
+        let _ = self.rules.insert(mic.to_string(), rule);
+    }
+
+    /// Return `true` if there is a rule for the market `mic`.
+    pub fn has_rule(&self, mic: &str) -> bool {
+        self.rules.contains_key(mic)
+    }
+
+    /// Validate the qualified symbol against the rule for its market.
+    pub fn validate(&self, symbol: &QualifiedSymbol) -> Result<(), SymbolError> {
+        self.validate_for(&symbol.market, &symbol.symbol)
+    }
+
+    /// Validate `symbol` against the rule for the market `mic`, if any.
+    pub fn validate_for(&self, mic: &str, symbol: &Symbol) -> Result<(), SymbolError> {
+        match self.rules.get(mic) {
+            Some(rule) if !rule.accepts(symbol) => Err(SymbolError::MarketRuleFailed(
+                mic.to_string(),
+                rule.description(),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for SymbolRules {
+    /// A rule set with rules for some of the major US and European markets.
+    fn default() -> Self {
+        let mut rules = SymbolRules::new();
+        for (mic, pattern, description) in DEFAULT_RULES.iter() {
+            rules.add_rule(
+                mic,
+                Box::new(PatternRule::new(pattern, description).unwrap()),
+            );
+        }
+        rules
+    }
+}
+
+const DEFAULT_RULES: [(&str, &str, &str); 5] = [
+    ("XNAS", r"[A-Z]{1,5}", "one to five letters"),
+    (
+        "XNYS",
+        r"[A-Z]{1,4}([.\-][A-Z]{1,2})?",
+        "one to four letters, with an optional class suffix",
+    ),
+    (
+        "ARCX",
+        r"[A-Z]{1,4}([.\-][A-Z]{1,2})?",
+        "one to four letters, with an optional class suffix",
+    ),
+    (
+        "XLON",
+        r"[A-Z0-9]{2,4}\.?",
+        "two to four letters or digits, with an optional trailing '.'",
+    ),
+    ("XETR", r"[A-Z0-9]{2,4}", "two to four letters or digits"),
+];
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbol_from_string() {
+        assert_eq!(Symbol::from_str("aapl").unwrap().to_string(), "AAPL");
+        assert_eq!(Symbol::from_str("BRK.B").unwrap().as_str(), "BRK.B");
+        assert_eq!(Symbol::from_str("^gspc").unwrap().as_str(), "^GSPC");
+        assert_eq!(Symbol::from_str("^DJI").unwrap().as_str(), "^DJI");
+        assert_eq!(
+            Symbol::from_str("^"),
+            Err(SymbolError::InvalidCharacter('^'))
+        );
+        assert_eq!(
+            Symbol::from_str("^^DJI"),
+            Err(SymbolError::InvalidCharacter('^'))
+        );
+        assert_eq!(Symbol::from_str("EURUSD=X").unwrap().as_str(), "EURUSD=X");
+
+        assert_eq!(Symbol::from_str(""), Err(SymbolError::EmptyString));
+        assert_eq!(
+            Symbol::from_str("ABCDEFGHIJKLM"),
+            Err(SymbolError::TooLong(13))
+        );
+        assert_eq!(
+            Symbol::from_str("AB CD"),
+            Err(SymbolError::InvalidCharacter(' '))
+        );
+    }
+
+    #[test]
+    fn test_qualified_from_string() {
+        let qualified = QualifiedSymbol::from_str("xnas:aapl").unwrap();
+        assert_eq!(qualified.market, "XNAS");
+        assert_eq!(qualified.symbol.as_str(), "AAPL");
+        assert_eq!(qualified.to_string(), "XNAS:AAPL");
+
+        assert_eq!(
+            QualifiedSymbol::from_str("AAPL"),
+            Err(SymbolError::MissingMarket)
+        );
+        assert_eq!(
+            QualifiedSymbol::from_str("NASDAQ:AAPL"),
+            Err(SymbolError::InvalidMarket("NASDAQ".to_string()))
+        );
+        assert_eq!(
+            QualifiedSymbol::from_str("XNAS:"),
+            Err(SymbolError::EmptyString)
+        );
+    }
+
+    #[test]
+    fn test_market_rules() {
+        let rules = SymbolRules::default();
+        assert!(rules
+            .validate(&QualifiedSymbol::from_str("XNYS:BRK.B").unwrap())
+            .is_ok());
+        assert!(rules
+            .validate(&QualifiedSymbol::from_str("XLON:BP.").unwrap())
+            .is_ok());
+        assert_eq!(
+            rules.validate(&QualifiedSymbol::from_str("XNAS:BRK.B").unwrap()),
+            Err(SymbolError::MarketRuleFailed(
+                "XNAS".to_string(),
+                "one to five letters".to_string()
+            ))
+        );
+
+        let mut rules = SymbolRules::new();
+        assert!(rules
+            .validate(&QualifiedSymbol::from_str("XNAS:BRK.B").unwrap())
+            .is_ok());
+        rules.add_rule(
+            "XTKS",
+            Box::new(PatternRule::new(r"\d{4}", "four digits").unwrap()),
+        );
+        assert!(rules
+            .validate(&QualifiedSymbol::from_str("XTKS:7203").unwrap())
+            .is_ok());
+        assert!(rules
+            .validate(&QualifiedSymbol::from_str("XTKS:TM").unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn test_assert_is_valid() {
+        use crate::request::{RequestErrorKind, RequestResult};
+
+        fn check(symbol: Symbol) -> RequestResult<()> {
+            assert_is_valid!(symbol, SymbolRules::default(), "XNAS");
+            Ok(())
+        }
+
+        assert!(check(Symbol::from_str("MSFT").unwrap()).is_ok());
+        match check(Symbol::from_str("BRK.B").unwrap()).map_err(|e| e.kind().clone()) {
+            Err(RequestErrorKind::BadSymbolError(reason)) => {
+                assert!(reason.starts_with("BRK.B, symbol does not match the XNAS rule"))
+            }
+            _ => panic!("expected BadSymbolError"),
+        }
+    }
+}